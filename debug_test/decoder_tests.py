@@ -0,0 +1,331 @@
+#!/usr/bin/env python3
+"""
+Property tests for the string decoders, no debugger needed.
+
+Random strings are encoded into the exact bytes SmolStr (in its 0.1 and
+current layouts), CompactString and EcoString use in memory, decoded through
+a ValueReader over fake target memory and compared with the original:
+
+    python3 decoder_tests.py [seed]
+
+LLDB's Python module is used if it is on the path; otherwise a minimal
+stand-in is installed, the decoders only need a few of its constants.
+"""
+
+from __future__ import annotations
+
+import os
+import random
+import sys
+import traceback
+import types
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+sys.path.insert(0, os.path.join(HERE, ".."))
+
+try:
+    import lldb
+except ImportError:
+
+    class _StubError:
+        def __init__(self):
+            self.message = None
+
+        def Success(self):
+            return self.message is None
+
+        def SetErrorString(self, message):
+            self.message = message
+
+    lldb = types.ModuleType("lldb")
+    lldb.SBError = _StubError
+    lldb.SBValue = object
+    lldb.eByteOrderLittle = 4
+    lldb.eByteOrderBig = 1
+    lldb.LLDB_INVALID_ADDRESS = 0xFFFFFFFFFFFFFFFF
+    # Formatter kinds and options only matter when registering providers
+    lldb.__getattr__ = lambda name: 0
+    sys.modules["lldb"] = lldb
+
+import rust_bonus_types as formatter  # noqa: E402
+
+# Characters of 1 to 4 bytes in UTF-8
+ALPHABET = "abcxyz019 _-" + "éßü" + "€漢字" + "😀🦀"
+
+# Where the fake allocations live, far from the values themselves
+HEAP_BASE = 0x10000
+
+
+class FakeProcess:
+    """Target memory as a few regions, with the ReadMemory of SBProcess"""
+
+    def __init__(self):
+        self.regions = {}
+        self.next_address = HEAP_BASE
+
+    def allocate(self, data: bytes) -> int:
+        address = self.next_address
+        self.regions[address] = data
+        self.next_address += (len(data) + 0x100) & ~0xFF
+        return address
+
+    def ReadMemory(self, address, size, error):
+        for base, data in self.regions.items():
+            if base <= address and address + size <= base + len(data):
+                return data[address - base : address - base + size]
+        error.SetErrorString("unmapped memory at 0x%x" % address)
+        return None
+
+
+def random_text(rng: random.Random, byte_length: int) -> str:
+    """A random string of exactly byte_length UTF-8 bytes"""
+    text = ""
+    remaining = byte_length
+    while remaining:
+        choices = [c for c in ALPHABET if len(c.encode()) <= remaining]
+        char = rng.choice(choices)
+        text += char
+        remaining -= len(char.encode())
+    return text
+
+
+# Inline capacity and the Static and Heap discriminants of each smol_str
+# layout. 0.3 kept the 0.2 one.
+SMOLSTR_VERSIONS = {
+    "0.2": (23, 24, 25),
+    "0.1": (22, 24, 23),
+}
+
+
+def smolstr_layout(word: int, version: str = "0.2"):
+    """The SmolStrLayout rustc produces: tag byte, inline buf, fat pointer"""
+    inline_capacity, static_discr, heap_discr = SMOLSTR_VERSIONS[version]
+    fat = (0, word, 2 * word)
+    return types.SimpleNamespace(
+        discr_offset=0,
+        discr_size=1,
+        inline_offset=1,
+        inline_capacity=inline_capacity,
+        static_discr=static_discr,
+        static_offset=word,
+        static_fields=fat,
+        heap_discr=heap_discr,
+        heap_offset=word,
+        heap_fields=fat,
+        arc_data_offset=2 * word,
+    )
+
+
+def encode_smolstr(
+    process: FakeProcess, text: str, word: int, static: bool, version: str = "0.2"
+):
+    """Lays out a SmolStr like SmolStr::new (or new_static) would"""
+    inline_capacity, static_discr, heap_discr = SMOLSTR_VERSIONS[version]
+    data = text.encode()
+    value = bytearray(24)
+    if len(data) <= inline_capacity and not static:
+        value[0] = len(data)
+        value[1 : 1 + len(data)] = data
+        return bytes(value)
+
+    if static:
+        value[0] = static_discr
+        pointer = process.allocate(data)
+    else:
+        value[0] = heap_discr
+        counts = (1).to_bytes(word, "little") * 2
+        pointer = process.allocate(counts + data)
+    value[word : 2 * word] = pointer.to_bytes(word, "little")
+    value[2 * word : 3 * word] = len(data).to_bytes(word, "little")
+    return bytes(value)
+
+
+def encode_compact_string(process: FakeProcess, text: str, word: int, static: bool):
+    """Lays out a CompactString of three words like CompactString::new would"""
+    data = text.encode()
+    size = 3 * word
+    if len(data) < size and not static:
+        value = bytearray(size)
+        value[: len(data)] = data
+        value[-1] = formatter.COMPACT_STR_INLINE_TAG + len(data)
+        return bytes(value)
+    if len(data) == size and not static:
+        return data
+
+    pointer = process.allocate(data)
+    tag = formatter.COMPACT_STR_STATIC_TAG if static else formatter.COMPACT_STR_HEAP_TAG
+    # The capacity word carries the tag in its last byte
+    capacity = len(data).to_bytes(word - 1, "little") + bytes([tag])
+    return (
+        pointer.to_bytes(word, "little") + len(data).to_bytes(word, "little") + capacity
+    )
+
+
+def encode_eco_string(process: FakeProcess, text: str, word: int):
+    """Lays out an EcoString of two words like EcoString::from would"""
+    data = text.encode()
+    size = 2 * word
+    if len(data) < size:
+        value = bytearray(size)
+        value[: len(data)] = data
+        value[-1] = formatter.ECOW_LEN_TAG | len(data)
+        return bytes(value)
+
+    # The EcoVec header, refcount and capacity, sits in front of the bytes
+    header = (1).to_bytes(word, "little") + len(data).to_bytes(word, "little")
+    pointer = process.allocate(header + data) + len(header)
+    return pointer.to_bytes(word, "little") + len(data).to_bytes(word, "little")
+
+
+def reader_for(process: FakeProcess, value: bytes, word: int, in_memory: bool):
+    if in_memory:
+        address = process.allocate(value)
+        return formatter.ValueReader(process, address, "little", word)
+    return formatter.ValueReader(process, 0, "little", word, raw=value)
+
+
+def check_smolstr(rng: random.Random, byte_length: int, word: int, version: str):
+    inline_capacity = SMOLSTR_VERSIONS[version][0]
+    for static in (False, True):
+        for in_memory in (True, False):
+            process = FakeProcess()
+            text = random_text(rng, byte_length)
+            value = encode_smolstr(process, text, word, static, version)
+            reader = reader_for(process, value, word, in_memory)
+            decoded, parts = formatter._smolstr_text_from(
+                reader, smolstr_layout(word, version)
+            )
+
+            if static:
+                variant = "Static"
+            else:
+                variant = "Inline" if byte_length <= inline_capacity else "Heap"
+            if decoded != text or parts.variant != variant:
+                raise AssertionError(
+                    "SmolStr %s %r (%d-bit, %s): decoded %r as %s"
+                    % (version, text, word * 8, variant, decoded, parts.variant)
+                )
+            if parts.length != byte_length:
+                raise AssertionError(
+                    "SmolStr %r: length %d, expected %d"
+                    % (text, parts.length, byte_length)
+                )
+
+
+def check_compact_string(rng: random.Random, byte_length: int, word: int):
+    size = 3 * word
+    for static in (False, True):
+        for in_memory in (True, False):
+            process = FakeProcess()
+            text = random_text(rng, byte_length)
+            value = encode_compact_string(process, text, word, static)
+            reader = reader_for(process, value, word, in_memory)
+            decoded, variant = formatter._compact_string_text_from(reader, size)
+
+            if static:
+                expected = "static"
+            else:
+                expected = "inline" if byte_length <= size else "heap"
+            if decoded != text or variant != expected:
+                raise AssertionError(
+                    "CompactString %r (%d-bit, %s): decoded %r as %s"
+                    % (text, word * 8, expected, decoded, variant)
+                )
+
+
+def check_eco_string(rng: random.Random, byte_length: int, word: int):
+    size = 2 * word
+    for in_memory in (True, False):
+        process = FakeProcess()
+        text = random_text(rng, byte_length)
+        value = encode_eco_string(process, text, word)
+        reader = reader_for(process, value, word, in_memory)
+        decoded, variant = formatter._tagged_string_text_from(
+            reader, size, formatter.ECOW_SCHEME
+        )
+
+        expected = "inline" if byte_length < size else "heap"
+        if decoded != text or variant != expected:
+            raise AssertionError(
+                "EcoString %r (%d-bit, %s): decoded %r as %s"
+                % (text, word * 8, expected, decoded, variant)
+            )
+
+
+def boundary_lengths(*boundaries):
+    """Every length up to a bit past the largest boundary"""
+    return range(0, max(boundaries) + 16)
+
+
+def smolstr_round_trips(rng: random.Random):
+    for version in SMOLSTR_VERSIONS:
+        for word in (4, 8):
+            for byte_length in boundary_lengths(23, 24):
+                for _ in range(8):
+                    check_smolstr(rng, byte_length, word, version)
+
+
+def smolstr_fallback_matches_current_layout(_rng: random.Random):
+    # Used when the debug info has no Repr variants to read the layout from
+    for word in (4, 8):
+        fallback = formatter.smolstr_fallback_layout(24, word)
+        expected = vars(smolstr_layout(word))
+        actual = {name: getattr(fallback, name) for name in expected}
+        if actual != expected:
+            raise AssertionError("fallback layout %r, expected %r" % (actual, expected))
+    if formatter.smolstr_fallback_layout(32, 8) is not None:
+        raise AssertionError("expected no fallback for a 32 byte SmolStr")
+
+
+def compact_string_round_trips(rng: random.Random):
+    for word in (4, 8):
+        for byte_length in boundary_lengths(3 * word):
+            for _ in range(8):
+                check_compact_string(rng, byte_length, word)
+
+
+def eco_string_round_trips(rng: random.Random):
+    for word in (4, 8):
+        for byte_length in boundary_lengths(2 * word - 1):
+            for _ in range(8):
+                check_eco_string(rng, byte_length, word)
+
+
+def unreadable_heap_text_is_none(rng: random.Random):
+    process = FakeProcess()
+    value = encode_smolstr(process, random_text(rng, 40), 8, False)
+    process.regions = {}
+    reader = formatter.ValueReader(process, 0, "little", 8, raw=value)
+    text, parts = formatter._smolstr_text_from(reader, smolstr_layout(8))
+    if text is not None or parts.variant != "Heap":
+        raise AssertionError("expected an unreadable heap string, got %r" % text)
+
+
+TESTS = [
+    smolstr_round_trips,
+    smolstr_fallback_matches_current_layout,
+    compact_string_round_trips,
+    eco_string_round_trips,
+    unreadable_heap_text_is_none,
+]
+
+
+def main(argv) -> int:
+    seed = int(argv[1]) if len(argv) > 1 else random.randrange(1 << 32)
+    print("seed %d" % seed)
+    failures = 0
+    for func in TESTS:
+        try:
+            func(random.Random(seed))
+            print("PASS %s" % func.__name__)
+        except Exception:
+            failures += 1
+            print("FAIL %s" % func.__name__)
+            traceback.print_exc()
+
+    print("%d failed" % failures if failures else "all passed")
+    return 1 if failures else 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv))
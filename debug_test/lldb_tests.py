@@ -0,0 +1,2080 @@
+#!/usr/bin/env python3
+"""
+Runs the debug_test binaries under LLDB and checks the formatter output.
+
+Build the binaries first, then run with LLDB's Python module on the path:
+
+    cargo build --bins
+    PYTHONPATH="$(lldb -P)" python3 lldb_tests.py [test-name ...]
+
+Each test launches a binary, stops at its breakpoint function and selects
+the caller's frame, where the sample values live.
+
+Golden tests compare rendered values with the files in golden/. After an
+intended formatting change, regenerate them and review the diff:
+
+    PYTHONPATH="$(lldb -P)" python3 lldb_tests.py --update [test-name ...]
+"""
+
+from __future__ import annotations
+
+import difflib
+import json
+import os
+import shutil
+import subprocess
+import sys
+import time
+import traceback
+
+import lldb
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+FORMATTER = os.path.join(HERE, "..", "rust_bonus_types.py")
+BINARIES = os.path.join(HERE, "target", "debug")
+GOLDEN = os.path.join(HERE, "golden")
+
+# 32-bit target for the pointer width tests, built with
+# `cargo build --bins --target i686-unknown-linux-gnu`
+TARGET_32BIT = "i686-unknown-linux-gnu"
+
+TESTS = []
+
+# Set by --update: golden tests rewrite their files instead of comparing
+UPDATE_GOLDEN = False
+
+
+class SkipTest(Exception):
+    """Raised when a test can't run here, e.g. its binary isn't built"""
+
+
+def test(
+    binary: str,
+    breakpoint: str = "inspect_variables",
+    frame: int = 1,
+    triple: str | None = None,
+):
+    """
+    Registers a test function run against a stopped binary
+
+    With triple, the binary built for that target is used, and the test is
+    skipped if it hasn't been built.
+    """
+
+    def register(func):
+        TESTS.append((func.__name__, binary, breakpoint, frame, triple, func))
+        return func
+
+    return register
+
+
+class Session:
+    """An LLDB session stopped at a breakpoint in one of the test binaries"""
+
+    def __init__(self, binary: str, breakpoint: str, frame: int, triple=None):
+        path = os.path.join(BINARIES, binary)
+        if triple is not None:
+            path = os.path.join(HERE, "target", triple, "debug", binary)
+            if not os.path.exists(path):
+                raise SkipTest("run `cargo build --bins --target %s`" % triple)
+
+        self.debugger = lldb.SBDebugger.Create()
+        self.debugger.SetAsync(False)
+        self.command("command script import %s" % FORMATTER)
+
+        self.target = self.debugger.CreateTarget(path)
+        if not self.target.IsValid():
+            raise AssertionError("could not load %s, run `cargo build --bins`" % binary)
+
+        self.target.BreakpointCreateByName(breakpoint)
+        self.process = self.target.LaunchSimple(None, None, HERE)
+        if self.process.GetState() != lldb.eStateStopped:
+            raise AssertionError("%s did not stop at %s" % (binary, breakpoint))
+
+        self.process.GetSelectedThread().SetSelectedFrame(frame)
+
+    def command(self, text: str) -> str:
+        ret = lldb.SBCommandReturnObject()
+        self.debugger.GetCommandInterpreter().HandleCommand(text, ret)
+        return (ret.GetOutput() or "") + (ret.GetError() or "")
+
+    def variable(self, name: str) -> lldb.SBValue:
+        frame = self.process.GetSelectedThread().GetSelectedFrame()
+        valobj = frame.GetValueForVariablePath(name)
+        if not valobj.IsValid():
+            raise AssertionError("no variable named %s" % name)
+        return valobj
+
+    def summary(self, name: str) -> str:
+        return self.variable(name).GetSummary() or ""
+
+    def close(self):
+        self.process.Kill()
+        lldb.SBDebugger.Destroy(self.debugger)
+
+
+def expect_in(output: str, expected: str):
+    if expected not in output:
+        raise AssertionError("expected %r in output:\n%s" % (expected, output))
+
+
+def expect_eq(actual, expected):
+    if actual != expected:
+        raise AssertionError("expected %r, got %r" % (expected, actual))
+
+
+def expect_no_tail_reads(session: Session, name: str, array_path, length: int):
+    """
+    Formats a value backed by an inline array and fails if any memory read
+    of the formatter or any child touches the slots past length
+
+    array_path names the fields leading from the value to its array. The
+    tail is uninitialized or stale, a sanitizer would flag reading it.
+    """
+    valobj = session.variable(name)
+    array = valobj.GetNonSyntheticValue()
+    for field in array_path:
+        array = array.GetChildMemberWithName(field)
+    element_size = array.GetByteSize() // array.GetNumChildren()
+    start = array.GetLoadAddress() + length * element_size
+    end = array.GetLoadAddress() + array.GetByteSize()
+
+    # The formatter module lives in this interpreter, imported by the session
+    module = sys.modules["rust_bonus_types"]
+    read_memory = module._read_memory
+    reads = []
+
+    def recording_read(process, address, size):
+        reads.append((address, size))
+        return read_memory(process, address, size)
+
+    module._read_memory = recording_read
+    try:
+        valobj.GetSummary()
+        for i in range(valobj.GetNumChildren()):
+            child = valobj.GetChildAtIndex(i)
+            reads.append((child.GetLoadAddress(), child.GetByteSize()))
+            child.GetSummary()
+    finally:
+        module._read_memory = read_memory
+
+    for address, size in reads:
+        if address < end and address + size > start:
+            raise AssertionError(
+                "%s: read of %d bytes at 0x%x overlaps the tail 0x%x..0x%x"
+                % (name, size, address, start, end)
+            )
+
+
+def render(session: Session, path: str) -> str:
+    """One golden line: the summary of path, or its value if it has none"""
+    value = session.variable(path)
+    return "%s = %s" % (path, value.GetSummary() or value.GetValue() or "")
+
+
+def golden(name: str, binary: str, paths, **kwargs):
+    """
+    Registers a test comparing the rendering of paths with golden/<name>.txt
+
+    The file holds one `path = summary` line per variable path. With
+    --update the test writes what LLDB renders now and passes.
+    """
+
+    def check(session: Session):
+        actual = "".join(render(session, path) + "\n" for path in paths)
+        filename = os.path.join(GOLDEN, name + ".txt")
+        if UPDATE_GOLDEN:
+            os.makedirs(GOLDEN, exist_ok=True)
+            with open(filename, "w", encoding="utf-8") as f:
+                f.write(actual)
+            return
+        try:
+            with open(filename, encoding="utf-8") as f:
+                expected = f.read()
+        except FileNotFoundError:
+            raise AssertionError("no %s, run with --update to create it" % filename)
+        if actual != expected:
+            diff = difflib.unified_diff(
+                expected.splitlines(True),
+                actual.splitlines(True),
+                filename,
+                "lldb",
+            )
+            raise AssertionError("golden mismatch:\n" + "".join(diff))
+
+    check.__name__ = "golden_" + name
+    test(binary, **kwargs)(check)
+
+
+@test("smolstr_debug_test")
+def formatter_debug_reports_smolstr_variant(session: Session):
+    output = session.command("rust-formatter-debug inline_short")
+    expect_in(output, "type: smol_str::SmolStr")
+    expect_in(output, "summary provider: rust_bonus_types.SmolStrSummaryProvider")
+    expect_in(output, "discriminant: +0x")
+    expect_in(output, 'variant = "Inline"')
+
+    output = session.command("rust-formatter-debug heap_long")
+    expect_in(output, 'variant = "Heap"')
+
+
+@test("smolstr_debug_test")
+def smallvec_edge_inline_capacities(session: Session):
+    expect_eq(session.summary("zero_cap_empty"), "size=0 []")
+    expect_eq(session.summary("zero_cap_spilled"), "size=3 [7, 8, 9]")
+    expect_eq(session.variable("one_cap_inline").GetNumChildren(), 1)
+    expect_eq(session.variable("one_cap_spilled").GetNumChildren(), 2)
+
+
+@test("smallvec_elements_test")
+def smallvec_strings_inline_and_spilled(session: Session):
+    expect_eq(session.summary("inline_strings"), 'size=2 ["alpha", "beta"]')
+    expect_eq(session.summary("spilled_strings"), 'size=3 ["one", "two", "three"]')
+    spilled = session.variable("spilled_strings")
+    expect_eq(spilled.GetNumChildren(), 3)
+    expect_eq(spilled.GetChildAtIndex(2).GetSummary(), '"three"')
+    expect_eq(
+        session.variable("inline_strings").GetChildAtIndex(0).GetTypeName(),
+        "alloc::string::String",
+    )
+
+
+@test("smallvec_elements_test")
+def smallvec_structs_expose_fields(session: Session):
+    expect_eq(
+        session.summary("inline_points"),
+        "size=2 [Point { x: 1, y: 2 }, Point { x: -3, y: 4 }]",
+    )
+    expect_eq(
+        session.summary("spilled_points"),
+        "size=2 [Point { x: 5, y: 6 }, Point { x: 7, y: 8 }]",
+    )
+    second = session.variable("spilled_points").GetChildAtIndex(1)
+    expect_eq(second.GetChildMemberWithName("y").GetValue(), "8")
+
+
+@test("bitvec_test")
+def bitvec_bits_in_index_order(session: Session):
+    expect_eq(session.summary("lsb"), "size=10 0b1011001011")
+    expect_eq(session.summary("msb"), "size=12 0b110100011011")
+    expect_eq(session.summary("empty"), "size=0")
+    # The slice starts at bit 9 of its first element
+    expect_eq(session.summary("tail"), "size=3 0b011")
+
+
+@test("bitvec_test")
+def bitvec_bits_are_bool_children(session: Session):
+    lsb = session.variable("lsb")
+    expect_eq(lsb.GetNumChildren(), 10)
+    expect_eq(
+        [lsb.GetChildAtIndex(i).GetValue() for i in range(4)],
+        ["true", "false", "true", "true"],
+    )
+    expect_eq(lsb.GetChildMemberWithName("[9]").GetValue(), "true")
+    tail = session.variable("tail")
+    expect_eq(tail.GetNumChildren(), 3)
+    expect_eq(tail.GetChildAtIndex(0).GetValue(), "false")
+
+
+@test("bitvec_test")
+def bitvec_summary_bounded_by_preview_budget(session: Session):
+    summary = session.summary("many")
+    expect_eq(summary, "size=300 0b" + "100" * 26 + "10...")
+    expect_eq(session.variable("many").GetChildAtIndex(297).GetValue(), "true")
+
+
+@test("bitvec_test")
+def bitvec_slice_shows_its_address(session: Session):
+    session.command("rust-formatter-set show-addresses true")
+    # The low bit of the pointer is part of the head, not the address
+    tail = session.variable("tail").GetNonSyntheticValue()
+    address = tail.GetChildMemberWithName("data_ptr").GetValueAsUnsigned() & ~1
+    expect_eq(session.summary("tail"), "size=3 0b011 @0x%x" % address)
+    # Owned BitVecs are values, without an address
+    expect_eq(session.summary("lsb"), "size=10 0b1011001011")
+    expect_eq(session.summary("empty"), "size=0")
+
+
+@test("ratio_test")
+def ratios_render_as_fractions(session: Session):
+    expect_eq(session.summary("three_quarters"), "3/4")
+    expect_eq(session.summary("whole"), "5")
+    expect_eq(session.summary("bytes"), "200/3")
+    expect_eq(session.summary("wide"), "-%d/3" % (1 << 100))
+
+
+@test("ratio_test")
+def ratio_sign_is_on_the_numerator(session: Session):
+    expect_eq(session.summary("negative"), "-3/4")
+    expect_eq(session.summary("raw_negative"), "-3/4")
+
+
+@test("inline_array_test")
+def inline_arrays_stop_at_their_length(session: Session):
+    expect_eq(session.summary("numbers"), "size=2 [1, 2]")
+    expect_eq(session.summary("strings"), 'size=1 ["kept"]')
+    expect_eq(session.summary("empty"), "size=0 []")
+    expect_eq(session.summary("tiny"), "size=1 [7]")
+    expect_eq(session.summary("small"), "size=3 [4, 5, 6]")
+    expect_eq(session.summary("text"), '"hello"')
+
+    strings = session.variable("strings")
+    expect_eq(strings.GetNumChildren(), 1)
+    expect_eq(strings.GetChildAtIndex(0).GetTypeName(), "alloc::string::String")
+    expect_eq(strings.GetChildAtIndex(1).IsValid(), False)
+
+
+@test("inline_array_test")
+def inline_array_tails_are_never_read(session: Session):
+    expect_no_tail_reads(session, "numbers", ["xs"], 2)
+    expect_no_tail_reads(session, "strings", ["xs"], 1)
+    expect_no_tail_reads(session, "empty", ["xs"], 0)
+    expect_no_tail_reads(session, "tiny", ["data"], 1)
+    small_inline = ["raw", "inline", "value", "value", "value"]
+    expect_no_tail_reads(session, "small", small_inline, 3)
+    expect_no_tail_reads(session, "text", ["xs"], 5)
+
+
+@test("inline_array_test")
+def zero_sized_inline_lengths_are_bounded(session: Session):
+    expect_eq(session.summary("units"), "size=3 [(), (), ()]")
+    # A corrupted len must not turn into a million children of no size
+    raw = session.variable("units").GetNonSyntheticValue()
+    raw.GetChildMemberWithName("len").SetValueFromCString("1000000")
+    expect_eq(session.summary("units"), "size=3 [(), (), ()]")
+    expect_eq(session.variable("units").GetNumChildren(), 3)
+
+
+@test("inline_array_test")
+def tinyvec_shows_the_storage_in_use(session: Session):
+    expect_eq(session.summary("tiny_inline"), "size=2 [1, 2]")
+    expect_eq(session.summary("tiny_heap"), "size=5 [10, 11, 12, 13, 14]")
+    expect_eq(session.variable("tiny_heap").GetNumChildren(), 5)
+
+    session.command("rust-formatter-set metadata true")
+    expect_eq(session.summary("tiny_inline"), "size=2 [1, 2] (inline)")
+    expect_eq(session.summary("tiny_heap"), "size=5 [10, 11, 12, 13, 14] (heap)")
+
+
+@test("chain_test")
+def option_box_vec_chain(session: Session):
+    expect_eq(session.summary("some_chain"), 'Some(size=2 ["alpha", "beta"])')
+    expect_eq(session.summary("none_chain"), "None")
+
+    payload = session.variable("some_chain").GetChildAtIndex(0)
+    expect_eq(payload.GetName(), "__0")
+
+
+@test("str_slice_test")
+def str_slices_use_fat_pointer_length(session: Session):
+    expect_eq(session.summary("full"), '"hello wörld"')
+    expect_eq(session.summary("middle"), '"llo"')
+    expect_eq(session.summary("multibyte"), '"wör"')
+    expect_eq(session.summary("empty"), '""')
+
+
+@test("str_slice_test")
+def boxed_str_reads_length_from_metadata(session: Session):
+    expect_eq(session.summary("boxed"), '"boxed wörds"')
+    expect_eq(session.summary("empty_boxed"), '""')
+
+
+@test("user_rules_test")
+def user_rule_makes_wrapper_transparent(session: Session):
+    output = session.command(
+        "rust-formatter-set user-rules %s" % os.path.join(HERE, "test.rules")
+    )
+    expect_in(output, "user-rules = ")
+    expect_eq(session.summary("wrapper"), '"wrapped"')
+
+
+@test("user_rules_test")
+def user_rules_read_packed_and_aligned_fields(session: Session):
+    session.command(
+        "rust-formatter-set user-rules %s" % os.path.join(HERE, "test.rules")
+    )
+    expect_eq(session.summary("packed"), '"packed"')
+    expect_eq(session.summary("aligned"), '"aligned"')
+
+
+@test("user_rules_test")
+def collection_elements_use_their_own_summary(session: Session):
+    session.command(
+        "rust-formatter-set user-rules %s" % os.path.join(HERE, "test.rules")
+    )
+    expect_eq(session.summary("items"), 'size=2 ["first", "second"]')
+    expect_eq(session.variable("items").GetChildAtIndex(1).GetSummary(), '"second"')
+
+
+@test("user_rules_test")
+def single_variant_enum_unwraps_to_its_payload(session: Session):
+    expect_eq(session.summary("handle"), 'V("handled")')
+    session.command(
+        "rust-formatter-set user-rules %s" % os.path.join(HERE, "test.rules")
+    )
+    expect_eq(session.summary("handle"), '"handled"')
+
+
+@test("arc_stress_test")
+def arc_counts_while_cloned_concurrently(session: Session):
+    summary = session.summary("shared")
+    expect_in(summary, "(strong=")
+    expect_in(summary, "size=3 [1, 2, 3]")
+
+    strong = session.variable("shared").GetChildMemberWithName("strong")
+    if strong.GetValueAsUnsigned() < 1:
+        raise AssertionError("strong count unreadable: %s" % summary)
+
+
+# option_test values, tagged and niche-packed alike
+golden(
+    "option",
+    "option_test",
+    [
+        "some_f32",
+        "none_f32",
+        "some_i32",
+        "none_i32",
+        "some_string",
+        "none_string",
+        "some_nonzero",
+        "none_nonzero",
+        "some_ref",
+        "none_ref",
+        "some_mut",
+        "none_mut",
+        "some_bytes",
+        "none_bytes",
+        "some_true",
+        "some_false",
+        "none_bool",
+        "some_char",
+        "none_char",
+        "nonzero_vec",
+        "tagged_vec",
+    ],
+)
+
+
+@test("compact_str_test")
+def compact_str_inline_heap_boundary(session: Session):
+    expect_eq(session.summary("inline_23"), '"abcdefghijklmnopqrstuvw"')
+    expect_eq(session.summary("inline_24"), '"abcdefghijklmnopqrstuvwx"')
+    expect_eq(session.summary("heap_25"), '"abcdefghijklmnopqrstuvwxy"')
+    expect_eq(session.summary("inline_24_multibyte"), '"abcdefghijklmnopqrstuvü"')
+    expect_eq(session.summary("static_str"), '"static string"')
+
+    session.command("rust-formatter-set metadata true")
+    expect_in(session.summary("inline_24"), "(inline)")
+    expect_in(session.summary("heap_25"), "(heap)")
+    expect_in(session.summary("static_str"), "(static)")
+
+
+@test("net_test")
+def socket_addresses_match_display(session: Session):
+    expect_eq(session.summary("link_local"), "[fe80::1%3]:443")
+    expect_eq(session.summary("no_scope"), "[2001:db8::8a2e:370:7334]:8080")
+    expect_eq(session.summary("mapped"), "::ffff:192.0.2.1")
+    expect_eq(session.summary("v4"), "192.0.2.1:80")
+
+
+@test("enum_test")
+def enum_struct_variant_named_children(session: Session):
+    expect_eq(session.summary("rect"), "Rect { w: 2.5, h: 4.0 }")
+    expect_eq(session.summary("circle"), "Circle(1.5)")
+    expect_eq(session.summary("empty"), "Empty")
+
+    rect = session.variable("rect")
+    expect_eq(rect.GetNumChildren(), 2)
+    expect_eq(rect.GetChildAtIndex(0).GetName(), "w")
+    expect_eq(rect.GetChildAtIndex(1).GetName(), "h")
+    expect_eq(rect.GetChildMemberWithName("h").GetValue(), "4")
+
+
+@test("range_test")
+def range_kinds_and_bounds(session: Session):
+    expect_eq(session.summary("range"), "1..5")
+    expect_eq(session.summary("range_inclusive"), "1..=5")
+    expect_eq(session.summary("range_from"), "3..")
+    expect_eq(session.summary("range_to"), "..7")
+    expect_eq(session.summary("range_to_inclusive"), "..=7")
+    expect_eq(session.summary("range_full"), "..")
+    expect_eq(session.summary("exhausted"), "1..=1 (exhausted)")
+    expect_eq(session.summary("included"), "Included(10)")
+    expect_eq(session.summary("excluded"), "Excluded(20)")
+    expect_eq(session.summary("unbounded"), "Unbounded")
+
+
+@test("smolstr_bench_test")
+def smolstr_vec_reads_per_element(session: Session):
+    strings = session.variable("strings")
+    count = strings.GetNumChildren()
+    expect_eq(count, 5000)
+
+    # The formatter module lives in this interpreter, imported by the session
+    stats = sys.modules["rust_bonus_types"].STATS
+    before = stats["memory_reads"]
+    expect_eq(strings.GetChildAtIndex(0).GetSummary(), '"item-0"')
+    for i in range(1, count):
+        strings.GetChildAtIndex(i).GetSummary()
+    reads = stats["memory_reads"] - before
+
+    # One read for the tag byte and one for the text of each inline string
+    print("  %d SmolStr summaries, %d memory reads" % (count, reads))
+    if reads > 2 * count:
+        raise AssertionError("%d reads for %d strings" % (reads, count))
+
+
+@test("rc_str_test")
+def rc_and_arc_str_show_text(session: Session):
+    expect_eq(session.summary("arc_str"), '(strong=1) "shared"')
+    expect_eq(session.summary("rc_str"), '(strong=2) "counted wörds"')
+    expect_eq(session.summary("empty"), '(strong=1) ""')
+
+
+@test("rc_slice_test")
+def rc_and_arc_slices_show_elements(session: Session):
+    expect_eq(session.summary("arc_slice"), "(strong=2) size=3 [10, 20, 30]")
+    expect_eq(session.summary("rc_slice"), "(strong=1) size=2 [-1, 2]")
+    expect_eq(session.summary("empty"), "(strong=1) size=0 []")
+
+    arc_slice = session.variable("arc_slice")
+    expect_eq(arc_slice.GetNumChildren(), 3)
+    expect_eq(arc_slice.GetChildAtIndex(0).GetName(), "[0]")
+    expect_eq(arc_slice.GetChildAtIndex(2).GetValueAsUnsigned(), 30)
+    expect_eq(session.variable("empty").GetNumChildren(), 0)
+
+
+@test("rc_shared_test")
+def shared_rc_renders_once_per_preview(session: Session):
+    pointer = session.variable("node").GetNonSyntheticValue()
+    pointer = pointer.GetChildMemberWithName("ptr").GetChildMemberWithName("pointer")
+    shared = "<shared @0x%x>" % pointer.GetValueAsUnsigned()
+    expect_eq(
+        session.summary("nodes"),
+        "size=4 [(strong=4) Node { id: 1 }, %s, (strong=1) Node { id: 2 }, %s]"
+        % (shared, shared),
+    )
+
+    # Each child on its own is a new summary and renders in full
+    nodes = session.variable("nodes")
+    expect_eq(nodes.GetChildAtIndex(1).GetSummary(), "(strong=4) Node { id: 1 }")
+    expect_eq(session.summary("node"), "(strong=4) Node { id: 1 }")
+
+
+@test("nested_test")
+def nested_collections_preview_each_level(session: Session):
+    nested = session.variable("nested")
+    expect_eq(
+        nested.GetSummary(),
+        "size=2 [size=1 [size=3 [1, 2, 3]], size=2 [size=1 [4], size=2 [5, 6]]]",
+    )
+
+    # The nested collections keep their full contents as children
+    inner = nested.GetChildAtIndex(1)
+    expect_eq(inner.GetSummary(), "size=2 [size=1 [4], size=2 [5, 6]]")
+    expect_eq(inner.GetChildAtIndex(1).GetSummary(), "size=2 [5, 6]")
+
+    rows = session.variable("rows")
+    expect_eq(rows.GetSummary(), "size=3 [size=3 [1, 2, 3], size=0 [], size=2 [-4, 5]]")
+    expect_eq(rows.GetNumChildren(), 3)
+    expect_eq(rows.GetChildAtIndex(2).GetNumChildren(), 2)
+    expect_eq(rows.GetChildAtIndex(2).GetChildAtIndex(0).GetValue(), "-4")
+    expect_eq(session.summary("by_key"), 'size=1 {"evens": size=3 [2, 4, 6]}')
+
+
+@test("nested_test")
+def nested_previews_share_the_budget(session: Session):
+    # Inner previews only get what the outer one has left
+    wide = session.summary("wide")
+    expect_eq(wide[:24], "size=4 [size=20 [0, 1, 2")
+    expect_eq(wide[-6:], ", ...]")
+    expect_eq("300" in wide, False)
+
+    session.command("rust-formatter-set max-depth 1")
+    expect_eq(session.summary("nested"), "size=2 [size=1 [...], size=2 [...]]")
+
+
+@test("smolstr_debug_test")
+def disabled_thirdparty_crates_use_default_rendering(session: Session):
+    expect_eq(session.summary("inline_short"), '"hello"')
+
+    output = session.command("rust-formatter-set enable-smol-str false")
+    expect_in(output, "enable-smol-str = False")
+    expect_eq(session.summary("inline_short"), "")
+    expect_eq(session.variable("inline_short").GetChildAtIndex(0).GetName(), "__0")
+    expect_eq(session.summary("heap_smallvec"), "size=4 [1, 2, 4, 5]")
+
+    session.command("rust-formatter-set enable-smol-str true")
+    session.command("rust-formatter-set enable-thirdparty false")
+    expect_eq(session.summary("heap_smallvec"), "")
+
+    session.command("rust-formatter-set enable-thirdparty true")
+    expect_eq(session.summary("inline_short"), '"hello"')
+
+
+@test("smolstr_debug_test")
+def categories_switch_from_lldb(session: Session):
+    session.command("type category disable rust-smolstr")
+    if session.summary("inline_short") == '"hello"':
+        raise AssertionError("SmolStr provider still enabled")
+    expect_eq(session.summary("heap_smallvec"), "size=4 [1, 2, 4, 5]")
+    expect_eq(session.summary("test_vec"), "size=3 [10, 20, 30]")
+    session.command("type category enable rust-smolstr")
+    expect_eq(session.summary("inline_short"), '"hello"')
+
+    session.command("type category disable rust-thirdparty")
+    if session.summary("heap_smallvec").startswith("size=4 ["):
+        raise AssertionError("SmallVec provider still enabled")
+    expect_eq(session.summary("inline_short"), '"hello"')
+    session.command("type category enable rust-thirdparty")
+    expect_eq(session.summary("heap_smallvec"), "size=4 [1, 2, 4, 5]")
+
+    session.command("type category disable rust-std")
+    if session.summary("test_vec") == "size=3 [10, 20, 30]":
+        raise AssertionError("Vec provider still enabled")
+    expect_eq(session.summary("inline_short"), '"hello"')
+    session.command("type category enable rust-std")
+    expect_eq(session.summary("test_vec"), "size=3 [10, 20, 30]")
+    expect_eq(session.summary("heap_smallvec"), "size=4 [1, 2, 4, 5]")
+
+
+@test("smolstr_debug_test")
+def reenabled_std_category_leaves_thirdparty_types_alone(session: Session):
+    # rust-std is now searched first, its generic struct summary mustn't
+    # take over the types of the third-party categories
+    session.command("type category disable rust-std")
+    session.command("type category enable rust-std")
+    expect_eq(session.summary("inline_short"), '"hello"')
+    expect_eq(session.summary("heap_smallvec"), "size=4 [1, 2, 4, 5]")
+    expect_eq(session.summary("test_vec"), "size=3 [10, 20, 30]")
+
+
+@test("ref_test", frame=0)
+def references_format_like_their_collection(session: Session):
+    expect_eq(session.summary("numbers"), "size=3 [1, 2, 3]")
+    expect_eq(session.summary("text"), '"borrowed"')
+    expect_eq(session.summary("small"), "size=2 [7, 8]")
+
+    numbers = session.variable("numbers")
+    expect_eq(numbers.GetNumChildren(), 3)
+    expect_eq(numbers.GetChildAtIndex(2).GetValue(), "3")
+
+
+@test("char_test")
+def chars_render_quoted_in_collections(session: Session):
+    expect_eq(
+        session.summary("chars"),
+        "size=7 ['a', 'ß', '€', '🦀', '\\n', '\\'', '\\u{7f}']",
+    )
+    expect_eq(session.summary("single"), "'z'")
+
+
+@test("smolstr_debug_test")
+def selftest_command_passes(session: Session):
+    output = session.command("rust-formatter-selftest")
+    expect_in(output, "PASS inline_short")
+    expect_in(output, ", 0 failed")
+
+
+@test("enum_test")
+def single_variant_enum_without_tag(session: Session):
+    expect_eq(session.summary("single"), "Data(7, -3)")
+
+    single = session.variable("single")
+    expect_eq(single.GetNumChildren(), 2)
+    expect_eq(single.GetChildMemberWithName("__1").GetValue(), "-3")
+
+
+@test("enum_test", frame=0)
+def enum_references_show_the_variant(session: Session):
+    expect_eq(session.summary("rect"), "Rect { w: 2.5, h: 4.0 }")
+    expect_eq(session.summary("circle"), "Circle(1.5)")
+    expect_eq(session.summary("empty"), "Empty")
+    expect_eq(session.summary("maybe"), "Some(5)")
+
+    rect = session.variable("rect")
+    expect_eq(rect.GetNumChildren(), 2)
+    expect_eq(rect.GetChildMemberWithName("w").GetValue(), "2.5")
+    expect_eq(session.variable("single").GetChildAtIndex(0).GetValue(), "7")
+
+
+@test("float_test")
+def floats_use_shortest_round_trip(session: Session):
+    expect_eq(session.summary("doubles"), "size=3 [0.1, 0.2, 0.3]")
+    expect_eq(
+        session.summary("awkward"), "size=4 [0.30000000000000004, 1e16, 1.5e-7, 2.0]"
+    )
+    expect_eq(session.summary("singles"), "size=3 [1.3, -0.0, 3.4028235e38]")
+    expect_eq(session.summary("special"), "size=3 [NaN, inf, -inf]")
+    expect_eq(session.summary("some_float"), "Some(0.1)")
+
+
+@test("btreemap_test")
+def btreemap_entries_in_key_order(session: Session):
+    expect_eq(session.summary("small"), 'size=3 {1: "one", 2: "two", 3: "three"}')
+    expect_eq(session.summary("empty"), "size=0 {}")
+
+    small = session.variable("small")
+    expect_eq(small.GetChildAtIndex(1).GetName(), "[2]")
+    expect_eq(small.GetChildAtIndex(1).GetSummary(), '"two"')
+
+
+@test("btreemap_test")
+def btreemap_large_values_across_nodes(session: Session):
+    large = session.variable("large")
+    expect_eq(large.GetNumChildren(), 40)
+    for i in range(40):
+        value = large.GetChildAtIndex(i)
+        expect_eq(value.GetName(), "[%d]" % i)
+        # Every byte of the value was filled with its key
+        for byte in (0, 63):
+            expect_eq(value.GetChildAtIndex(byte).GetValueAsUnsigned(), i)
+
+
+@test("invalid_utf8_test")
+def invalid_utf8_decodes_lossily(session: Session):
+    expect_eq(session.summary("corrupted"), '"va\ufffdid text"')
+    expect_eq(session.summary("as_str"), '"va\ufffdid text"')
+    expect_eq(session.summary("smol"), '"va\ufffdid text"')
+
+
+@test("indexset_test")
+def indexset_values_in_insertion_order(session: Session):
+    expect_eq(session.summary("numbers"), "size=4 [30, 10, 40, 20]")
+    expect_eq(session.summary("words"), 'size=3 ["zeta", "alpha", "mu"]')
+
+    words = session.variable("words")
+    expect_eq(words.GetNumChildren(), 3)
+    expect_eq(words.GetChildAtIndex(1).GetName(), "[1]")
+    expect_eq(words.GetChildAtIndex(1).GetSummary(), '"alpha"')
+
+
+def check_width_summaries(session: Session):
+    expect_eq(session.summary("inline"), '"short"')
+    expect_eq(session.summary("heap"), '"a string too long to be stored inline"')
+    expect_eq(session.summary("small_inline"), "size=2 [1, 2]")
+    expect_eq(session.summary("small_heap"), "size=5 [1, 2, 3, 4, 5]")
+    expect_eq(session.summary("numbers"), "size=3 [10, 20, 30]")
+    expect_eq(session.summary("text"), '"usize sized"')
+    expect_in(session.summary("shared"), "(strong=2)")
+
+
+@test("width_test")
+def collections_on_64bit_target(session: Session):
+    check_width_summaries(session)
+
+
+@test("width_test")
+def frame_variables_listed_like_an_ide(session: Session):
+    # lldb-dap and the MI drivers list the frame's variables with GetVariables
+    # and ask each for its summary, rather than going through `v`
+    frame = session.process.GetSelectedThread().GetSelectedFrame()
+    variables = frame.GetVariables(False, True, False, True, lldb.eDynamicCanRunTarget)
+    values = {value.GetName(): value for value in variables}
+    expect_eq(values["inline"].GetSummary(), '"short"')
+    expect_eq(values["heap"].GetSummary(), '"a string too long to be stored inline"')
+    expect_eq(values["small_heap"].GetSummary(), "size=5 [1, 2, 3, 4, 5]")
+    expect_eq(values["small_heap"].GetNumChildren(), 5)
+    expect_eq(values["small_heap"].GetChildAtIndex(4).GetValue(), "5")
+
+
+@test("width_test")
+def frame_variable_in_batch_mode(session: Session):
+    lldb_path = shutil.which("lldb")
+    if lldb_path is None:
+        raise SkipTest("no lldb executable on PATH")
+    output = subprocess.run(
+        [
+            lldb_path,
+            "--batch",
+            "-o",
+            "command script import %s" % FORMATTER,
+            "-o",
+            "breakpoint set --name inspect_variables",
+            "-o",
+            "run",
+            "-o",
+            "frame select 1",
+            "-o",
+            "frame variable inline heap small_inline small_heap",
+            os.path.join(BINARIES, "width_test"),
+        ],
+        cwd=HERE,
+        capture_output=True,
+        text=True,
+        timeout=120,
+    ).stdout
+    expect_in(output, 'inline = "short"')
+    expect_in(output, 'heap = "a string too long to be stored inline"')
+    expect_in(output, "small_inline = size=2 [1, 2]")
+    expect_in(output, "small_heap = size=5 [1, 2, 3, 4, 5]")
+
+
+@test("width_test", triple=TARGET_32BIT)
+def collections_on_32bit_target(session: Session):
+    expect_eq(session.target.GetAddressByteSize(), 4)
+    check_width_summaries(session)
+
+
+@test("bool_test")
+def bool_collections_as_values_and_bits(session: Session):
+    expect_eq(session.summary("flags"), "size=5 [true, false, true, true, false]")
+    expect_eq(session.summary("small_flags"), "size=3 [false, true, true]")
+    expect_eq(session.summary("bytes"), "size=2 [1, 0]")
+
+    session.command("rust-formatter-set bools-as-bits true")
+    expect_eq(session.summary("flags"), "size=5 10110")
+    expect_eq(session.summary("small_flags"), "size=3 011")
+    expect_eq(session.summary("empty_flags"), "size=0 []")
+    # Only bool elements are shown as bits
+    expect_eq(session.summary("bytes"), "size=2 [1, 0]")
+
+    # Nested bits only get what is left of the outer budget
+    session.command("rust-formatter-set preview-budget 20")
+    expect_eq(
+        session.summary("nested_flags"), "size=2 [size=100 %s..., ...]" % ("1" * 20)
+    )
+    session.command("rust-formatter-set max-depth 1")
+    expect_eq(session.summary("nested_flags"), "size=2 [size=100 [...], size=3 [...]]")
+
+
+@test("smolstr_debug_test")
+def version_command_lists_providers(session: Session):
+    output = session.command("rust-formatter-version")
+    expect_in(output, "rust_bonus_types ")
+    expect_in(output, "SmolStrSummaryProvider: smol_str::SmolStr (rust-smolstr)")
+    expect_in(output, "SmolStrSyntheticProvider: smol_str::SmolStr")
+    expect_in(output, "SmallVecSummaryProvider: ^smallvec::SmallVec<.+>$")
+    expect_in(output, "SmallVecSyntheticProvider: ^smallvec::SmallVec<.+>$")
+
+
+@test("smolstr_debug_test")
+def settings_command_lists_values_and_defaults(session: Session):
+    session.command("rust-formatter-set max-children 5")
+    output = session.command("rust-formatter-settings")
+    expect_in(output, "target.rust-formatters.max-children = 5 (default: 0)")
+    expect_in(output, "target.rust-formatters.max-depth = 8 (default: 8)")
+    expect_in(output, "target.rust-formatters.int-radix = 10 (default: 10)")
+    expect_in(output, "target.rust-formatters.metadata = false (default: false)")
+
+
+@test("option_test")
+def option_scalar_niche_payload(session: Session):
+    payload = session.variable("some_false").GetChildAtIndex(0)
+    expect_eq(payload.GetName(), "__0")
+    expect_eq(payload.GetValue(), "false")
+
+
+@test("option_test")
+def option_reference_niche_payload(session: Session):
+    expect_eq(session.variable("none_bytes").GetNumChildren(), 0)
+    payload = session.variable("some_bytes").GetChildAtIndex(0)
+    expect_eq(payload.GetNumChildren(), 3)
+    expect_eq(payload.GetChildAtIndex(2).GetValue(), "3")
+
+
+@test("smolstr_debug_test")
+def option_bool_in_main(session: Session):
+    expect_eq(session.summary("some_bool"), "Some(true)")
+    expect_eq(session.summary("none_bool"), "None")
+
+
+@test("chrono_test")
+def chrono_dates_and_durations(session: Session):
+    expect_eq(session.summary("leap_day"), "2024-02-29")
+    expect_eq(session.summary("new_years_eve"), "1999-12-31")
+    expect_eq(session.summary("ides"), "-0044-03-15")
+    expect_eq(session.summary("far_future"), "+12345-01-01")
+
+    expect_eq(session.summary("ninety_seconds"), "1m 30.5s")
+    expect_eq(session.summary("days_and_hours"), "2d 3h")
+    expect_eq(session.summary("negative"), "-1.5s")
+    expect_eq(session.summary("zero"), "0s")
+
+
+@test("chrono_test")
+def chrono_datetimes_show_their_offset(session: Session):
+    expect_eq(session.summary("noon"), "2024-02-29 12:30:00")
+    expect_eq(session.summary("utc"), "1999-12-31 23:59:59.500 UTC")
+    expect_eq(session.summary("plus_two"), "2024-02-29 12:30:00 +02:00")
+    expect_eq(session.summary("minus_five_thirty"), "2024-01-01 00:00:00 -05:30")
+
+    # The offset of Local depends on the machine running the test
+    local = session.summary("local")
+    expect_eq(local[-6] in "+-" and local[-3] == ":", True)
+
+
+@test("slice_test")
+def boxed_slices_show_elements(session: Session):
+    expect_eq(session.summary("boxed"), "size=3 [4, 5, 6]")
+    expect_eq(session.summary("empty_boxed"), "size=0 []")
+    expect_eq(session.summary("borrowed"), "size=2 [5, 6]")
+
+    boxed = session.variable("boxed")
+    expect_eq(boxed.GetNumChildren(), 3)
+    expect_eq(boxed.GetChildAtIndex(2).GetValue(), "6")
+    expect_eq(session.variable("empty_boxed").GetNumChildren(), 0)
+
+    expect_eq(session.summary("boxed_u32"), "size=4 [10, 20, 30, 40]")
+    expect_eq(session.variable("boxed_u32").GetNumChildren(), 4)
+    expect_eq(session.summary("empty_u32"), "size=0 []")
+    expect_eq(session.variable("empty_u32").GetNumChildren(), 0)
+
+
+@test("slice_test")
+def slices_of_structs_expose_fields(session: Session):
+    expect_eq(
+        session.summary("points"),
+        "size=2 [Point { x: 1, y: 2 }, Point { x: -3, y: 4 }]",
+    )
+    points = session.variable("points")
+    expect_eq(points.GetNumChildren(), 2)
+    second = points.GetChildAtIndex(1)
+    expect_eq(second.GetChildMemberWithName("x").GetValue(), "-3")
+    expect_eq(second.GetChildMemberWithName("y").GetValue(), "4")
+
+    # Fields inside the elements get their own providers
+    named = session.variable("named")
+    expect_eq(named.GetNumChildren(), 2)
+    first = named.GetChildAtIndex(0)
+    expect_eq(first.GetChildMemberWithName("name").GetSummary(), '"alpha"')
+    expect_eq(first.GetChildMemberWithName("id").GetValue(), "7")
+    expect_eq(
+        named.GetChildAtIndex(1).GetChildMemberWithName("name").GetSummary(),
+        '"beta"',
+    )
+
+    # Slices of arrays are slices, references to arrays are not
+    expect_eq(
+        session.summary("grid"), "size=3 [size=2 [1, 2], size=2 [3, 4], size=2 [5, 6]]"
+    )
+    expect_eq(session.variable("grid").GetChildAtIndex(2).GetNumChildren(), 2)
+    expect_eq("size=" in (session.variable("array_ref").GetSummary() or ""), False)
+
+
+@test("array_test")
+def large_arrays_preview_within_the_budget(session: Session):
+    expect_eq(
+        session.summary("pattern"),
+        "size=1024 [%s, ...]" % ", ".join(str(i) for i in range(23)),
+    )
+    expect_eq(session.summary("small"), "size=3 [1, 2, 3]")
+    expect_in(session.summary("frame"), "payload: size=512 [171, 171, ")
+
+    # Expanding still reaches every element
+    pattern = session.variable("pattern")
+    expect_eq(pattern.GetNumChildren(), 1024)
+    expect_eq(pattern.GetChildAtIndex(1023).GetValueAsUnsigned(), 255)
+
+
+@test("array_test")
+def large_arrays_honor_max_children_and_pages(session: Session):
+    session.command("rust-formatter-set max-children 10")
+    expect_eq(session.variable("pattern").GetNumChildren(), 10)
+    expect_eq(session.summary("pattern")[:15], "size=1024 [0, 1")
+
+    session.command("rust-formatter-set max-children 0")
+    session.command("rust-formatter-set page-size 256")
+    pattern = session.variable("pattern")
+    expect_eq(pattern.GetNumChildren(), 4)
+    last_page = pattern.GetChildAtIndex(3)
+    expect_eq(last_page.GetName(), "[page 768..1024]")
+    expect_eq(last_page.GetChildAtIndex(255).GetValueAsUnsigned(), 255)
+
+
+@test("slice_test")
+def sub_slices_read_their_own_length(session: Session):
+    expect_eq(session.summary("middle"), "size=3 [2, 3, 4]")
+    middle = session.variable("middle")
+    expect_eq(middle.GetNumChildren(), 3)
+    expect_eq(middle.GetChildAtIndex(0).GetValue(), "2")
+
+    session.command("rust-formatter-set metadata true")
+    expect_eq(session.summary("middle"), "size=3 [2, 3, 4] (slice)")
+    expect_eq(session.summary("boxed"), "size=3 [4, 5, 6] (boxed slice)")
+    expect_eq(session.summary("owned"), "size=5 [1, 2, 3, 4, 5]")
+
+
+@test("iter_test")
+def iterators_mid_iteration(session: Session):
+    expect_eq(session.summary("slice_iter"), "Iter { remaining=3 }")
+    expect_eq(session.summary("slice_iter_mut"), "IterMut { remaining=1 }")
+    expect_eq(session.summary("into_iter"), "IntoIter { position=1, remaining=3 }")
+    expect_eq(session.summary("map_iter"), "Iter { remaining=2 }")
+
+
+@test("max_children_test")
+def max_children_overrides_by_type(session: Session):
+    expect_eq(session.variable("map").GetNumChildren(), 20)
+
+    session.command("rust-formatter-set max-children 5")
+    expect_eq(session.variable("numbers").GetNumChildren(), 5)
+    expect_eq(session.variable("map").GetNumChildren(), 5)
+
+    output = session.command(
+        "rust-formatter-set max-children-overrides '.*=8;BTreeMap<.*>=15'"
+    )
+    expect_in(output, "max-children-overrides = ")
+    expect_eq(session.variable("numbers").GetNumChildren(), 8)
+    expect_eq(session.variable("map").GetNumChildren(), 15)
+    expect_eq(session.summary("map")[:8], "size=20 ")
+    expect_in(session.summary("numbers"), ", 8, 9, ")
+
+    output = session.command("rust-formatter-set max-children-overrides Vec=x")
+    expect_in(output, "invalid value")
+    expect_eq(session.variable("map").GetNumChildren(), 15)
+
+
+@test("max_children_test")
+def disabled_types_get_default_formatting(session: Session):
+    expect_eq(session.summary("numbers")[:8], "size=20 ")
+
+    output = session.command("rust-formatter-set disable-types Vec<.*>")
+    expect_in(output, "disable-types = Vec<.*>")
+    numbers = session.variable("numbers")
+    expect_eq(numbers.GetSummary() or "", "")
+    expect_eq(
+        [numbers.GetChildAtIndex(i).GetName() for i in range(numbers.GetNumChildren())],
+        ["buf", "len"],
+    )
+    # Other types keep their providers
+    expect_eq(session.summary("map")[:8], "size=20 ")
+
+    output = session.command("rust-formatter-set disable-types 'Vec<(.*'")
+    expect_in(output, "invalid value")
+    expect_eq(session.summary("numbers"), "")
+
+    session.command("rust-formatter-set disable-types ''")
+    expect_eq(session.summary("numbers")[:8], "size=20 ")
+    expect_eq(session.variable("numbers").GetNumChildren(), 20)
+
+
+@test("max_children_test")
+def disabled_types_outlast_reenabled_categories(session: Session):
+    session.command("rust-formatter-set disable-types Vec<.*>")
+    # rust-std is now searched before rust-disabled
+    session.command("type category disable rust-std")
+    session.command("type category enable rust-std")
+    numbers = session.variable("numbers")
+    expect_eq(numbers.GetSummary() or "", "")
+    expect_eq(
+        [numbers.GetChildAtIndex(i).GetName() for i in range(numbers.GetNumChildren())],
+        ["buf", "len"],
+    )
+    expect_eq(session.summary("map")[:8], "size=20 ")
+
+    # LLDB matches POSIX extended regexes, which have no \d
+    output = session.command("rust-formatter-set disable-types 'Vec<\\d+>'")
+    expect_in(output, "invalid value")
+
+
+@test("cow_test")
+def arc_cow_shows_both_layers(session: Session):
+    expect_eq(session.summary("borrowed"), 'Borrowed("static text")')
+    expect_eq(session.summary("owned"), 'Owned("owned text")')
+    expect_eq(session.summary("arc_borrowed"), '(strong=1) Borrowed("static text")')
+    expect_eq(session.summary("arc_owned"), '(strong=2) Owned("owned text")')
+
+    value = session.variable("arc_owned").GetChildMemberWithName("value")
+    expect_eq(value.GetChildAtIndex(0).GetSummary(), '"owned text"')
+
+
+@test("tree_test")
+def generic_recursive_enum_renders_tree(session: Session):
+    expect_eq(session.summary("leaf"), "Leaf(1)")
+    expect_eq(session.summary("tree"), "Node(Leaf(1), Node(Leaf(2), Leaf(3)))")
+    expect_eq(session.summary("options"), "Node(Leaf(Some(4)), Leaf(None))")
+    expect_eq(session.summary("level"), "Warning")
+    expect_eq(session.summary("deep")[:5], "Node(")
+    expect_in(session.summary("deep"), "Node(...)")
+
+    tree = session.variable("tree")
+    expect_eq(tree.GetNumChildren(), 2)
+    expect_eq(tree.GetChildAtIndex(0).GetName(), "__0")
+
+
+@test("address_test")
+def show_addresses_on_pointers_only(session: Session):
+    expect_eq("@0x" in session.summary("boxed"), False)
+    expect_eq(session.summary("name_ref"), '"text"')
+
+    session.command("rust-formatter-set show-addresses true")
+    boxed = session.variable("boxed")
+    name_ref = session.variable("name_ref")
+    expect_eq(session.summary("boxed"), "5 @0x%x" % boxed.GetValueAsUnsigned())
+    expect_eq(
+        session.summary("name_ref"), '"text" @0x%x' % name_ref.GetValueAsUnsigned()
+    )
+    expect_eq(session.summary("name"), '"text"')
+    expect_eq(session.summary("number"), "")
+
+    session.command("rust-formatter-set show-addresses false")
+    expect_eq(session.summary("name_ref"), '"text"')
+
+
+@test("hashmap_test")
+def hashmap_tuple_and_struct_keys(session: Session):
+    expect_eq(session.summary("grid"), 'size=1 {(1, 2): "a"}')
+    grid = session.variable("grid")
+    expect_eq(grid.GetNumChildren(), 1)
+    expect_eq(grid.GetChildAtIndex(0).GetName(), "[(1, 2)]")
+    expect_eq(grid.GetChildAtIndex(0).GetSummary(), '"a"')
+
+    names = session.variable("names")
+    expect_eq(
+        sorted(names.GetChildAtIndex(i).GetName() for i in range(2)),
+        ['["one"]', '["two"]'],
+    )
+
+    points = session.variable("points")
+    expect_eq(points.GetChildAtIndex(0).GetName(), "[Point { x: 3, y: 4 }]")
+    expect_eq(session.summary("empty"), "size=0 {}")
+
+    session.command("rust-formatter-set key-summaries false")
+    names = session.variable("names")
+    expect_eq(names.GetChildAtIndex(0).GetName(), "[String { .. }]")
+    points = session.variable("points")
+    expect_eq(points.GetChildAtIndex(0).GetName(), "[Point { .. }]")
+
+
+@test("hashmap_test")
+def hashmap_skips_deleted_buckets(session: Session):
+    churned = session.variable("churned")
+    live = list(range(0, 1000, 50))
+    expect_eq(churned.GetNumChildren(), len(live))
+    expect_in(session.summary("churned"), "size=%d {" % len(live))
+
+    entries = [churned.GetChildAtIndex(i) for i in range(len(live))]
+    expect_eq(
+        sorted(int(entry.GetName()[1:-1]) for entry in entries),
+        live,
+    )
+    for entry in entries:
+        key = int(entry.GetName()[1:-1])
+        expect_eq((key, entry.GetValueAsUnsigned()), (key, key * 2))
+
+
+# smolstr_matrix values around the 23 byte inline limit, the golden file holds
+# their text, variant and length in bytes
+SMOLSTR_MATRIX = [
+    "empty",
+    "one",
+    "ascii_22",
+    "ascii_23",
+    "ascii_24",
+    "multi_22",
+    "multi_23",
+    "multi_24",
+    "emoji_23",
+    "emoji_24",
+    "only_multi",
+    "static_short",
+    "static_24",
+    "static_multi",
+    "heap_long",
+]
+
+golden(
+    "smolstr_matrix",
+    "smolstr_matrix",
+    [
+        path
+        for name in SMOLSTR_MATRIX
+        for path in (name, name + ".variant", name + ".length")
+    ],
+)
+
+
+@test("smolstr_matrix")
+def smolstr_layout_from_debug_info_matches_fallback(session: Session):
+    # smol_str 0.3: 23 bytes inline, Static at tag 24, Heap at 25. The
+    # fallback for debug info without the variants assumes the same.
+    module = sys.modules["rust_bonus_types"]
+    value = session.variable("ascii_24").GetNonSyntheticValue()
+    layout = module._smolstr_layout(value)
+    expect_eq(type(layout).__name__, "SmolStrLayout")
+    expect_eq(
+        (layout.inline_capacity, layout.static_discr, layout.heap_discr),
+        (23, 24, 25),
+    )
+
+    word = session.process.GetAddressByteSize()
+    fallback = module.smolstr_fallback_layout(value.GetByteSize(), word)
+    for name in fallback._fields:
+        if name != "arc_inner_type":
+            expect_eq((name, getattr(layout, name)), (name, getattr(fallback, name)))
+
+
+# edge_cases collections: name prefix and the summaries of the empty,
+# single-element and full values. Hash based collections list their
+# entries in bucket order, only their single-element summary is fixed.
+EDGE_CASES = [
+    ("vec", "size=0 []", "size=1 [1]", "size=4 [1, 2, 3, 4]"),
+    ("deque", "size=0 []", "size=1 [1]", "size=4 [2, 3, 4, 5]"),
+    ("smallvec", "size=0 []", "size=1 [1]", "size=4 [1, 2, 3, 4]"),
+    ("hashmap", "size=0 {}", "size=1 {1: 10}", None),
+    ("hashset", "size=0 []", "size=1 [1]", None),
+    (
+        "btreemap",
+        "size=0 {}",
+        "size=1 {1: 10}",
+        "size=11 {1: 10, 2: 20, 3: 30, 4: 40, 5: 50, 6: 60, 7: 70, 8: 80, "
+        "9: 90, 10: 100, 11: 110}",
+    ),
+    (
+        "btreeset",
+        "size=0 []",
+        "size=1 [1]",
+        "size=11 [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]",
+    ),
+    ("indexmap", "size=0 {}", "size=1 {1: 10}", "size=3 {3: 30, 1: 10, 2: 20}"),
+]
+
+
+@test("edge_cases")
+def collections_empty_single_and_full(session: Session):
+    for prefix, empty, one, full in EDGE_CASES:
+        expect_eq(session.summary(prefix + "_empty"), empty)
+        expect_eq(session.variable(prefix + "_empty").GetNumChildren(), 0)
+        expect_eq(session.summary(prefix + "_one"), one)
+        expect_eq(session.variable(prefix + "_one").GetNumChildren(), 1)
+        if full is not None:
+            expect_eq(session.summary(prefix + "_full"), full)
+
+    for name, count in (
+        ("vec_full", 4),
+        ("deque_full", 4),
+        ("smallvec_full", 4),
+        ("hashmap_full", 3),
+        ("hashset_full", 3),
+        ("btreemap_full", 11),
+        ("btreeset_full", 11),
+        ("indexmap_full", 3),
+    ):
+        expect_eq((name, session.variable(name).GetNumChildren()), (name, count))
+
+    hashmap = session.variable("hashmap_full")
+    expect_eq(
+        sorted(hashmap.GetChildAtIndex(i).GetName() for i in range(3)),
+        ["[1]", "[2]", "[3]"],
+    )
+    hashset = session.variable("hashset_full")
+    expect_eq(
+        sorted(hashset.GetChildAtIndex(i).GetValueAsUnsigned() for i in range(3)),
+        [1, 2, 3],
+    )
+    expect_eq(session.variable("deque_full").GetChildAtIndex(3).GetValue(), "5")
+    expect_eq(session.variable("btreeset_full").GetChildAtIndex(10).GetValue(), "11")
+
+
+@test("dyn_error_test")
+def boxed_dyn_errors_show_concrete_error(session: Session):
+    expect_eq(session.summary("ok"), "Ok(3)")
+    expect_eq(
+        session.summary("os"),
+        'Err(Os { code: 2, kind: NotFound, message: "No such file or directory" })',
+    )
+    expect_eq(session.summary("simple"), "Err(Kind(TimedOut))")
+
+    custom = session.summary("custom")
+    expect_eq(custom[:28], "Err(Custom { kind: NotFound,")
+    expect_in(custom, '"missing file"')
+
+    boxed = session.variable("custom").GetChildAtIndex(0)
+    value = boxed.GetChildMemberWithName("value")
+    expect_eq(value.GetTypeName(), "std::io::error::Error")
+
+
+@test("anyhow_test")
+def io_error_from_raw_os_error(session: Session):
+    expect_eq(
+        session.summary("not_found"),
+        'Os { code: 2, kind: NotFound, message: "No such file or directory" }',
+    )
+    expect_eq(
+        session.summary("denied"),
+        'Os { code: 13, kind: PermissionDenied, message: "Permission denied" }',
+    )
+
+
+@test("anyhow_test")
+def anyhow_errors_show_wrapped_error(session: Session):
+    expect_eq(session.summary("message"), '"config missing"')
+    expect_eq(
+        session.summary("wrapped"),
+        'Os { code: 2, kind: NotFound, message: "No such file or directory" }',
+    )
+
+
+@test("async_test")
+def async_fn_state_and_locals(session: Session):
+    expect_eq(session.summary("idle")[:26], "async fn work: Unresumed {")
+    expect_eq(session.summary("finished")[:23], "async fn work: Returned")
+
+    suspended = session.summary("suspended")
+    expect_eq(suspended[:24], "async fn work: Suspend0 ")
+    expect_in(suspended, "count: 3")
+    expect_in(suspended, "doubled: 6")
+    expect_in(suspended, "awaitee: ")
+
+    value = session.variable("suspended")
+    expect_eq(value.GetChildMemberWithName("doubled").GetValueAsUnsigned(), 6)
+
+
+@test("option_test")
+def vec_of_options_decodes_each_element(session: Session):
+    expect_eq(session.summary("nonzero_vec"), "size=3 [Some(1), None, Some(7)]")
+    expect_eq(session.summary("tagged_vec"), "size=3 [None, Some(0), Some(255)]")
+
+    elements = session.variable("nonzero_vec")
+    expect_eq(
+        [elements.GetChildAtIndex(i).GetSummary() for i in range(3)],
+        ["Some(1)", "None", "Some(7)"],
+    )
+    expect_eq(elements.GetChildAtIndex(1).GetNumChildren(), 0)
+    expect_eq(elements.GetChildAtIndex(2).GetChildAtIndex(0).GetSummary(), "7")
+
+
+@test("option_test")
+def option_words_and_delimiters_are_configurable(session: Session):
+    session.command("rust-formatter-set none-word Nothing")
+    session.command("rust-formatter-set some-word Just")
+    expect_eq(session.summary("some_i32"), "Just(42)")
+    expect_eq(session.summary("none_i32"), "Nothing")
+
+    session.command("rust-formatter-set sequence-open vec![")
+    expect_eq(session.summary("nonzero_vec"), "size=3 vec![Just(1), Nothing, Just(7)]")
+
+    session.command("rust-formatter-set none-word None")
+    session.command("rust-formatter-set some-word Some")
+    session.command("rust-formatter-set sequence-open [")
+    expect_eq(session.summary("nonzero_vec"), "size=3 [Some(1), None, Some(7)]")
+
+
+@test("duration_test")
+def durations_inside_options_and_vecs(session: Session):
+    expect_eq(session.summary("duration"), "1.5s")
+    expect_eq(session.summary("some_duration"), "Some(1m 30s)")
+    expect_eq(session.summary("none_duration"), "None")
+    expect_eq(session.summary("durations"), "size=3 [2h, 0.00025s, 0s]")
+    expect_eq(session.summary("started")[:8], "Instant(")
+    expect_eq(session.summary("some_instant")[:13], "Some(Instant(")
+
+
+@test("timer_test")
+def time_fields_of_user_structs(session: Session):
+    timer = session.variable("timer")
+    expect_eq(timer.GetChildMemberWithName("start").GetSummary()[:8], "Instant(")
+    expect_eq(timer.GetChildMemberWithName("start").GetNumChildren(), 0)
+    expect_eq(timer.GetChildMemberWithName("elapsed").GetSummary(), "1.5s")
+    expect_in(session.summary("timer"), "elapsed: 1.5s }")
+
+    output = session.command("frame variable timer")
+    expect_in(output, "start = Instant(")
+    expect_in(output, "elapsed = 1.5s")
+    if "tv_sec" in output:
+        raise AssertionError("timespec fields shown:\n%s" % output)
+
+
+@test("timer_test")
+def time_values_from_arithmetic(session: Session):
+    expect_eq(session.summary("remaining"), "1m 30s")
+    expect_eq(session.summary("doubled"), "3s")
+    expect_eq(session.summary("deadline")[:8], "Instant(")
+
+    stopwatch = session.variable("stopwatch")
+    started = stopwatch.GetChildMemberWithName("started")
+    expect_eq(started.GetSummary()[:13], "Some(Instant(")
+    laps = stopwatch.GetChildMemberWithName("laps")
+    expect_eq(laps.GetSummary(), "size=2 [0.25s, 1m 1s]")
+    created = stopwatch.GetChildMemberWithName("created")
+    expect_eq(created.GetSummary(), "1970-01-02 00:00:00 UTC")
+
+
+@test("layout_mismatch_test")
+def mis_sized_types_fall_back_to_default(session: Session):
+    # The providers are called directly, the stand-in types don't match
+    # their registered names
+    module = sys.modules["rust_bonus_types"]
+    fake_smol = session.variable("fake_smol")
+    fake_compact = session.variable("fake_compact")
+
+    expect_eq(module.SmolStrSummaryProvider(fake_smol, {}), "")
+    expect_eq(module.CompactStringSummaryProvider(fake_compact, {}), "")
+
+    synthetic = module.SmolStrSyntheticProvider(fake_smol, {})
+    expect_eq(synthetic.num_children(), 1)
+    expect_eq(synthetic.get_child_at_index(0).GetName(), "__0")
+
+    warning = module.LAYOUT_WARNINGS["layout_mismatch_test::FakeSmolStr"]
+    expect_in(warning, "is 16 bytes, the provider expects 24")
+    expect_in(session.command("rust-formatter-debug fake_smol"), "warning: ")
+
+
+@test("rc_refcell_test")
+def rc_refcell_composes(session: Session):
+    expect_eq(
+        session.summary("shared"),
+        "(strong=2) RefCell { <unborrowed> size=3 [1, 2, 3] }",
+    )
+    expect_eq(session.summary("borrowed"), "RefCell { <borrowed=1> 5 }")
+    expect_eq(session.summary("mutably_borrowed"), "RefCell { <mutably borrowed> 6 }")
+
+    cell = session.variable("shared").GetChildMemberWithName("value")
+    expect_eq(cell.GetChildMemberWithName("borrow").GetSummary(), '"<unborrowed>"')
+    expect_eq(cell.GetChildMemberWithName("value").GetNumChildren(), 3)
+
+
+@test("budget_test")
+def format_budget_bounds_huge_collections(session: Session):
+    expect_eq(session.summary("small_tree"), "size=3 {0: 0, 1: 1, 2: 2}")
+
+    session.command("rust-formatter-set format-budget-ms 20")
+    for name in ("huge_tree", "huge_map"):
+        started = time.monotonic()
+        summary = session.summary(name)
+        elapsed = time.monotonic() - started
+        expect_in(summary, "…(budget exceeded)")
+        if elapsed > 2.0:
+            raise AssertionError("%s took %.1fs to format" % (name, elapsed))
+
+    expect_eq(session.summary("small_tree"), "size=3 {0: 0, 1: 1, 2: 2}")
+
+
+@test("string_keys_test")
+def smolstr_and_compact_string_keys_render_plain(session: Session):
+    expect_eq(session.summary("single_smol"), 'size=1 {"x": 10}')
+
+    preview = session.summary("smol_keys")
+    for entry in ('"let": 1', '"fn": 2', '"an identifier longer than'):
+        expect_in(preview, entry)
+    expect_eq("\\x" in preview, False)
+
+    children = session.variable("compact_keys")
+    names = sorted(
+        children.GetChildAtIndex(i).GetName() for i in range(children.GetNumChildren())
+    )
+    expect_eq(names, ['["a compact string that lives on the heap"]', '["match"]'])
+
+    # Keys stay plain strings when the string summaries show metadata
+    session.command("rust-formatter-set metadata true")
+    expect_eq(session.summary("single_smol"), 'size=1 {"x": 10}')
+
+
+@test("broken_vec_test")
+def vec_len_beyond_capacity_is_clamped(session: Session):
+    expect_eq(
+        session.summary("broken"), "size=1000000 (len>cap, truncated) [1, 2, 3, 4]"
+    )
+    expect_eq(session.variable("broken").GetNumChildren(), 4)
+
+
+@test("broken_vec_test")
+def vec_deque_len_beyond_capacity_is_clamped(session: Session):
+    raw = session.variable("deque").GetNonSyntheticValue()
+    raw.GetChildMemberWithName("len").SetValueFromCString("1000000")
+    expect_eq(
+        session.summary("deque"), "size=1000000 (len>cap, truncated) [1, 2, 3, 4]"
+    )
+    expect_eq(session.variable("deque").GetNumChildren(), 4)
+
+
+@test("glam_test")
+def glam_quaternions_and_affines(session: Session):
+    expect_eq(session.summary("quarter_turn"), "(0.0, 0.0, 0.70710677, 0.70710677)")
+    expect_eq(session.summary("identity"), "(0.0, 0.0, 0.0, 1.0)")
+    expect_eq(
+        session.summary("transform"),
+        "translation=(1.0, 2.0, 3.0) "
+        "matrix=[(2.0, 0.0, 0.0), (0.0, 2.0, 0.0), (0.0, 0.0, 2.0)]",
+    )
+    expect_eq(
+        session.summary("flat"),
+        "translation=(5.0, -6.5) matrix=[(1.0, 0.0), (0.0, 1.0)]",
+    )
+
+    session.command("rust-formatter-set quat-axis-angle true")
+    expect_eq(session.summary("quarter_turn"), "axis=(0.0, 0.0, 1.0) angle=90.0°")
+    expect_eq(session.summary("identity"), "axis=(1.0, 0.0, 0.0) angle=0.0°")
+
+
+@test("poison_test")
+def poisoned_locks_are_marked(session: Session):
+    expect_eq(session.summary("healthy"), "Mutex { 5 }")
+    expect_eq(session.summary("poisoned"), "Mutex (poisoned) { size=2 [1, 2] }")
+    expect_eq(session.summary("poisoned_rwlock"), 'RwLock (poisoned) { "stale" }')
+
+    poisoned = session.variable("poisoned")
+    expect_eq(poisoned.GetChildMemberWithName("poisoned").GetValue(), "true")
+    expect_eq(poisoned.GetChildMemberWithName("data").GetNumChildren(), 2)
+    healthy = session.variable("healthy")
+    expect_eq(healthy.GetChildMemberWithName("poisoned").GetValue(), "false")
+
+
+@test("arraystring_test")
+def arraystring_reads_len_bytes(session: Session):
+    expect_eq(session.summary("partial"), '"hello"')
+    expect_eq(session.summary("full"), '"größe"')
+    expect_eq(session.summary("empty"), '""')
+
+
+@test("global_test")
+def statics_and_lazy_wrappers(session: Session):
+    expect_in(session.command("target variable NUMBERS"), "Mutex { size=3 [1, 2, 3] }")
+    expect_in(session.command("target variable EMPTY"), "size=0 []")
+    expect_in(session.command("target variable NAMES"), 'size=1 {1: "one"}')
+    expect_in(session.command("target variable CONFIG"), '"release"')
+    expect_in(session.command("target variable UNSET"), "<uninitialized>")
+    expect_in(session.command("target variable UNUSED"), "<uninitialized>")
+
+
+@test("once_test")
+def once_cells_show_value_or_uninitialized(session: Session):
+    expect_eq(session.summary("set"), '"ready"')
+    expect_eq(session.summary("unset"), "<uninitialized>")
+    expect_eq(session.summary("forced"), "size=3 [1, 2, 3]")
+    expect_eq(session.summary("lazy"), "<uninitialized>")
+    expect_eq(session.summary("crate_cell"), "7")
+    expect_eq(session.summary("crate_lazy"), "<uninitialized>")
+
+    expect_eq(session.variable("set").GetNumChildren(), 1)
+    expect_eq(session.variable("unset").GetNumChildren(), 0)
+    expect_eq(session.variable("forced").GetChildAtIndex(0).GetNumChildren(), 3)
+
+
+@test("token_test")
+def struct_fields_use_their_providers(session: Session):
+    token = session.variable("token")
+    expect_eq(token.GetChildMemberWithName("text").GetSummary(), '"ident"')
+    spans = token.GetChildMemberWithName("spans")
+    expect_eq(spans.GetSummary(), "size=2 [3, 8]")
+    expect_eq(spans.GetChildAtIndex(1).GetValue(), "8")
+
+    spilled = session.variable("spilled")
+    expect_eq(
+        spilled.GetChildMemberWithName("text").GetSummary(),
+        '"a token text longer than the inline limit"',
+    )
+    expect_eq(
+        spilled.GetChildMemberWithName("spans").GetSummary(), "size=5 [1, 2, 3, 4, 5]"
+    )
+
+    pair = session.variable("pair")
+    expect_eq(pair.GetChildAtIndex(0).GetSummary(), '"pair"')
+    expect_eq(pair.GetChildAtIndex(1).GetSummary(), "size=1 [9]")
+
+
+@test("option_result_test")
+def option_inside_result(session: Session):
+    expect_eq(session.summary("ok_some"), "Ok(Some(5))")
+    expect_eq(session.summary("ok_none"), "Ok(None)")
+    expect_eq(session.summary("err"), 'Err("x")')
+    ok_some = session.variable("ok_some")
+    expect_eq(ok_some.GetChildAtIndex(0).GetSummary(), "Some(5)")
+
+
+@test("option_result_test")
+def result_inside_option(session: Session):
+    expect_eq(session.summary("some_ok"), "Some(Ok(5))")
+    expect_eq(session.summary("some_err"), 'Some(Err("x"))')
+    expect_eq(session.summary("none"), "None")
+    some_err = session.variable("some_err")
+    expect_eq(some_err.GetChildAtIndex(0).GetSummary(), 'Err("x")')
+
+
+@test("option_result_test")
+def nested_niches_in_scalars(session: Session):
+    expect_eq(session.summary("flags"), "size=3 [Ok(Some(true)), Ok(None), Err(())]")
+    expect_eq(session.summary("chars"), "size=3 [Some(Ok('a')), Some(Err(())), None]")
+
+
+@test("result_test")
+def result_elements_render_their_variant(session: Session):
+    expect_eq(session.summary("results"), 'size=3 [Ok(1), Err("bad input"), Ok(3)]')
+    expect_eq(session.summary("nested"), "size=3 [Ok(Some(7)), Ok(None), Err(())]")
+
+    results = session.variable("results")
+    expect_eq(results.GetChildAtIndex(1).GetSummary(), 'Err("bad input")')
+    expect_eq(results.GetChildAtIndex(1).GetChildAtIndex(0).GetSummary(), '"bad input"')
+    expect_eq(results.GetChildAtIndex(2).GetChildAtIndex(0).GetValue(), "3")
+
+
+@test("compact_test")
+def compact_mode_drops_whitespace(session: Session):
+    expect_eq(session.summary("numbers"), "size=3 [1, 2, 3]")
+    expect_eq(session.summary("map"), 'size=1 {1: "one"}')
+    expect_eq(session.summary("rect"), "Rect { w: 2.5, h: 4.0 }")
+    expect_in(session.summary("long"), "20, 21, 22, ...]")
+
+    session.command("rust-formatter-set compact true")
+    expect_eq(session.summary("numbers"), "size=3 [1,2,3]")
+    expect_eq(session.summary("map"), 'size=1 {1:"one"}')
+    expect_eq(session.summary("rect"), "Rect{w:2.5,h:4.0}")
+    expect_eq(session.summary("long"), "size=100 [0,1,2,3,4,5,6,7,8,9,10,11,12,...]")
+    expect_eq(session.variable("map").GetChildAtIndex(0).GetName(), "[1]")
+
+
+COMPACT_MIX_VALUES = (
+    "word",
+    "long_word",
+    "pair",
+    "names",
+    "rows",
+    "empty",
+    "cow_str",
+    "cow_owned",
+    "cow_slice",
+)
+
+
+def expect_single_line_balanced(text: str):
+    """Fails if text spans lines or a bracket outside its strings is unclosed"""
+    if "\n" in text:
+        raise AssertionError("summary spans lines: %r" % text)
+    closing = {"]": "[", "}": "{", ")": "("}
+    stack = []
+    in_string = False
+    for char in text:
+        if char == '"':
+            in_string = not in_string
+        elif in_string:
+            continue
+        elif char in "[{(":
+            stack.append(char)
+        elif char in closing:
+            if not stack or stack.pop() != closing[char]:
+                raise AssertionError("unbalanced %r in %r" % (char, text))
+    if stack or in_string:
+        raise AssertionError("unclosed %r in %r" % (stack or '"', text))
+    if "...." in text or "...,..." in text:
+        raise AssertionError("elided twice: %r" % text)
+
+
+@test("compact_mix_test")
+def compact_strings_smallvecs_and_cows(session: Session):
+    session.command("rust-formatter-set compact true")
+    session.command("rust-formatter-set preview-budget 10")
+    expect_eq(session.summary("word"), '"short"')
+    # Strings aren't previews, the budget doesn't cut them
+    expect_eq(session.summary("long_word"), '"a string too long to be stored inline"')
+    expect_eq(session.summary("pair"), 'size=2 ["a",...]')
+    expect_eq(session.summary("names"), 'size=3 ["alpha",...]')
+    expect_eq(session.summary("rows"), "size=2 [size=3 [1,2,...],...]")
+    expect_eq(session.summary("empty"), "size=0 []")
+    expect_eq(session.summary("cow_str"), 'Borrowed("text")')
+    expect_eq(session.summary("cow_owned"), "Owned(size=6 [1,2,...])")
+    expect_eq(session.summary("cow_slice"), "Borrowed(size=3 [7,8,...])")
+    for name in COMPACT_MIX_VALUES:
+        expect_single_line_balanced(session.summary(name))
+
+
+@test("compact_mix_test")
+def compact_metadata_shows_spilled_state(session: Session):
+    session.command("rust-formatter-set compact true")
+    session.command("rust-formatter-set metadata true")
+    expect_eq(session.summary("word"), '"short" (inline)')
+    expect_eq(session.summary("pair"), 'size=2 ["a" (inline),"b" (inline)] (inline)')
+    expect_eq(
+        session.summary("names"),
+        'size=3 ["alpha" (inline),"beta" (inline),"gamma" (inline)] (heap)',
+    )
+    expect_eq(session.summary("empty"), "size=0 [] (inline)")
+
+    # The whole budget goes to the elements, the metadata comes after
+    session.command("rust-formatter-set preview-budget 4")
+    expect_eq(session.summary("names"), 'size=3 ["alpha" (inline),...] (heap)')
+    for name in COMPACT_MIX_VALUES:
+        expect_single_line_balanced(session.summary(name))
+
+
+@test("niche_elements_test")
+def niche_option_elements_decode_per_element(session: Session):
+    expect_eq(session.summary("inline"), "size=2 [Some(1), None]")
+    expect_eq(session.summary("spilled"), "size=3 [None, Some(2), Some(3)]")
+    expect_eq(session.summary("boxes"), "size=3 [Some(4), None, Some(5)]")
+    expect_eq(session.variable("spilled").GetChildAtIndex(0).GetSummary(), "None")
+
+
+@test("struct_test")
+def structs_render_like_debug(session: Session):
+    expect_eq(
+        session.summary("config"), 'Config { name: "dev", retries: 3, verbose: true }'
+    )
+    config = session.variable("config")
+    expect_eq(config.GetNumChildren(), 3)
+    expect_eq(config.GetChildMemberWithName("retries").GetValue(), "3")
+
+    expect_eq(session.summary("meters"), "Meters(2.5)")
+    expect_eq(
+        session.summary("line"),
+        "Line { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }",
+    )
+    expect_eq(session.summary("wide").endswith(", ..)"), True)
+
+    session.command("rust-formatter-set struct-summaries false")
+    expect_eq(session.summary("config"), "")
+
+
+@test("struct_test")
+def phantom_fields_are_left_out(session: Session):
+    expect_eq(session.summary("typed"), "Typed { id: 5 }")
+    typed = session.variable("typed")
+    expect_eq(typed.GetNumChildren(), 1)
+    expect_eq(typed.GetChildAtIndex(0).GetName(), "id")
+    expect_eq(typed.GetChildMemberWithName("_marker").IsValid(), False)
+
+    expect_eq(session.summary("handle"), "Handle(7)")
+    expect_eq(session.variable("handle").GetNumChildren(), 1)
+
+
+@test("pin_test", breakpoint="inspect_pinned", frame=0)
+def pinned_self_formats_as_the_value(session: Session):
+    expect_eq(session.summary("self"), 'Counter { polls: 2, label: "ticks" }')
+    pinned = session.variable("self")
+    expect_eq(pinned.GetNumChildren(), 2)
+    expect_eq(pinned.GetChildMemberWithName("polls").GetValue(), "2")
+    expect_eq(pinned.GetChildMemberWithName("label").GetSummary(), '"ticks"')
+
+
+@test("pin_test")
+def pinned_boxes_format_as_the_value(session: Session):
+    expect_eq(session.summary("boxed"), "size=3 [1, 2, 3]")
+    expect_eq(session.variable("boxed").GetChildAtIndex(1).GetValue(), "2")
+
+
+@test("hash_alias_test")
+def hash_set_aliases_use_the_set_provider(session: Session):
+    for name in ("fx_set", "ahash_set", "hashbrown_set"):
+        expect_eq(session.summary(name).startswith("size=3 ["), True)
+        values = session.variable(name)
+        expect_eq(
+            sorted(values.GetChildAtIndex(i).GetValue() for i in range(3)),
+            ["10", "20", "30"],
+        )
+
+    expect_eq(session.summary("fx_map"), 'size=1 {1: "one"}')
+    expect_eq(session.summary("ahash_map"), 'size=1 {2: "two"}')
+    expect_eq(session.summary("hashbrown_map"), 'size=1 {3: "three"}')
+
+
+@test("json_test")
+def json_command_serializes_the_tree(session: Session):
+    tree = json.loads(session.command("rust-formatter-json maps"))
+    expect_eq(tree["name"], "maps")
+    expect_in(tree["type"], "Vec<")
+    expect_eq(tree["summary"].startswith("size=2 "), True)
+    expect_eq(len(tree["children"]), 2)
+
+    first = tree["children"][0]
+    expect_eq(first["name"], "[0]")
+    expect_eq(first["summary"], 'size=1 {"one": 1}')
+    expect_eq(first["children"][0]["name"], '["one"]')
+    expect_eq(first["children"][0]["value"], "1")
+    expect_eq(first["children"][0]["children"], [])
+    second = tree["children"][1]
+    expect_eq(
+        sorted((entry["name"], entry["value"]) for entry in second["children"]),
+        [('["three"]', "3"), ('["two"]', "2")],
+    )
+    expect_eq("truncated" in tree, False)
+
+    session.command("rust-formatter-set max-depth 1")
+    tree = json.loads(session.command("rust-formatter-json maps"))
+    expect_eq(tree["children"][0]["children"], [])
+    expect_eq(tree["children"][0]["truncated"], True)
+
+    session.command("rust-formatter-set max-children 1")
+    tree = json.loads(session.command("rust-formatter-json maps"))
+    expect_eq(len(tree["children"]), 1)
+
+
+@test("bstr_test")
+def byte_strings_decode_lossily(session: Session):
+    expect_eq(session.summary("text"), '"hello bytes"')
+    expect_eq(session.summary("mixed"), '"ok\ufffdbytes"')
+    expect_eq(session.summary("borrowed"), '"hello"')
+
+    session.command("rust-formatter-set hexdump true")
+    expect_eq(session.summary("mixed"), '"ok\\xffbytes"')
+    expect_eq(session.summary("text"), '"hello bytes"')
+
+
+@test("ecow_test")
+def eco_strings_inline_and_heap(session: Session):
+    expect_eq(session.summary("empty"), '""')
+    expect_eq(session.summary("inline"), '"short ecow"')
+    expect_eq(session.summary("full_inline"), '"fifteen bytes.."')
+    expect_eq(session.summary("heap"), '"an EcoString too long for the inline buffer"')
+    expect_eq(session.summary("shared"), session.summary("heap"))
+    expect_eq(session.summary("names"), 'size=2 ["a", "%s"]' % ("b" * 20))
+
+    session.command("rust-formatter-set metadata true")
+    expect_eq(session.summary("inline"), '"short ecow" (inline)')
+    expect_eq(
+        session.summary("heap"),
+        '"an EcoString too long for the inline buffer" (heap)',
+    )
+
+
+@test("compact_test")
+def hex_index_labels(session: Session):
+    expect_eq(session.variable("long").GetChildAtIndex(10).GetName(), "[10]")
+
+    session.command("rust-formatter-set index-radix 16")
+    long = session.variable("long")
+    expect_eq(long.GetChildAtIndex(10).GetName(), "[0x0a]")
+    expect_eq(long.GetChildAtIndex(10).GetValue(), "10")
+    expect_eq(long.GetChildMemberWithName("[0x63]").GetValue(), "99")
+    expect_eq(session.summary("numbers"), "size=3 [1, 2, 3]")
+
+    expect_in(session.command("rust-formatter-set index-radix 8"), "must be 10 or 16")
+
+
+@test("padded_option_test")
+def tagged_options_around_padding(session: Session):
+    bytes_summary = "size=33 [%s, ...]" % ", ".join(str(i) for i in range(1, 24))
+    expect_eq(session.summary("bytes"), "Some(%s)" % bytes_summary)
+    expect_eq(session.summary("no_bytes"), "None")
+    bytes_value = session.variable("bytes").GetChildAtIndex(0)
+    expect_eq(bytes_value.GetChildAtIndex(32).GetValue(), "33")
+
+    packet = session.variable("packet")
+    expect_eq(
+        packet.GetChildMemberWithName("payload").GetSummary(),
+        "Some(%s)" % bytes_summary,
+    )
+    expect_eq(packet.GetChildMemberWithName("crc").GetValue(), "3735928559")
+    empty = session.variable("empty")
+    expect_eq(empty.GetChildMemberWithName("payload").GetSummary(), "None")
+    expect_eq(empty.GetChildMemberWithName("header").GetValue(), "2")
+
+    expect_eq(
+        session.summary("big"),
+        "Some(Big { flag: true, bytes: %s, value: 42 })" % bytes_summary,
+    )
+    expect_eq(session.summary("no_big"), "None")
+
+
+@test("corrupt_enum_test")
+def corrupted_discriminants_are_marked(session: Session):
+    expect_eq(session.summary("maybe"), "<unknown variant #7>")
+    expect_eq(session.summary("color"), "<unknown variant #9>")
+    expect_eq(session.summary("intact"), "Green(2)")
+
+    session.command("rust-formatter-set verbose true")
+    expect_eq(session.summary("color"), "<unknown variant #9>")
+
+
+@test("tuple_test")
+def tuples_render_like_debug(session: Session):
+    expect_eq(session.summary("pairs"), 'size=2 [("alpha", 1), ("beta", -2)]')
+    second = session.variable("pairs").GetChildAtIndex(1)
+    expect_eq(second.GetSummary(), '("beta", -2)')
+    expect_eq(second.GetChildAtIndex(0).GetName(), "0")
+    expect_eq(second.GetChildMemberWithName("1").GetValue(), "-2")
+    # The names from the debug info still resolve
+    expect_eq(second.GetChildMemberWithName("__0").GetSummary(), '"beta"')
+
+    expect_eq(session.summary("single"), "(5,)")
+    expect_eq(session.summary("nested"), '((3, 4), "point")')
+    expect_eq(session.variable("nested").GetNumChildren(), 2)
+
+
+@test("no_std_test")
+def no_std_types_format_by_their_core_and_alloc_paths(session: Session):
+    expect_eq(session.variable("numbers").GetTypeName()[:15], "alloc::vec::Vec")
+    expect_eq(session.summary("numbers"), "size=3 [1, 2, 3]")
+    expect_eq(session.summary("name"), '"core"')
+    expect_eq(session.summary("some"), "Some(7)")
+    expect_eq(session.summary("none"), "None")
+    expect_eq(
+        session.summary("shared"), "(strong=1) RefCell { <unborrowed> size=2 [4, 5] }"
+    )
+    expect_eq(session.summary("sorted"), 'size=1 {1: "one"}')
+    expect_eq(session.summary("hashed"), "size=1 {2: 20}")
+
+
+@test("cell_test")
+def cells_forward_to_their_value(session: Session):
+    expect_eq(session.summary("counter"), "5")
+    expect_eq(session.summary("empty_link"), "None")
+    node = session.variable("node")
+    expect_eq(session.summary("link"), "Some(0x%x)" % node.GetLoadAddress())
+    expect_eq(session.summary("pair"), "(1, 2)")
+    expect_eq(session.variable("numbers").GetNumChildren(), 3)
+    expect_eq(session.summary("numbers"), "size=3 [1, 2, 3]")
+
+
+@test("int128_test")
+def int128_values_use_all_bits(session: Session):
+    expect_eq(session.variable("big").GetValue(), "55340232221128654852")
+    expect_eq(
+        session.variable("negative").GetValue(), "-1267650600228229401496703205381"
+    )
+    expect_eq(
+        session.summary("wide_vec"),
+        "size=3 [1, 340282366920938463463374607431768211455, 55340232221128654852]",
+    )
+    expect_eq(
+        session.summary("maybe_negative"), "Some(-1267650600228229401496703205381)"
+    )
+
+    session.command("rust-formatter-set int-radix 16")
+    expect_eq(
+        session.summary("wide_vec"),
+        "size=3 [0x1, 0xffffffffffffffffffffffffffffffff, 0x30000000000000004]",
+    )
+    expect_eq(
+        session.summary("maybe_negative"), "Some(0xffffffeffffffffffffffffffffffffb)"
+    )
+    expect_eq(session.summary("small_vec"), "size=2 [0xffffffff, 0xff]")
+    session.command("rust-formatter-set int-radix 10")
+    expect_eq(session.summary("small_vec"), "size=2 [-1, 255]")
+
+
+@test("systemtime_test")
+def system_times_before_the_epoch(session: Session):
+    expect_eq(session.summary("moon_landing"), "1969-07-20 20:17:40 UTC")
+    expect_eq(session.summary("just_before"), "1969-12-31 23:59:58.75 UTC")
+    expect_eq(session.summary("epoch"), "1970-01-01 00:00:00 UTC")
+    expect_eq(session.summary("leap_day"), "2024-02-29 12:00:00 UTC")
+    expect_eq(session.summary("maybe_time"), "Some(1969-07-20 20:17:40 UTC)")
+
+
+@test("repr_c_enum_test")
+def repr_c_enums_select_their_union_arm(session: Session):
+    expect_eq(session.summary("circle"), "Circle(1.5)")
+    expect_eq(session.summary("rect"), "Rect { w: 2.0, h: 3.5 }")
+    expect_eq(session.summary("empty"), "Empty")
+
+    rect = session.variable("rect")
+    expect_eq(rect.GetNumChildren(), 2)
+    expect_eq(rect.GetChildMemberWithName("h").GetValue(), "3.5")
+
+    expect_eq(session.summary("negative"), "Negative(5)")
+    expect_eq(session.summary("zero"), "Zero")
+    expect_eq(session.summary("large"), "Large(9)")
+    expect_eq(session.variable("large").GetChildAtIndex(0).GetValue(), "9")
+
+
+@test("niche_enum_test")
+def niche_enums_without_a_tag(session: Session):
+    # The pointer or bool payload doubles as the discriminant
+    expect_eq(session.summary("some_ptr")[:7], "Some(0x")
+    pointer = session.variable("some_ptr").GetChildAtIndex(0)
+    expect_eq(pointer.GetSummary()[:2], "0x")
+    expect_eq(session.summary("none_ptr"), "None")
+    expect_eq(session.variable("none_ptr").GetNumChildren(), 0)
+
+    expect_eq(session.summary("full"), "Full(7)")
+    expect_eq(session.summary("empty"), "Empty")
+    expect_eq(session.summary("set"), "Set(false)")
+    expect_eq(session.summary("unset"), "Unset")
+    expect_eq(session.summary("unknown"), "Unknown")
+
+
+@test("page_test")
+def huge_vecs_group_elements_into_pages(session: Session):
+    expect_eq(session.variable("huge").GetNumChildren(), 50000)
+
+    session.command("rust-formatter-set page-size 1000")
+    huge = session.variable("huge")
+    expect_eq(huge.GetNumChildren(), 50)
+    expect_eq(huge.GetChildAtIndex(0).GetName(), "[page 0..1000]")
+    expect_eq(huge.GetChildAtIndex(49).GetName(), "[page 49000..50000]")
+    expect_in(session.summary("huge"), "size=50000 [0, 1, 2, ")
+
+    # A page covers exactly its part of the buffer
+    page = huge.GetChildAtIndex(3)
+    expect_eq(page.GetNumChildren(), 1000)
+    expect_eq(page.GetByteSize(), 1000 * 8)
+    first = huge.GetChildAtIndex(0).GetChildAtIndex(0)
+    expect_eq(page.GetLoadAddress() - first.GetLoadAddress(), 3000 * 8)
+    expect_eq(page.GetChildAtIndex(0).GetValueAsUnsigned(), 3000)
+    expect_eq(page.GetChildAtIndex(999).GetValueAsUnsigned(), 3999)
+    expect_eq(huge.GetIndexOfChildWithName("[page 3000..4000]"), 3)
+
+    uneven = session.variable("uneven")
+    expect_eq(uneven.GetNumChildren(), 3)
+    last = uneven.GetChildAtIndex(2)
+    expect_eq(last.GetName(), "[page 2000..2500]")
+    expect_eq(last.GetNumChildren(), 500)
+    expect_eq(last.GetChildAtIndex(499).GetValueAsUnsigned(), 2499)
+
+    huge_slice = session.variable("huge_slice")
+    expect_eq(huge_slice.GetNumChildren(), 40)
+    first = huge_slice.GetChildAtIndex(0).GetChildAtIndex(0)
+    expect_eq(first.GetValueAsUnsigned(), 10000)
+
+    session.command("rust-formatter-set page-size 0")
+    expect_eq(session.variable("uneven").GetNumChildren(), 2500)
+
+
+@test("int_vec_test")
+def integer_vectors_keep_integer_components(session: Session):
+    expect_eq(session.summary("ivec"), "(1, -2, 3)")
+    expect_eq(session.summary("uvec"), "(10, 20, 255, 4000000000)")
+    expect_eq(session.summary("wide"), "(-5000000000, 7)")
+    expect_eq(session.summary("vector"), "(4, -5, 6)")
+    expect_eq(session.summary("float_vector"), "(1.5, 0.0, -2.25)")
+    # Matrix2::new takes rows, the storage holds columns
+    expect_eq(session.summary("matrix"), "[(1.0, 3.0), (2.0, 4.0)]")
+
+    session.command("rust-formatter-set int-radix 16")
+    expect_eq(session.summary("uvec"), "(0xa, 0x14, 0xff, 0xee6b2800)")
+    expect_eq(session.summary("vector"), "(0x4, 0xfffffffb, 0x6)")
+    expect_eq(session.summary("float_vector"), "(1.5, 0.0, -2.25)")
+
+
+@test("exit_status_test")
+def exit_statuses_and_std_errors(session: Session):
+    expect_eq(session.summary("success"), "exit status: 0")
+    expect_eq(session.summary("failure"), "exit status: 3")
+    expect_eq(session.summary("killed"), "signal: 9 (SIGKILL)")
+
+    expect_eq(
+        session.summary("out_of_range"),
+        "out of range integral type conversion attempted",
+    )
+    expect_eq(session.summary("invalid_digit"), "invalid digit found in string")
+    expect_eq(session.summary("too_large"), "number too large to fit in target type")
+    expect_eq(session.summary("empty_float"), "cannot parse float from empty string")
+    expect_eq(
+        session.summary("bad_utf8"), "invalid utf-8 sequence of 1 bytes from index 1"
+    )
+    expect_eq(
+        session.summary("truncated_utf8"),
+        "incomplete utf-8 byte sequence from index 1",
+    )
+
+
+def main(argv) -> int:
+    global UPDATE_GOLDEN
+    UPDATE_GOLDEN = "--update" in argv[1:]
+    selected = set(arg for arg in argv[1:] if arg != "--update")
+    failures = 0
+    for name, binary, breakpoint, frame, triple, func in TESTS:
+        if selected and name not in selected:
+            continue
+        session = None
+        try:
+            session = Session(binary, breakpoint, frame, triple)
+            func(session)
+            print("PASS %s" % name)
+        except SkipTest as e:
+            print("SKIP %s: %s" % (name, e))
+        except Exception:
+            failures += 1
+            print("FAIL %s" % name)
+            traceback.print_exc()
+        finally:
+            if session is not None:
+                session.close()
+
+    print("%d failed" % failures if failures else "all passed")
+    return 1 if failures else 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv))
@@ -0,0 +1,16 @@
+#!/bin/bash
+# Builds the test crate and runs rust-formatter-selftest against it.
+# Exits non-zero if any check fails, so it can run as a CI step.
+set -uo pipefail
+cd "$(dirname "$0")"
+
+cargo build || exit 1
+
+output=$(lldb --batch target/debug/smolstr_debug_test \
+  -o "command script import ../rust_bonus_types.py" \
+  -o "breakpoint set --name inspect_variables" \
+  -o "run" \
+  -o "rust-formatter-selftest" 2>&1)
+echo "$output"
+
+grep -q "^selftest: [0-9]* passed, 0 failed" <<<"$output"
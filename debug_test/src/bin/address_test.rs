@@ -0,0 +1,15 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let boxed = Box::new(5i32);
+    let name = String::from("text");
+    let name_ref = &name;
+    let number = 7u64;
+
+    inspect_variables();
+
+    println!("{} {} {} {}", boxed, name, name_ref, number);
+}
@@ -0,0 +1,28 @@
+use std::io;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let not_found = io::Error::from_raw_os_error(2);
+    let denied = io::Error::from_raw_os_error(13);
+
+    let message = anyhow::anyhow!("config missing");
+    let wrapped = anyhow::Error::new(io::Error::from_raw_os_error(2));
+
+    // Prevent optimization
+    std::hint::black_box(&not_found);
+    std::hint::black_box(&denied);
+    std::hint::black_box(&message);
+    std::hint::black_box(&wrapped);
+
+    inspect_variables();
+
+    println!("not_found: {:?}", not_found);
+    println!("denied: {:?}", denied);
+    println!("message: {:?}", message);
+    println!("wrapped: {:?}", wrapped);
+}
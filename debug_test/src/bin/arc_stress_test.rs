@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[inline(never)]
+fn inspect_variables(shared: &Arc<Vec<u64>>) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box(shared);
+}
+
+fn main() {
+    let shared = Arc::new(vec![1u64, 2, 3]);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // Keeps cloning and dropping the Arc so the counts are in flux
+    // whenever the debugger stops the process
+    let churn = {
+        let shared = Arc::clone(&shared);
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut clones = Vec::new();
+            while !stop.load(Ordering::Relaxed) {
+                clones.push(Arc::clone(&shared));
+                clones.push(Arc::clone(&shared));
+                let weak = Arc::downgrade(&shared);
+                clones.pop();
+                if clones.len() > 64 {
+                    clones.clear();
+                }
+                drop(weak);
+            }
+        })
+    };
+
+    thread::sleep(Duration::from_millis(50));
+    inspect_variables(&shared);
+
+    stop.store(true, Ordering::Relaxed);
+    churn.join().unwrap();
+
+    println!(
+        "shared: {:?} (strong={})",
+        shared,
+        Arc::strong_count(&shared)
+    );
+}
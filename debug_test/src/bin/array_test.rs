@@ -0,0 +1,33 @@
+struct Frame<const N: usize> {
+    id: u32,
+    payload: [u8; N],
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let mut pattern = [0u8; 1024];
+    for (i, byte) in pattern.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let small = [1i32, 2, 3];
+    let frame = Frame::<512> {
+        id: 7,
+        payload: [0xab; 512],
+    };
+
+    // Prevent optimization
+    std::hint::black_box(&pattern);
+    std::hint::black_box(&small);
+    std::hint::black_box(&frame);
+
+    inspect_variables();
+
+    println!("pattern: {:?}", &pattern[..4]);
+    println!("small: {:?}", small);
+    println!("frame: {} {:?}", frame.id, &frame.payload[..4]);
+}
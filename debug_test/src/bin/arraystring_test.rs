@@ -0,0 +1,19 @@
+use arrayvec::ArrayString;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let partial = ArrayString::<16>::from("hello").unwrap();
+    // "größe" is 7 bytes, filling the buffer exactly
+    let full = ArrayString::<7>::from("größe").unwrap();
+    let empty = ArrayString::<8>::new();
+
+    inspect_variables();
+
+    println!("partial: {:?}", partial);
+    println!("full: {:?}", full);
+    println!("empty: {:?}", empty);
+}
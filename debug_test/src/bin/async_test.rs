@@ -0,0 +1,60 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// Returns Pending on the first poll, so an await on it suspends
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        Poll::Pending
+    }
+}
+
+async fn work(count: u32) -> u32 {
+    let doubled = count * 2;
+    YieldOnce(false).await;
+    doubled + 1
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let idle = work(1);
+
+    // Polled once, so paused at the await
+    let mut suspended = work(3);
+    let first = unsafe { Pin::new_unchecked(&mut suspended) }.poll(&mut cx);
+
+    let mut finished = work(5);
+    while unsafe { Pin::new_unchecked(&mut finished) }
+        .poll(&mut cx)
+        .is_pending()
+    {}
+
+    inspect_variables();
+
+    println!("{:?}", first);
+    drop((idle, suspended, finished));
+}
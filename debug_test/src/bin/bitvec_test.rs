@@ -0,0 +1,31 @@
+use bitvec::prelude::*;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let lsb: BitVec<u8, Lsb0> = bitvec![u8, Lsb0; 1, 0, 1, 1, 0, 0, 1, 0, 1, 1];
+    let msb: BitVec<u16, Msb0> = bitvec![u16, Msb0; 1, 1, 0, 1, 0, 0, 0, 1, 1, 0, 1, 1];
+    let empty: BitVec<u8, Lsb0> = BitVec::new();
+    // Starts at bit 9 of the first u16, which bitvec keeps in the pointer
+    let tail: &BitSlice<u16, Msb0> = &msb[9..];
+    let many: BitVec<u32, Lsb0> = (0..300).map(|i| i % 3 == 0).collect();
+
+    // Prevent optimization
+    std::hint::black_box(&lsb);
+    std::hint::black_box(&msb);
+    std::hint::black_box(&empty);
+    std::hint::black_box(&tail);
+    std::hint::black_box(&many);
+
+    inspect_variables();
+
+    println!("lsb: {:?}", lsb);
+    println!("msb: {:?}", msb);
+    println!("empty: {:?}", empty);
+    println!("tail: {:?}", tail);
+    println!("many: {}", many.len());
+}
@@ -0,0 +1,22 @@
+use smallvec::SmallVec;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let flags = vec![true, false, true, true, false];
+    let small_flags: SmallVec<bool, 4> = SmallVec::from([false, true, true]);
+    let empty_flags: Vec<bool> = Vec::new();
+    // Same bytes as bools, but must keep rendering as numbers
+    let bytes: Vec<i8> = vec![1, 0];
+    let nested_flags = vec![vec![true; 100], vec![false; 3]];
+
+    inspect_variables();
+
+    println!(
+        "{:?} {:?} {:?} {:?} {:?}",
+        flags, small_flags, empty_flags, bytes, nested_flags
+    );
+}
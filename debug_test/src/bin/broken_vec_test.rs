@@ -0,0 +1,24 @@
+use std::collections::VecDeque;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut broken: Vec<i32> = Vec::with_capacity(4);
+    broken.extend([1, 2, 3, 4]);
+    let capacity = broken.capacity();
+    // The bug being chased: a len far past the allocation
+    unsafe { broken.set_len(1_000_000) };
+    std::hint::black_box(&broken);
+    // VecDeque has no set_len, the test sets its len from the debugger
+    let mut deque: VecDeque<i32> = VecDeque::with_capacity(4);
+    deque.extend([1, 2, 3, 4]);
+
+    inspect_variables();
+
+    // Restore the len before anything reads the elements
+    unsafe { broken.set_len(4) };
+    println!("broken: {:?} (capacity {}) {:?}", broken, capacity, deque);
+}
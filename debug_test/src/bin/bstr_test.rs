@@ -0,0 +1,18 @@
+use bstr::{BStr, BString, ByteSlice};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let text = BString::from("hello bytes");
+    let mixed = BString::from(b"ok\xffbytes".to_vec());
+    let borrowed: &BStr = text[..5].as_bstr();
+
+    inspect_variables();
+
+    println!("text: {:?}", text);
+    println!("mixed: {:?}", mixed);
+    println!("borrowed: {:?}", borrowed);
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+#[inline(never)]
+fn inspect_variables(small: &BTreeMap<u32, String>, large: &BTreeMap<u64, [u8; 64]>) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((small, large));
+}
+
+fn main() {
+    let mut small = BTreeMap::new();
+    small.insert(2, String::from("two"));
+    small.insert(1, String::from("one"));
+    small.insert(3, String::from("three"));
+
+    // Large values push the vals array far from the keys, and 40 entries
+    // need internal nodes. Each value is filled with its key.
+    let mut large = BTreeMap::new();
+    for key in (0..40u64).rev() {
+        large.insert(key, [key as u8; 64]);
+    }
+    let empty: BTreeMap<u32, u32> = BTreeMap::new();
+
+    inspect_variables(&small, &large);
+
+    println!("small: {:?}", small);
+    println!("large: {} entries", large.len());
+    println!("empty: {:?}", empty);
+}
@@ -0,0 +1,18 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let huge_tree: BTreeMap<u32, Vec<u32>> = (0..300_000).map(|i| (i, vec![i, i + 1])).collect();
+    let huge_map: HashMap<u32, u32> = (0..300_000).map(|i| (i, i * 2)).collect();
+    let small_tree: BTreeMap<u32, u32> = (0..3).map(|i| (i, i)).collect();
+
+    inspect_variables();
+
+    println!("huge_tree: {}", huge_tree.len());
+    println!("huge_map: {}", huge_map.len());
+    println!("small_tree: {:?}", small_tree);
+}
@@ -0,0 +1,23 @@
+use std::cell::{Cell, UnsafeCell};
+use std::ptr::NonNull;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let counter = Cell::new(5u32);
+    let mut node = 0u64;
+    let link: Cell<Option<NonNull<u64>>> = Cell::new(Some(NonNull::from(&mut node)));
+    let empty_link: Cell<Option<NonNull<u64>>> = Cell::new(None);
+    let pair = Cell::new((1u8, 2u8));
+    let numbers = UnsafeCell::new(vec![1, 2, 3]);
+
+    inspect_variables();
+
+    println!("counter: {:?}", counter);
+    println!("link: {:?} {:?}", link, empty_link);
+    println!("pair: {:?} node: {}", pair, node);
+    println!("numbers: {:?}", unsafe { &*numbers.get() });
+}
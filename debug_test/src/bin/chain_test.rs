@@ -0,0 +1,17 @@
+#[inline(never)]
+fn inspect_variables(some_chain: &Option<Box<Vec<String>>>, none_chain: &Option<Box<Vec<String>>>) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((some_chain, none_chain));
+}
+
+fn main() {
+    // Option -> Box -> Vec -> String, every layer has its own provider
+    let some_chain: Option<Box<Vec<String>>> =
+        Some(Box::new(vec![String::from("alpha"), String::from("beta")]));
+    let none_chain: Option<Box<Vec<String>>> = None;
+
+    inspect_variables(&some_chain, &none_chain);
+
+    println!("some_chain: {:?}", some_chain);
+    println!("none_chain: {:?}", none_chain);
+}
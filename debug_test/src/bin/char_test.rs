@@ -0,0 +1,16 @@
+#[inline(never)]
+fn inspect_variables(chars: &[char], single: char) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((chars, single));
+}
+
+fn main() {
+    // ASCII, multi-byte scalars and characters Debug escapes
+    let chars: Vec<char> = vec!['a', 'ß', '€', '🦀', '\n', '\'', '\u{7f}'];
+    let single = 'z';
+
+    inspect_variables(&chars, single);
+
+    println!("chars: {:?}", chars);
+    println!("single: {:?}", single);
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeDelta, TimeZone, Timelike, Utc};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let leap_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    let new_years_eve = NaiveDate::from_ymd_opt(1999, 12, 31).unwrap();
+    let ides = NaiveDate::from_ymd_opt(-44, 3, 15).unwrap();
+    let far_future = NaiveDate::from_ymd_opt(12345, 1, 1).unwrap();
+
+    let ninety_seconds = TimeDelta::new(90, 500_000_000).unwrap();
+    let days_and_hours = TimeDelta::new(2 * 86_400 + 3 * 3_600, 0).unwrap();
+    // Stored as secs=-2, nanos=500000000
+    let negative = TimeDelta::new(-2, 500_000_000).unwrap();
+    let zero = TimeDelta::zero();
+
+    let noon = leap_day.and_hms_opt(12, 30, 0).unwrap();
+    let utc: DateTime<Utc> = Utc
+        .with_ymd_and_hms(1999, 12, 31, 23, 59, 59)
+        .unwrap()
+        .with_nanosecond(500_000_000)
+        .unwrap();
+    // Stored as 10:30 UTC with local_minus_utc = 7200
+    let plus_two: DateTime<FixedOffset> = FixedOffset::east_opt(2 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2024, 2, 29, 12, 30, 0)
+        .unwrap();
+    let minus_five_thirty: DateTime<FixedOffset> = FixedOffset::west_opt(5 * 3600 + 30 * 60)
+        .unwrap()
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .unwrap();
+    let local: DateTime<Local> = utc.with_timezone(&Local);
+
+    inspect_variables();
+
+    println!("{} {} {} {}", leap_day, new_years_eve, ides, far_future);
+    println!(
+        "{} {} {} {}",
+        ninety_seconds, days_and_hours, negative, zero
+    );
+    println!(
+        "{} {} {} {} {}",
+        noon, utc, plus_two, minus_five_thirty, local
+    );
+}
@@ -0,0 +1,42 @@
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+use std::borrow::Cow;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let word = SmolStr::new("short");
+    let long_word = SmolStr::new("a string too long to be stored inline");
+
+    let pair: SmallVec<SmolStr, 2> = SmallVec::from([SmolStr::new("a"), SmolStr::new("b")]);
+    let names: SmallVec<SmolStr, 2> = ["alpha", "beta", "gamma"]
+        .into_iter()
+        .map(SmolStr::new)
+        .collect();
+    let rows: SmallVec<Vec<i32>, 2> = SmallVec::from([vec![1, 2, 3], vec![4]]);
+    let empty: SmallVec<SmolStr, 2> = SmallVec::new();
+
+    let cow_str: Cow<'static, str> = Cow::Borrowed("text");
+    let cow_owned: Cow<'static, [i32]> = Cow::Owned(vec![1, 2, 3, 4, 5, 6]);
+    let cow_slice: Cow<'static, [i32]> = Cow::Borrowed(&[7, 8, 9]);
+
+    // Prevent optimization
+    std::hint::black_box(&word);
+    std::hint::black_box(&long_word);
+    std::hint::black_box(&pair);
+    std::hint::black_box(&names);
+    std::hint::black_box(&rows);
+    std::hint::black_box(&empty);
+    std::hint::black_box(&cow_str);
+    std::hint::black_box(&cow_owned);
+    std::hint::black_box(&cow_slice);
+
+    inspect_variables();
+
+    println!("{} {} {:?} {:?} {:?}", word, long_word, pair, names, rows);
+    println!("{:?} {} {:?} {:?}", empty, cow_str, cow_owned, cow_slice);
+}
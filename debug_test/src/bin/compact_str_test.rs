@@ -0,0 +1,45 @@
+use compact_str::CompactString;
+
+#[inline(never)]
+fn inspect_variables(
+    inline_23: &CompactString,
+    inline_24: &CompactString,
+    heap_25: &CompactString,
+    inline_24_multibyte: &CompactString,
+    static_str: &CompactString,
+) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((
+        inline_23,
+        inline_24,
+        heap_25,
+        inline_24_multibyte,
+        static_str,
+    ));
+}
+
+fn main() {
+    // Last inline length that stores the length in the tag byte
+    let inline_23 = CompactString::new("abcdefghijklmnopqrstuvw");
+    // Full capacity: the tag byte is the last character
+    let inline_24 = CompactString::new("abcdefghijklmnopqrstuvwx");
+    // One past the inline capacity
+    let heap_25 = CompactString::new("abcdefghijklmnopqrstuvwxy");
+    // Full capacity ending in a UTF-8 continuation byte
+    let inline_24_multibyte = CompactString::new("abcdefghijklmnopqrstuvü");
+    let static_str = CompactString::const_new("static string");
+
+    inspect_variables(
+        &inline_23,
+        &inline_24,
+        &heap_25,
+        &inline_24_multibyte,
+        &static_str,
+    );
+
+    println!("inline_23: {}", inline_23);
+    println!("inline_24: {}", inline_24);
+    println!("heap_25: {}", heap_25);
+    println!("inline_24_multibyte: {}", inline_24_multibyte);
+    println!("static_str: {}", static_str);
+}
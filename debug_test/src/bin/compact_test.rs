@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+#[derive(Debug)]
+enum Shape {
+    Rect { w: f64, h: f64 },
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let numbers = vec![1, 2, 3];
+    let long: Vec<u32> = (0..100).collect();
+    let map: HashMap<u32, &str> = HashMap::from([(1, "one")]);
+    let rect = Shape::Rect { w: 2.5, h: 4.0 };
+
+    inspect_variables();
+
+    println!("numbers: {:?}", numbers);
+    println!("long: {:?}", long.len());
+    println!("map: {:?}", map);
+    println!("rect: {:?}", rect);
+}
@@ -0,0 +1,31 @@
+#[derive(Debug)]
+#[allow(dead_code)]
+enum Color {
+    Red(u8),
+    Green(u8),
+    Blue(u8),
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+/// Overwrites the tag byte of an enum whose tag is its first byte
+fn corrupt_tag<T>(value: &mut T, tag: u8) {
+    unsafe { std::ptr::write_volatile(value as *mut T as *mut u8, tag) }
+}
+
+fn main() {
+    let mut maybe: Option<u32> = Some(5);
+    let mut color = Color::Red(1);
+    let intact = Color::Green(2);
+
+    corrupt_tag(&mut maybe, 7);
+    corrupt_tag(&mut color, 9);
+
+    inspect_variables();
+
+    // Only the addresses, reading the corrupted values is undefined behavior
+    println!("{:p} {:p} {:?}", &maybe, &color, intact);
+}
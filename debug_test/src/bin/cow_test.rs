@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let borrowed: Cow<'static, str> = Cow::Borrowed("static text");
+    let owned: Cow<'static, str> = Cow::Owned(String::from("owned text"));
+
+    let arc_borrowed: Arc<Cow<'static, str>> = Arc::new(borrowed.clone());
+    let arc_owned: Arc<Cow<'static, str>> = Arc::new(owned.clone());
+    let arc_owned_clone = Arc::clone(&arc_owned);
+
+    inspect_variables();
+
+    println!(
+        "{} {} {} {}",
+        borrowed, owned, arc_borrowed, arc_owned_clone
+    );
+}
@@ -0,0 +1,26 @@
+use std::time::{Duration, Instant};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let duration = Duration::from_millis(1500);
+    let some_duration = Some(Duration::from_secs(90));
+    let none_duration: Option<Duration> = None;
+    let durations = vec![
+        Duration::from_secs(7200),
+        Duration::from_micros(250),
+        Duration::ZERO,
+    ];
+    let started = Instant::now();
+    let some_instant = Some(started);
+
+    inspect_variables();
+
+    println!(
+        "{:?} {:?} {:?} {:?} {:?} {:?}",
+        duration, some_duration, none_duration, durations, started, some_instant
+    );
+}
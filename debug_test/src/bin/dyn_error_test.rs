@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::io;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn open_config(fail: bool) -> Result<u32, Box<dyn Error>> {
+    if fail {
+        return Err(Box::new(io::Error::new(
+            io::ErrorKind::NotFound,
+            "missing file",
+        )));
+    }
+    Ok(3)
+}
+
+fn main() {
+    let ok = open_config(false);
+    let custom = open_config(true);
+    let os: Result<u32, Box<dyn Error>> = Err(Box::new(io::Error::from_raw_os_error(2)));
+    let simple: Result<u32, Box<dyn Error>> =
+        Err(Box::new(io::Error::from(io::ErrorKind::TimedOut)));
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?} {:?}", ok, custom, os, simple);
+}
@@ -0,0 +1,22 @@
+use ecow::EcoString;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let empty = EcoString::new();
+    let inline = EcoString::from("short ecow");
+    let full_inline = EcoString::from("fifteen bytes..");
+    let heap = EcoString::from("an EcoString too long for the inline buffer");
+    let shared = heap.clone();
+    let names: Vec<EcoString> = vec![EcoString::from("a"), EcoString::from("b".repeat(20))];
+
+    inspect_variables();
+
+    println!("empty: {:?}", empty);
+    println!("inline: {:?} {:?}", inline, full_inline);
+    println!("heap: {:?} {:?}", heap, shared);
+    println!("names: {:?}", names);
+}
@@ -0,0 +1,77 @@
+use indexmap::IndexMap;
+use smallvec::SmallVec;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    // Empty collections keep dangling or shared placeholder pointers that
+    // must not be read
+    let vec_empty: Vec<u32> = Vec::new();
+    let vec_one = vec![1u32];
+    let mut vec_full: Vec<u32> = Vec::with_capacity(4);
+    vec_full.extend([1, 2, 3, 4]);
+
+    let deque_empty: VecDeque<u32> = VecDeque::new();
+    let deque_one = VecDeque::from([1u32]);
+    // Full, with the elements wrapped around the end of the buffer
+    let mut deque_full: VecDeque<u32> = VecDeque::with_capacity(4);
+    deque_full.extend([1, 2, 3, 4]);
+    deque_full.pop_front();
+    deque_full.push_back(5);
+
+    let smallvec_empty: SmallVec<u32, 4> = SmallVec::new();
+    let smallvec_one: SmallVec<u32, 4> = SmallVec::from_slice(&[1]);
+    let smallvec_full: SmallVec<u32, 4> = SmallVec::from_slice(&[1, 2, 3, 4]);
+
+    let hashmap_empty: HashMap<u32, u32> = HashMap::new();
+    let hashmap_one = HashMap::from([(1u32, 10u32)]);
+    // Three entries fill the four buckets of with_capacity(3)
+    let mut hashmap_full: HashMap<u32, u32> = HashMap::with_capacity(3);
+    hashmap_full.extend([(1, 10), (2, 20), (3, 30)]);
+
+    let hashset_empty: HashSet<u32> = HashSet::new();
+    let hashset_one = HashSet::from([1u32]);
+    let mut hashset_full: HashSet<u32> = HashSet::with_capacity(3);
+    hashset_full.extend([1, 2, 3]);
+
+    // Eleven entries fill a single B-tree node
+    let btreemap_empty: BTreeMap<u32, u32> = BTreeMap::new();
+    let btreemap_one = BTreeMap::from([(1u32, 10u32)]);
+    let btreemap_full: BTreeMap<u32, u32> = (1..=11).map(|i| (i, i * 10)).collect();
+
+    let btreeset_empty: BTreeSet<u32> = BTreeSet::new();
+    let btreeset_one = BTreeSet::from([1u32]);
+    let btreeset_full: BTreeSet<u32> = (1..=11).collect();
+
+    let indexmap_empty: IndexMap<u32, u32> = IndexMap::new();
+    let indexmap_one = IndexMap::from([(1u32, 10u32)]);
+    let mut indexmap_full: IndexMap<u32, u32> = IndexMap::with_capacity(3);
+    indexmap_full.extend([(3, 30), (1, 10), (2, 20)]);
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?}", vec_empty, vec_one, vec_full);
+    println!("{:?} {:?} {:?}", deque_empty, deque_one, deque_full);
+    println!(
+        "{:?} {:?} {:?}",
+        smallvec_empty, smallvec_one, smallvec_full
+    );
+    println!("{:?} {:?} {:?}", hashmap_empty, hashmap_one, hashmap_full);
+    println!("{:?} {:?} {:?}", hashset_empty, hashset_one, hashset_full);
+    println!(
+        "{:?} {:?} {:?}",
+        btreemap_empty, btreemap_one, btreemap_full
+    );
+    println!(
+        "{:?} {:?} {:?}",
+        btreeset_empty, btreeset_one, btreeset_full
+    );
+    println!(
+        "{:?} {:?} {:?}",
+        indexmap_empty, indexmap_one, indexmap_full
+    );
+}
@@ -0,0 +1,39 @@
+#[derive(Debug)]
+enum Shape {
+    Rect { w: f64, h: f64 },
+    Circle(f64),
+    Empty,
+}
+
+// Only one variant, so the layout needs no tag at all
+#[derive(Debug)]
+enum Single {
+    Data(u32, i64),
+}
+
+#[inline(never)]
+fn inspect_variables(
+    rect: &Shape,
+    circle: &Shape,
+    empty: &Shape,
+    single: &Single,
+    maybe: &Option<i32>,
+) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((rect, circle, empty, single, maybe));
+}
+
+fn main() {
+    let rect = Shape::Rect { w: 2.5, h: 4.0 };
+    let circle = Shape::Circle(1.5);
+    let empty = Shape::Empty;
+    let single = Single::Data(7, -3);
+    let maybe = Some(5);
+
+    inspect_variables(&rect, &circle, &empty, &single, &maybe);
+
+    println!("rect: {:?}", rect);
+    println!("circle: {:?}", circle);
+    println!("empty: {:?}", empty);
+    println!("single: {:?}", single);
+}
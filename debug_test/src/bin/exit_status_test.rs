@@ -0,0 +1,37 @@
+use std::process::{Command, ExitStatus};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn shell(script: &str) -> ExitStatus {
+    Command::new("sh")
+        .args(["-c", script])
+        .status()
+        .expect("sh runs")
+}
+
+fn main() {
+    let success = shell("exit 0");
+    let failure = shell("exit 3");
+    let killed = shell("kill -9 $$");
+
+    let out_of_range = u8::try_from(300i32).unwrap_err();
+    let invalid_digit = "12a".parse::<i32>().unwrap_err();
+    let too_large = "300".parse::<u8>().unwrap_err();
+    let empty_float = "".parse::<f64>().unwrap_err();
+    let bad_utf8 = std::str::from_utf8(&[b'a', 0xff, b'b']).unwrap_err();
+    let truncated_utf8 = std::str::from_utf8(&[b'a', 0xe2, 0x82]).unwrap_err();
+
+    std::hint::black_box((&success, &failure, &killed));
+    std::hint::black_box((&out_of_range, &invalid_digit, &too_large));
+    std::hint::black_box((&empty_float, &bad_utf8, &truncated_utf8));
+
+    inspect_variables();
+
+    println!("{} / {} / {}", success, failure, killed);
+    println!("{} / {} / {}", out_of_range, invalid_digit, too_large);
+    println!("{} / {} / {}", empty_float, bad_utf8, truncated_utf8);
+}
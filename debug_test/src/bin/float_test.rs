@@ -0,0 +1,24 @@
+use smallvec::SmallVec;
+
+#[inline(never)]
+fn inspect_variables(doubles: &[f64], singles: &[f32]) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((doubles, singles));
+}
+
+fn main() {
+    // Values whose full-precision rendering is far longer than Debug's
+    let doubles = vec![0.1, 0.2, 0.3];
+    let awkward = vec![0.1 + 0.2, 1e16, 1.5e-7, 2.0];
+    let singles: SmallVec<f32, 4> = SmallVec::from([1.3f32, -0.0, f32::MAX]);
+    let special = vec![f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+    let some_float = Some(0.1f64);
+
+    inspect_variables(&doubles, &singles);
+
+    println!("doubles: {:?}", doubles);
+    println!("awkward: {:?}", awkward);
+    println!("singles: {:?}", singles);
+    println!("special: {:?}", special);
+    println!("some_float: {:?}", some_float);
+}
@@ -0,0 +1,29 @@
+use glam::{Affine2, Affine3A, Quat, Vec2, Vec3A};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let quarter_turn = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+    let identity = Quat::IDENTITY;
+    let transform = Affine3A::from_cols(
+        Vec3A::new(2.0, 0.0, 0.0),
+        Vec3A::new(0.0, 2.0, 0.0),
+        Vec3A::new(0.0, 0.0, 2.0),
+        Vec3A::new(1.0, 2.0, 3.0),
+    );
+    let flat = Affine2::from_cols(
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(5.0, -6.5),
+    );
+
+    inspect_variables();
+
+    println!("quarter_turn: {:?}", quarter_turn);
+    println!("identity: {:?}", identity);
+    println!("transform: {:?}", transform);
+    println!("flat: {:?}", flat);
+}
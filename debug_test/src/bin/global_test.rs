@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+static NUMBERS: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+static EMPTY: Vec<u8> = Vec::new();
+static NAMES: LazyLock<HashMap<u32, &str>> = LazyLock::new(|| HashMap::from([(1, "one")]));
+static UNUSED: LazyLock<Vec<i32>> = LazyLock::new(|| vec![1, 2, 3]);
+static CONFIG: OnceLock<String> = OnceLock::new();
+static UNSET: OnceLock<String> = OnceLock::new();
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    NUMBERS.lock().unwrap().extend([1, 2, 3]);
+    let _ = NAMES.len();
+    CONFIG.set(String::from("release")).unwrap();
+
+    inspect_variables();
+
+    println!("NUMBERS: {:?}", NUMBERS.lock().unwrap());
+    println!("EMPTY: {:?}", EMPTY);
+    println!("NAMES: {:?}", *NAMES);
+    println!("CONFIG: {:?}", CONFIG.get());
+    println!("UNSET: {:?}", UNSET.get());
+    println!("UNUSED: {:?}", UNUSED.len());
+}
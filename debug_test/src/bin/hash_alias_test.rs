@@ -0,0 +1,22 @@
+use ahash::{AHashMap, AHashSet};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let fx_set: FxHashSet<u32> = [10, 20, 30].into_iter().collect();
+    let ahash_set: AHashSet<u32> = [10, 20, 30].into_iter().collect();
+    let hashbrown_set: hashbrown::HashSet<u32> = [10, 20, 30].into_iter().collect();
+
+    let fx_map: FxHashMap<u32, &str> = [(1, "one")].into_iter().collect();
+    let ahash_map: AHashMap<u32, &str> = [(2, "two")].into_iter().collect();
+    let hashbrown_map: hashbrown::HashMap<u32, &str> = [(3, "three")].into_iter().collect();
+
+    inspect_variables();
+
+    println!("sets: {:?} {:?} {:?}", fx_set, ahash_set, hashbrown_set);
+    println!("maps: {:?} {:?} {:?}", fx_map, ahash_map, hashbrown_map);
+}
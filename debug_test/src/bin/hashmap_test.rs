@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut grid: HashMap<(u32, u32), String> = HashMap::new();
+    grid.insert((1, 2), String::from("a"));
+
+    let mut names: HashMap<String, u32> = HashMap::new();
+    names.insert(String::from("one"), 1);
+    names.insert(String::from("two"), 2);
+
+    let mut points: HashMap<Point, &str> = HashMap::new();
+    points.insert(Point { x: 3, y: 4 }, "p");
+
+    let empty: HashMap<u32, u32> = HashMap::new();
+
+    // Heavy churn leaves DELETED control bytes among the live entries
+    let mut churned: HashMap<u32, u32> = HashMap::new();
+    for key in 0..1000 {
+        churned.insert(key, key * 2);
+    }
+    churned.retain(|key, _| key % 50 == 0);
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?} {:?}", grid, names, points, empty);
+    println!("{:?}", churned);
+}
@@ -0,0 +1,25 @@
+use indexmap::IndexSet;
+
+#[inline(never)]
+fn inspect_variables(numbers: &IndexSet<u32>, words: &IndexSet<String>) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((numbers, words));
+}
+
+fn main() {
+    // Inserted out of sorted order; the duplicate 10 keeps its first slot
+    let mut numbers = IndexSet::new();
+    for n in [30, 10, 40, 10, 20] {
+        numbers.insert(n);
+    }
+
+    let mut words = IndexSet::new();
+    for word in ["zeta", "alpha", "mu"] {
+        words.insert(String::from(word));
+    }
+
+    inspect_variables(&numbers, &words);
+
+    println!("numbers: {:?}", numbers);
+    println!("words: {:?}", words);
+}
@@ -0,0 +1,64 @@
+use arrayvec::{ArrayString, ArrayVec};
+use smallvec::SmallVec;
+use tinyvec::TinyVec;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // Each container is filled and then shrunk, so the slots past the
+    // length still hold the old elements
+    let mut numbers: ArrayVec<i32, 5> = (1..=5).collect();
+    numbers.truncate(2);
+
+    // The popped strings are dropped, their slots point at freed memory
+    let mut strings: ArrayVec<String, 4> = ArrayVec::new();
+    for word in ["kept", "gone", "freed"] {
+        strings.push(String::from(word));
+    }
+    strings.truncate(1);
+
+    let empty: ArrayVec<u64, 3> = ArrayVec::new();
+    // Zero-sized, the test corrupts its len from the debugger
+    let units: ArrayVec<(), 3> = ArrayVec::from([(), (), ()]);
+
+    let mut tiny: tinyvec::ArrayVec<[i32; 4]> = tinyvec::array_vec!([i32; 4] => 7, 8, 9);
+    tiny.truncate(1);
+
+    let mut tiny_inline: TinyVec<[u8; 4]> = TinyVec::new();
+    tiny_inline.extend([1, 2, 3]);
+    tiny_inline.pop();
+    let tiny_heap: TinyVec<[u8; 2]> = (10..15).collect();
+
+    let mut small: SmallVec<i32, 4> = SmallVec::from([4, 5, 6, 7]);
+    small.truncate(3);
+
+    let mut text = ArrayString::<16>::from("hello world").unwrap();
+    text.truncate(5);
+
+    // Prevent optimization
+    std::hint::black_box(&numbers);
+    std::hint::black_box(&strings);
+    std::hint::black_box(&empty);
+    std::hint::black_box(&units);
+    std::hint::black_box(&tiny);
+    std::hint::black_box(&tiny_inline);
+    std::hint::black_box(&tiny_heap);
+    std::hint::black_box(&small);
+    std::hint::black_box(&text);
+
+    inspect_variables();
+
+    println!("numbers: {:?}", numbers);
+    println!("strings: {:?}", strings);
+    println!("empty: {:?}", empty);
+    println!("units: {:?}", units);
+    println!("tiny: {:?}", tiny);
+    println!("tiny_inline: {:?}", tiny_inline);
+    println!("tiny_heap: {:?}", tiny_heap);
+    println!("small: {:?}", small);
+    println!("text: {:?}", text);
+}
@@ -0,0 +1,28 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // Both halves of the 128-bit values are set, a reader of only the low
+    // 64 bits or one swapping them renders something else
+    let big: u128 = u128::from(u64::MAX) * 3 + 7;
+    let negative: i128 = -(1i128 << 100) - 5;
+    let minus_one: i128 = -1;
+    let wide_vec: Vec<u128> = vec![1, u128::MAX, big];
+    let maybe_negative: Option<i128> = Some(negative);
+    let small_vec: Vec<i32> = vec![-1, 255];
+
+    std::hint::black_box((&big, &negative, &minus_one));
+    std::hint::black_box((&wide_vec, &maybe_negative, &small_vec));
+
+    inspect_variables();
+
+    println!("big: {}", big);
+    println!("negative: {}", negative);
+    println!("minus_one: {}", minus_one);
+    println!("wide_vec: {:?}", wide_vec);
+    println!("maybe_negative: {:?}", maybe_negative);
+    println!("small_vec: {:?}", small_vec);
+}
@@ -0,0 +1,25 @@
+use glam::{I64Vec2, IVec3, UVec4};
+use nalgebra::{Matrix2, Vector3};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let ivec = IVec3::new(1, -2, 3);
+    let uvec = UVec4::new(10, 20, 255, 4_000_000_000);
+    let wide = I64Vec2::new(-5_000_000_000, 7);
+    let vector = Vector3::new(4i32, -5, 6);
+    let float_vector = Vector3::new(1.5f32, 0.0, -2.25);
+    let matrix = Matrix2::new(1.0f64, 2.0, 3.0, 4.0);
+
+    std::hint::black_box((&ivec, &uvec, &wide));
+    std::hint::black_box((&vector, &float_vector, &matrix));
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?}", ivec, uvec, wide);
+    println!("{:?} {:?} {:?}", vector, float_vector, matrix);
+}
@@ -0,0 +1,23 @@
+use smol_str::SmolStr;
+
+#[inline(never)]
+#[allow(clippy::ptr_arg)]
+fn inspect_variables(corrupted: &String, as_str: &str, smol: &SmolStr) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((corrupted, as_str, smol));
+}
+
+fn main() {
+    // Overwrite a byte with one that can't appear in UTF-8, the way a
+    // stray write or an interrupted mutation would
+    let mut corrupted = String::from("valid text");
+    unsafe {
+        corrupted.as_bytes_mut()[2] = 0xFF;
+    }
+    let as_str: &str = corrupted.as_str();
+    let smol = SmolStr::new(as_str);
+
+    inspect_variables(&corrupted, as_str, &smol);
+
+    println!("corrupted: {:?}", corrupted.as_bytes());
+}
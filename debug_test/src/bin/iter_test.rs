@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    // Each iterator has been advanced part of the way
+    let data = [1, 2, 3, 4, 5];
+    let mut slice_iter = data.iter();
+    slice_iter.next();
+    slice_iter.next();
+
+    let mut other = [7, 8];
+    let mut slice_iter_mut = other.iter_mut();
+    slice_iter_mut.next();
+
+    let mut into_iter = vec![10, 20, 30, 40].into_iter();
+    into_iter.next();
+
+    let map: HashMap<u32, u32> = [(1, 10), (2, 20), (3, 30)].into_iter().collect();
+    let mut map_iter = map.iter();
+    map_iter.next();
+
+    inspect_variables();
+
+    println!("{:?}", slice_iter.next());
+    println!("{:?}", slice_iter_mut.next());
+    println!("{:?}", into_iter.next());
+    println!("{:?}", map_iter.next());
+}
@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let maps: Vec<HashMap<String, i32>> = vec![
+        HashMap::from([(String::from("one"), 1)]),
+        HashMap::from([(String::from("two"), 2), (String::from("three"), 3)]),
+    ];
+
+    inspect_variables();
+
+    println!("maps: {:?}", maps);
+}
@@ -0,0 +1,22 @@
+// Stand-ins for SmolStr and CompactString of other sizes, as a different
+// crate version might have them
+
+#[allow(dead_code)]
+struct FakeSmolStr([u8; 16]);
+
+#[allow(dead_code)]
+struct FakeCompactString([u8; 32]);
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let fake_smol = FakeSmolStr([3; 16]);
+    let fake_compact = FakeCompactString([0xff; 32]);
+
+    inspect_variables();
+
+    std::hint::black_box((&fake_smol, &fake_compact));
+}
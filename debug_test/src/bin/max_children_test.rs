@@ -0,0 +1,16 @@
+use std::collections::BTreeMap;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let numbers: Vec<u32> = (0..20).collect();
+    let map: BTreeMap<u32, u32> = (0..20).map(|i| (i, i * i)).collect();
+
+    inspect_variables();
+
+    println!("{:?}", numbers);
+    println!("{:?}", map);
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+#[inline(never)]
+fn inspect_variables(nested: &[Vec<Vec<u32>>]) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box(nested);
+}
+
+fn main() {
+    // Two levels of collections below the outer one, each previewed
+    // within what is left of the outer preview budget
+    let nested: Vec<Vec<Vec<u32>>> = vec![vec![vec![1, 2, 3]], vec![vec![4], vec![5, 6]]];
+
+    let rows: Vec<Vec<i32>> = vec![vec![1, 2, 3], vec![], vec![-4, 5]];
+    let wide: Vec<Vec<u32>> = (0..4)
+        .map(|row| (0..20).map(|i| row * 100 + i).collect())
+        .collect();
+
+    // A single entry keeps the preview independent of the hash order
+    let mut by_key: HashMap<&str, Vec<i32>> = HashMap::new();
+    by_key.insert("evens", vec![2, 4, 6]);
+
+    std::hint::black_box(&rows);
+    std::hint::black_box(&wide);
+    std::hint::black_box(&by_key);
+
+    inspect_variables(&nested);
+
+    println!("nested: {:?}", nested);
+    println!("rows: {:?}", rows);
+    println!("wide: {:?}", wide);
+    println!("by_key: {:?}", by_key);
+}
@@ -0,0 +1,33 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+#[inline(never)]
+fn inspect_variables(
+    link_local: &SocketAddrV6,
+    no_scope: &SocketAddrV6,
+    mapped: &Ipv6Addr,
+    v4: &SocketAddrV4,
+) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((link_local, no_scope, mapped, v4));
+}
+
+fn main() {
+    // Link-local address with a scope id: [fe80::1%3]:443
+    let link_local = SocketAddrV6::new(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 443, 0, 3);
+    // Scope id 0 is not shown, flowinfo never is
+    let no_scope = SocketAddrV6::new(
+        Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0x8a2e, 0x370, 0x7334),
+        8080,
+        7,
+        0,
+    );
+    let mapped = Ipv4Addr::new(192, 0, 2, 1).to_ipv6_mapped();
+    let v4 = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 80);
+
+    inspect_variables(&link_local, &no_scope, &mapped, &v4);
+
+    println!("link_local: {}", link_local);
+    println!("no_scope: {}", no_scope);
+    println!("mapped: {}", mapped);
+    println!("v4: {}", v4);
+}
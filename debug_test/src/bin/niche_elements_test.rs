@@ -0,0 +1,25 @@
+use smallvec::SmallVec;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut inline: SmallVec<Option<Box<u64>>, 2> = SmallVec::new();
+    inline.push(Some(Box::new(1)));
+    inline.push(None);
+
+    let mut spilled: SmallVec<Option<Box<u64>>, 2> = SmallVec::new();
+    spilled.push(None);
+    spilled.push(Some(Box::new(2)));
+    spilled.push(Some(Box::new(3)));
+
+    let boxes: Vec<Option<Box<u64>>> = vec![Some(Box::new(4)), None, Some(Box::new(5))];
+
+    inspect_variables();
+
+    println!("inline: {:?}", inline);
+    println!("spilled: {:?}", spilled);
+    println!("boxes: {:?}", boxes);
+}
@@ -0,0 +1,63 @@
+use std::ptr::NonNull;
+
+// Option-likes without a tag: None is the null pointer
+#[derive(Debug)]
+enum MyOpt<T> {
+    None,
+    Some(NonNull<T>),
+}
+
+#[derive(Debug)]
+enum Slot {
+    Empty,
+    Full(Box<u32>),
+}
+
+// The dataless variants live in the bool's unused values 2 and 3
+#[derive(Debug)]
+enum Flag {
+    Set(bool),
+    Unset,
+    Unknown,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let mut target = 11u32;
+    let some_ptr: MyOpt<u32> = MyOpt::Some(NonNull::from(&mut target));
+    let none_ptr: MyOpt<u32> = MyOpt::None;
+    let full = Slot::Full(Box::new(7));
+    let empty = Slot::Empty;
+    let set = Flag::Set(false);
+    let unset = Flag::Unset;
+    let unknown = Flag::Unknown;
+
+    // Prevent optimization
+    std::hint::black_box(&some_ptr);
+    std::hint::black_box(&none_ptr);
+    std::hint::black_box(&full);
+    std::hint::black_box(&empty);
+    std::hint::black_box(&set);
+    std::hint::black_box(&unset);
+    std::hint::black_box(&unknown);
+
+    inspect_variables();
+
+    assert_eq!(
+        std::mem::size_of::<MyOpt<u32>>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(std::mem::size_of::<Flag>(), 1);
+    println!("some_ptr: {:?}", some_ptr);
+    println!("none_ptr: {:?}", none_ptr);
+    println!("full: {:?}", full);
+    println!("empty: {:?}", empty);
+    println!("set: {:?}", set);
+    println!("unset: {:?}", unset);
+    println!("unknown: {:?}", unknown);
+}
@@ -0,0 +1,54 @@
+// A no_std crate using only core, alloc and hashbrown; std is linked for
+// the runtime that calls main and for println, nothing else
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::num::NonZeroU32;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    core::hint::black_box(());
+}
+
+fn main() {
+    let numbers: Vec<u32> = vec![1, 2, 3];
+    let name = String::from("core");
+    let some: Option<u32> = Some(7);
+    let none: Option<NonZeroU32> = None;
+    let shared = Rc::new(RefCell::new(vec![4i32, 5]));
+    let mut sorted = BTreeMap::new();
+    sorted.insert(1u8, "one");
+    let mut hashed = hashbrown::HashMap::new();
+    hashed.insert(2u8, 20u16);
+
+    // Prevent optimization
+    core::hint::black_box(&numbers);
+    core::hint::black_box(&name);
+    core::hint::black_box(&some);
+    core::hint::black_box(&none);
+    core::hint::black_box(&shared);
+    core::hint::black_box(&sorted);
+    core::hint::black_box(&hashed);
+
+    inspect_variables();
+
+    std::println!(
+        "{:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+        numbers,
+        name,
+        some,
+        none,
+        shared,
+        sorted,
+        hashed
+    );
+}
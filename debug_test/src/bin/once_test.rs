@@ -0,0 +1,29 @@
+use once_cell::sync::OnceCell as SyncOnceCell;
+use once_cell::unsync::Lazy;
+use std::cell::{LazyCell, OnceCell};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let set: OnceCell<String> = OnceCell::new();
+    set.set(String::from("ready")).unwrap();
+    let unset: OnceCell<String> = OnceCell::new();
+    let forced = LazyCell::new(|| vec![1, 2, 3]);
+    LazyCell::force(&forced);
+    let lazy = LazyCell::new(|| 42);
+    let crate_cell = SyncOnceCell::new();
+    crate_cell.set(7u32).unwrap();
+    let crate_lazy = Lazy::new(|| String::from("later"));
+
+    inspect_variables();
+
+    println!("set: {:?}", set);
+    println!("unset: {:?}", unset);
+    println!("forced: {:?}", *forced);
+    println!("lazy: {:?}", *lazy);
+    println!("crate_cell: {:?}", crate_cell);
+    println!("crate_lazy: {:?}", *crate_lazy);
+}
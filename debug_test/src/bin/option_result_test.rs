@@ -0,0 +1,42 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // The Ok(None) and Err cases share the niche of String's capacity with
+    // the tags of both enums, there is no separate tag byte for either
+    let ok_some: Result<Option<i32>, String> = Ok(Some(5));
+    let ok_none: Result<Option<i32>, String> = Ok(None);
+    let err: Result<Option<i32>, String> = Err(String::from("x"));
+
+    let some_ok: Option<Result<i32, String>> = Some(Ok(5));
+    let some_err: Option<Result<i32, String>> = Some(Err(String::from("x")));
+    let none: Option<Result<i32, String>> = None;
+
+    // Both niches in the bool: 2 for Ok(None), 3 for Err
+    let flags: Vec<Result<Option<bool>, ()>> = vec![Ok(Some(true)), Ok(None), Err(())];
+    let chars: Vec<Option<Result<char, ()>>> = vec![Some(Ok('a')), Some(Err(())), None];
+
+    // Prevent optimization
+    std::hint::black_box(&ok_some);
+    std::hint::black_box(&ok_none);
+    std::hint::black_box(&err);
+    std::hint::black_box(&some_ok);
+    std::hint::black_box(&some_err);
+    std::hint::black_box(&none);
+    std::hint::black_box(&flags);
+    std::hint::black_box(&chars);
+
+    inspect_variables();
+
+    println!("ok_some: {:?}", ok_some);
+    println!("ok_none: {:?}", ok_none);
+    println!("err: {:?}", err);
+    println!("some_ok: {:?}", some_ok);
+    println!("some_err: {:?}", some_err);
+    println!("none: {:?}", none);
+    println!("flags: {:?}", flags);
+    println!("chars: {:?}", chars);
+}
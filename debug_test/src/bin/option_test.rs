@@ -1,3 +1,11 @@
+use std::num::NonZeroU32;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
 fn main() {
     let some_f32: Option<f32> = Some(1.3);
     let none_f32: Option<f32> = None;
@@ -6,6 +14,34 @@ fn main() {
     let some_string: Option<String> = Some(String::from("hello"));
     let none_string: Option<String> = None;
 
+    // Zero niche: no tag, the zero bit pattern is None
+    let some_nonzero: Option<NonZeroU32> = Some(NonZeroU32::new(7).unwrap());
+    let none_nonzero: Option<NonZeroU32> = None;
+
+    // Pointer niche: null is None
+    let referenced = 5i32;
+    let some_ref: Option<&i32> = Some(&referenced);
+    let none_ref: Option<&i32> = None;
+    let mut counter = 9i32;
+    let some_mut: Option<&mut i32> = Some(&mut counter);
+    let none_mut: Option<&mut i32> = None;
+
+    // Fat pointer niche: a null data pointer is None
+    let bytes = [1u8, 2, 3];
+    let some_bytes: Option<&[u8]> = Some(&bytes);
+    let none_bytes: Option<&[u8]> = None;
+
+    // Scalar niches: bool keeps None in 2, char in 0x110000
+    let some_true: Option<bool> = Some(true);
+    let some_false: Option<bool> = Some(false);
+    let none_bool: Option<bool> = None;
+    let some_char: Option<char> = Some('ß');
+    let none_char: Option<char> = None;
+
+    // Collections of niche-packed and tagged Options
+    let nonzero_vec: Vec<Option<NonZeroU32>> = vec![NonZeroU32::new(1), None, NonZeroU32::new(7)];
+    let tagged_vec: Vec<Option<u8>> = vec![None, Some(0), Some(255)];
+
     // Prevent optimization
     std::hint::black_box(&some_f32);
     std::hint::black_box(&none_f32);
@@ -13,6 +49,23 @@ fn main() {
     std::hint::black_box(&none_i32);
     std::hint::black_box(&some_string);
     std::hint::black_box(&none_string);
+    std::hint::black_box(&some_nonzero);
+    std::hint::black_box(&none_nonzero);
+    std::hint::black_box(&some_ref);
+    std::hint::black_box(&none_ref);
+    std::hint::black_box(&some_mut);
+    std::hint::black_box(&none_mut);
+    std::hint::black_box(&some_bytes);
+    std::hint::black_box(&none_bytes);
+    std::hint::black_box(&some_true);
+    std::hint::black_box(&some_false);
+    std::hint::black_box(&none_bool);
+    std::hint::black_box(&some_char);
+    std::hint::black_box(&none_char);
+    std::hint::black_box(&nonzero_vec);
+    std::hint::black_box(&tagged_vec);
+
+    inspect_variables();
 
     println!("some_f32: {:?}", some_f32);
     println!("none_f32: {:?}", none_f32);
@@ -20,4 +73,19 @@ fn main() {
     println!("none_i32: {:?}", none_i32);
     println!("some_string: {:?}", some_string);
     println!("none_string: {:?}", none_string);
+    println!("some_nonzero: {:?}", some_nonzero);
+    println!("none_nonzero: {:?}", none_nonzero);
+    println!("some_ref: {:?}", some_ref);
+    println!("none_ref: {:?}", none_ref);
+    println!("some_mut: {:?}", some_mut);
+    println!("none_mut: {:?}", none_mut);
+    println!("some_bytes: {:?}", some_bytes);
+    println!("none_bytes: {:?}", none_bytes);
+    println!("some_true: {:?}", some_true);
+    println!("some_false: {:?}", some_false);
+    println!("none_bool: {:?}", none_bool);
+    println!("some_char: {:?}", some_char);
+    println!("none_char: {:?}", none_char);
+    println!("nonzero_vec: {:?}", nonzero_vec);
+    println!("tagged_vec: {:?}", tagged_vec);
 }
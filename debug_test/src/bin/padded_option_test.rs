@@ -0,0 +1,53 @@
+/// No niche in the payload, the tag of an Option<[u8; 33]> needs its own byte
+#[derive(Debug)]
+struct Packet {
+    header: u16,
+    payload: Option<[u8; 33]>,
+    crc: u32,
+}
+
+/// Padding after `flag` and `bytes`, aligned to 8 by `value`
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Big {
+    flag: bool,
+    bytes: [u8; 33],
+    value: u64,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut array = [0u8; 33];
+    for (i, byte) in array.iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+
+    let bytes: Option<[u8; 33]> = Some(array);
+    let no_bytes: Option<[u8; 33]> = None;
+    let packet = Packet {
+        header: 1,
+        payload: Some(array),
+        crc: 0xDEADBEEF,
+    };
+    let empty = Packet {
+        header: 2,
+        payload: None,
+        crc: 0,
+    };
+    let big = Some(Big {
+        flag: true,
+        bytes: array,
+        value: 42,
+    });
+    let no_big: Option<Big> = None;
+
+    inspect_variables();
+
+    println!("{:?} {:?}", bytes, no_bytes);
+    println!("{:?} {:?}", packet, empty);
+    println!("{:?} {:?}", big, no_big);
+}
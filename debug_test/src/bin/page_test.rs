@@ -0,0 +1,17 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let huge: Vec<u64> = (0..50_000).collect();
+    let uneven: Vec<u32> = (0..2_500).collect();
+    let huge_slice: &[u64] = &huge[10_000..];
+
+    std::hint::black_box((&huge, &uneven, &huge_slice));
+
+    inspect_variables();
+
+    println!("{} {} {}", huge.len(), uneven.len(), huge_slice.len());
+}
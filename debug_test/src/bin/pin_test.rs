@@ -0,0 +1,40 @@
+use std::pin::Pin;
+
+#[derive(Debug)]
+struct Counter {
+    polls: u32,
+    label: String,
+}
+
+impl Counter {
+    #[inline(never)]
+    fn inspect_pinned(self: Pin<&mut Self>) {
+        // Breakpoint target, `self` is the pinned counter
+        std::hint::black_box(&self);
+    }
+
+    fn poll_twice(mut self: Pin<&mut Self>) {
+        self.polls += 2;
+        self.as_mut().inspect_pinned();
+    }
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut counter = Counter {
+        polls: 0,
+        label: String::from("ticks"),
+    };
+    Pin::new(&mut counter).poll_twice();
+
+    let boxed: Pin<Box<Vec<i32>>> = Box::pin(vec![1, 2, 3]);
+
+    inspect_variables();
+
+    println!("counter: {:?}", counter);
+    println!("boxed: {:?}", boxed);
+}
@@ -0,0 +1,36 @@
+use std::sync::{Mutex, RwLock};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let healthy = Mutex::new(5);
+    let poisoned = Mutex::new(vec![1, 2]);
+    let poisoned_rwlock = RwLock::new(String::from("stale"));
+
+    // Keep the expected panics out of the test output
+    std::panic::set_hook(Box::new(|_| {}));
+    std::thread::scope(|scope| {
+        let _ = scope
+            .spawn(|| {
+                let _guard = poisoned.lock().unwrap();
+                panic!("poison the mutex");
+            })
+            .join();
+        let _ = scope
+            .spawn(|| {
+                let _guard = poisoned_rwlock.write().unwrap();
+                panic!("poison the rwlock");
+            })
+            .join();
+    });
+    let _ = std::panic::take_hook();
+
+    inspect_variables();
+
+    println!("healthy: {:?}", healthy);
+    println!("poisoned: {}", poisoned.is_poisoned());
+    println!("poisoned_rwlock: {}", poisoned_rwlock.is_poisoned());
+}
@@ -0,0 +1,50 @@
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let range: Range<i32> = 1..5;
+    let range_inclusive: RangeInclusive<i32> = 1..=5;
+    let range_from: RangeFrom<i32> = 3..;
+    let range_to: RangeTo<i32> = ..7;
+    let range_to_inclusive: RangeToInclusive<i32> = ..=7;
+    let range_full: RangeFull = ..;
+
+    // Iterated to the end, which sets the `exhausted` flag
+    let mut exhausted: RangeInclusive<i32> = 1..=1;
+    exhausted.next();
+
+    let included: Bound<i32> = Bound::Included(10);
+    let excluded: Bound<i32> = Bound::Excluded(20);
+    let unbounded: Bound<i32> = Bound::Unbounded;
+
+    std::hint::black_box((
+        &range,
+        &range_inclusive,
+        &range_from,
+        &range_to,
+        &range_to_inclusive,
+        &range_full,
+        &exhausted,
+        &included,
+        &excluded,
+        &unbounded,
+    ));
+
+    inspect_variables();
+
+    println!("range: {:?}", range);
+    println!("range_inclusive: {:?}", range_inclusive);
+    println!("range_from: {:?}", range_from);
+    println!("range_to: {:?}", range_to);
+    println!("range_to_inclusive: {:?}", range_to_inclusive);
+    println!("range_full: {:?}", range_full);
+    println!("exhausted: {:?}", exhausted);
+    println!("included: {:?}", included);
+    println!("excluded: {:?}", excluded);
+    println!("unbounded: {:?}", unbounded);
+}
@@ -0,0 +1,34 @@
+use num_rational::Ratio;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let three_quarters: Ratio<i32> = Ratio::new(6, 8);
+    let negative: Ratio<i64> = Ratio::new(3, -4);
+    // Stored as is, with the sign on the denominator
+    let raw_negative: Ratio<i32> = Ratio::new_raw(3, -4);
+    let whole: Ratio<i32> = Ratio::from_integer(5);
+    let bytes: Ratio<u8> = Ratio::new(200, 3);
+    let wide: Ratio<i128> = Ratio::new(-(1i128 << 100), 3);
+
+    // Prevent optimization
+    std::hint::black_box(&three_quarters);
+    std::hint::black_box(&negative);
+    std::hint::black_box(&raw_negative);
+    std::hint::black_box(&whole);
+    std::hint::black_box(&bytes);
+    std::hint::black_box(&wide);
+
+    inspect_variables();
+
+    println!("three_quarters: {}", three_quarters);
+    println!("negative: {}", negative);
+    println!("raw_negative: {:?}", raw_negative);
+    println!("whole: {}", whole);
+    println!("bytes: {}", bytes);
+    println!("wide: {}", wide);
+}
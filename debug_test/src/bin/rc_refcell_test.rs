@@ -0,0 +1,25 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let shared: Rc<RefCell<Vec<i32>>> = Rc::new(RefCell::new(vec![1, 2, 3]));
+    let other = Rc::clone(&shared);
+    let borrowed = RefCell::new(5);
+    let mutably_borrowed = RefCell::new(6);
+
+    {
+        let _reader = borrowed.borrow();
+        let _writer = mutably_borrowed.borrow_mut();
+        inspect_variables();
+    }
+
+    other.borrow_mut().push(4);
+    println!("shared: {:?}", shared);
+    println!("borrowed: {:?}", borrowed);
+    println!("mutably_borrowed: {:?}", mutably_borrowed);
+}
@@ -0,0 +1,28 @@
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct Node {
+    id: u32,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let node = Rc::new(Node { id: 1 });
+    let other = Rc::new(Node { id: 2 });
+    // Three clones of one node around a node of its own
+    let nodes: Vec<Rc<Node>> = vec![node.clone(), node.clone(), other, node.clone()];
+
+    // Prevent optimization
+    std::hint::black_box(&node);
+    std::hint::black_box(&nodes);
+
+    inspect_variables();
+
+    println!("node: {:?}", node);
+    println!("nodes: {:?}", nodes);
+}
@@ -0,0 +1,28 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // The elements sit after the counts in the same allocation
+    let arc_slice: Arc<[u64]> = Arc::from(vec![10, 20, 30]);
+    let arc_clone = Arc::clone(&arc_slice);
+    let rc_slice: Rc<[i16]> = Rc::from(vec![-1, 2]);
+    let empty: Arc<[u64]> = Arc::from(Vec::new());
+
+    std::hint::black_box((&arc_slice, &arc_clone, &rc_slice, &empty));
+
+    inspect_variables();
+
+    println!(
+        "arc_slice: {:?} ({} refs)",
+        arc_slice,
+        Arc::strong_count(&arc_clone)
+    );
+    println!("rc_slice: {:?}", rc_slice);
+    println!("empty: {:?}", empty);
+}
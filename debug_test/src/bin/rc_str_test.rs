@@ -0,0 +1,21 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[inline(never)]
+fn inspect_variables(arc_str: &Arc<str>, rc_str: &Rc<str>, empty: &Arc<str>) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((arc_str, rc_str, empty));
+}
+
+fn main() {
+    let arc_str: Arc<str> = Arc::from("shared");
+    let rc_str: Rc<str> = Rc::from("counted wörds");
+    let rc_clone = Rc::clone(&rc_str);
+    let empty: Arc<str> = Arc::from("");
+
+    inspect_variables(&arc_str, &rc_str, &empty);
+
+    println!("arc_str: {}", arc_str);
+    println!("rc_str: {} ({} refs)", rc_str, Rc::strong_count(&rc_clone));
+    println!("empty: {:?}", empty);
+}
@@ -0,0 +1,20 @@
+use smallvec::SmallVec;
+
+#[inline(never)]
+#[allow(clippy::ptr_arg)] // references to the collections themselves on purpose
+fn inspect_variables(numbers: &Vec<i32>, text: &String, small: &mut SmallVec<u32, 4>) {
+    // Dummy function to set breakpoint on, its arguments are references
+    std::hint::black_box((numbers, text, small));
+}
+
+fn main() {
+    let numbers = vec![1, 2, 3];
+    let text = String::from("borrowed");
+    let mut small: SmallVec<u32, 4> = SmallVec::from([7, 8]);
+
+    inspect_variables(&numbers, &text, &mut small);
+
+    println!("numbers: {:?}", numbers);
+    println!("text: {}", text);
+    println!("small: {:?}", small);
+}
@@ -0,0 +1,44 @@
+// A C int tag followed by a union of the variants' fields, instead of the
+// layout rustc picks for its own enums
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+enum Shape {
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+    Empty,
+}
+
+// Explicit discriminants, one of them negative; with fields they need the
+// tag type spelled out next to the C layout
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C, i32)]
+enum Code {
+    Negative(i32) = -1,
+    Zero = 0,
+    Large(u64) = 7,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let circle = Shape::Circle(1.5);
+    let rect = Shape::Rect { w: 2.0, h: 3.5 };
+    let empty = Shape::Empty;
+    let negative = Code::Negative(5);
+    let zero = Code::Zero;
+    let large = Code::Large(9);
+
+    std::hint::black_box((&circle, &rect, &empty));
+    std::hint::black_box((&negative, &zero, &large));
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?}", circle, rect, empty);
+    println!("{:?} {:?} {:?}", negative, zero, large);
+}
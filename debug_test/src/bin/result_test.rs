@@ -0,0 +1,14 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let results: Vec<Result<i32, String>> = vec![Ok(1), Err(String::from("bad input")), Ok(3)];
+    let nested: Vec<Result<Option<u8>, ()>> = vec![Ok(Some(7)), Ok(None), Err(())];
+
+    inspect_variables();
+
+    println!("results: {:?}", results);
+    println!("nested: {:?}", nested);
+}
@@ -0,0 +1,54 @@
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug)]
+struct Named {
+    name: String,
+    id: u32,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let boxed: Box<[u64]> = vec![4, 5, 6].into_boxed_slice();
+    let empty_boxed: Box<[u64]> = Vec::new().into_boxed_slice();
+    let borrowed: &[u64] = &boxed[1..];
+    let boxed_u32: Box<[u32]> = Box::new([10, 20, 30, 40]);
+    let empty_u32: Box<[u32]> = Box::new([]);
+    let point_array = [Point { x: 1, y: 2 }, Point { x: -3, y: 4 }];
+    let points: &[Point] = &point_array;
+    let named_vec = vec![
+        Named {
+            name: String::from("alpha"),
+            id: 7,
+        },
+        Named {
+            name: String::from("beta"),
+            id: 8,
+        },
+    ];
+    let named: &[Named] = &named_vec;
+    let grid_rows = [[1i32, 2], [3, 4], [5, 6]];
+    let grid: &[[i32; 2]] = &grid_rows;
+    let array_ref: &[i32; 12] = &[0; 12];
+    let owned = vec![1i32, 2, 3, 4, 5];
+    let middle: &[i32] = &owned[1..4];
+
+    inspect_variables();
+
+    println!("boxed: {:?}", boxed);
+    println!("empty_boxed: {:?}", empty_boxed);
+    println!("borrowed: {:?}", borrowed);
+    println!("boxed_u32: {:?} empty_u32: {:?}", boxed_u32, empty_u32);
+    println!("points: {:?}", points);
+    println!("named: {:?}", named);
+    println!("grid: {:?}", grid);
+    println!("array_ref: {:?}", array_ref);
+    println!("owned: {:?} middle: {:?}", owned, middle);
+}
@@ -0,0 +1,42 @@
+use smallvec::SmallVec;
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let mut inline_strings: SmallVec<String, 2> = SmallVec::new();
+    inline_strings.push(String::from("alpha"));
+    inline_strings.push(String::from("beta"));
+
+    let mut spilled_strings: SmallVec<String, 2> = SmallVec::new();
+    for word in ["one", "two", "three"] {
+        spilled_strings.push(String::from(word));
+    }
+
+    let inline_points: SmallVec<Point, 4> =
+        SmallVec::from([Point { x: 1, y: 2 }, Point { x: -3, y: 4 }]);
+    let spilled_points: SmallVec<Point, 1> =
+        SmallVec::from([Point { x: 5, y: 6 }, Point { x: 7, y: 8 }]);
+
+    // Prevent optimization
+    std::hint::black_box(&inline_strings);
+    std::hint::black_box(&spilled_strings);
+    std::hint::black_box(&inline_points);
+    std::hint::black_box(&spilled_points);
+
+    inspect_variables();
+
+    println!("inline_strings: {:?}", inline_strings);
+    println!("spilled_strings: {:?}", spilled_strings);
+    println!("inline_points: {:?}", inline_points);
+    println!("spilled_points: {:?}", spilled_points);
+}
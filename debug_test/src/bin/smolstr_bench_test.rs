@@ -0,0 +1,19 @@
+use smol_str::SmolStr;
+
+#[inline(never)]
+fn inspect_variables(strings: &[SmolStr]) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box(strings);
+}
+
+fn main() {
+    // Thousands of short inline strings, to measure how much memory the
+    // formatter reads per element
+    let strings: Vec<SmolStr> = (0..5000)
+        .map(|i| SmolStr::new(format!("item-{}", i)))
+        .collect();
+
+    inspect_variables(&strings);
+
+    println!("strings: {}", strings.len());
+}
@@ -0,0 +1,54 @@
+use smol_str::SmolStr;
+
+// SmolStr keeps up to 23 bytes inline, longer strings go to the heap
+// unless they are &'static str from new_static. The values below sit on
+// each side of that boundary, with ASCII and with multibyte characters
+// ending at the boundary.
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let empty = SmolStr::new("");
+    let one = SmolStr::new("a");
+    let ascii_22 = SmolStr::new("abcdefghijklmnopqrstuv");
+    let ascii_23 = SmolStr::new("abcdefghijklmnopqrstuvw");
+    let ascii_24 = SmolStr::new("abcdefghijklmnopqrstuvwx");
+
+    // 2-byte é and 4-byte 😀 as the last character
+    let multi_22 = SmolStr::new("abcdefghijklmnopqrsté");
+    let multi_23 = SmolStr::new("abcdefghijklmnopqrstué");
+    let multi_24 = SmolStr::new("abcdefghijklmnopqrstuvé");
+    let emoji_23 = SmolStr::new("abcdefghijklmnopqrs😀");
+    let emoji_24 = SmolStr::new("abcdefghijklmnopqrst😀");
+    let only_multi = SmolStr::new("é");
+
+    let static_short = SmolStr::new_static("static");
+    let static_24 = SmolStr::new_static("abcdefghijklmnopqrstuvwx");
+    let static_multi = SmolStr::new_static("statisch ä");
+    let heap_long = SmolStr::new("x".repeat(100));
+
+    inspect_variables();
+
+    for s in [
+        &empty,
+        &one,
+        &ascii_22,
+        &ascii_23,
+        &ascii_24,
+        &multi_22,
+        &multi_23,
+        &multi_24,
+        &emoji_23,
+        &emoji_24,
+        &only_multi,
+        &static_short,
+        &static_24,
+        &static_multi,
+        &heap_long,
+    ] {
+        println!("{} ({} bytes)", s, s.len());
+    }
+}
@@ -0,0 +1,29 @@
+#[inline(never)]
+fn inspect_variables(full: &str, middle: &str, multibyte: &str, empty: &str) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((full, middle, multibyte, empty));
+}
+
+fn main() {
+    let text = String::from("hello wörld");
+
+    // Slices that don't start at the beginning of the string, so the data
+    // pointer is followed by more (non-nul) bytes
+    let full: &str = &text;
+    let middle: &str = &text[2..5];
+    let multibyte: &str = &text[6..10];
+    let empty: &str = &text[3..3];
+
+    // Owned the same way, the length is the metadata half of the Box
+    let boxed: Box<str> = Box::from("boxed wörds");
+    let empty_boxed: Box<str> = Box::from("");
+
+    inspect_variables(full, middle, multibyte, empty);
+    std::hint::black_box((&boxed, &empty_boxed));
+
+    println!("full: {:?}", full);
+    println!("middle: {:?}", middle);
+    println!("multibyte: {:?}", multibyte);
+    println!("empty: {:?}", empty);
+    println!("boxed: {:?} empty_boxed: {:?}", boxed, empty_boxed);
+}
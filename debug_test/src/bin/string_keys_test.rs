@@ -0,0 +1,34 @@
+use compact_str::CompactString;
+use smol_str::SmolStr;
+use std::collections::HashMap;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let mut smol_keys: HashMap<SmolStr, i32> = HashMap::new();
+    smol_keys.insert(SmolStr::new("let"), 1);
+    smol_keys.insert(SmolStr::new_static("fn"), 2);
+    smol_keys.insert(
+        SmolStr::new("an identifier longer than the inline buffer"),
+        3,
+    );
+
+    let mut single_smol: HashMap<SmolStr, i32> = HashMap::new();
+    single_smol.insert(SmolStr::new("x"), 10);
+
+    let mut compact_keys: HashMap<CompactString, i32> = HashMap::new();
+    compact_keys.insert(CompactString::new("match"), 4);
+    compact_keys.insert(
+        CompactString::new("a compact string that lives on the heap"),
+        5,
+    );
+
+    inspect_variables();
+
+    println!("smol_keys: {:?}", smol_keys);
+    println!("single_smol: {:?}", single_smol);
+    println!("compact_keys: {:?}", compact_keys);
+}
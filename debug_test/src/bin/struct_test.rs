@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+#[derive(Debug)]
+struct Config {
+    name: &'static str,
+    retries: u32,
+    verbose: bool,
+}
+
+#[derive(Debug)]
+struct Meters(f64);
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug)]
+struct Line {
+    from: Point,
+    to: Point,
+}
+
+#[derive(Debug)]
+struct Typed<T> {
+    id: u64,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Debug)]
+struct Handle<T>(u32, PhantomData<fn() -> T>);
+
+#[derive(Debug)]
+struct Wide(u64, u64, u64, u64, u64, u64, u64, u64, u64, u64);
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let config = Config {
+        name: "dev",
+        retries: 3,
+        verbose: true,
+    };
+    let meters = Meters(2.5);
+    let line = Line {
+        from: Point { x: 0, y: 0 },
+        to: Point { x: 3, y: 4 },
+    };
+    let big = 1_000_000_000_000_000_000;
+    let wide = Wide(big, big, big, big, big, big, big, big, big, big);
+    let typed: Typed<String> = Typed {
+        id: 5,
+        _marker: PhantomData,
+    };
+    let handle: Handle<Point> = Handle(7, PhantomData);
+
+    inspect_variables();
+
+    println!("config: {:?}", config);
+    println!("meters: {:?}", meters);
+    println!("line: {:?}", line);
+    println!("wide: {:?}", wide);
+    println!("typed: {:?}", typed);
+    println!("handle: {:?}", handle);
+}
@@ -0,0 +1,27 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // Before the epoch tv_sec is negative and tv_nsec counts forward from it
+    let moon_landing: SystemTime = UNIX_EPOCH - Duration::from_secs(14_182_940);
+    let just_before: SystemTime = UNIX_EPOCH - Duration::new(1, 250_000_000);
+    let epoch: SystemTime = UNIX_EPOCH;
+    let leap_day: SystemTime = UNIX_EPOCH + Duration::from_secs(1_709_208_000);
+    let maybe_time: Option<SystemTime> = Some(moon_landing);
+
+    std::hint::black_box((&moon_landing, &just_before, &epoch, &leap_day));
+    std::hint::black_box(&maybe_time);
+
+    inspect_variables();
+
+    println!("moon_landing: {:?}", moon_landing);
+    println!("just_before: {:?}", just_before);
+    println!("epoch: {:?}", epoch);
+    println!("leap_day: {:?}", leap_day);
+    println!("maybe_time: {:?}", maybe_time);
+}
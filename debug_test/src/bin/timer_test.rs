@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug)]
+struct Timer {
+    start: Instant,
+    elapsed: Duration,
+}
+
+#[derive(Debug)]
+struct Stopwatch {
+    started: Option<Instant>,
+    laps: Vec<Duration>,
+    created: SystemTime,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    let start = Instant::now();
+    let timer = Timer {
+        start,
+        elapsed: Duration::from_millis(1500),
+    };
+
+    // Values computed from others rather than constructed directly
+    let deadline = start + Duration::from_secs(90);
+    let remaining = deadline.duration_since(start);
+    let doubled = timer.elapsed * 2;
+
+    let stopwatch = Stopwatch {
+        started: Some(start),
+        laps: vec![Duration::from_millis(250), Duration::from_secs(61)],
+        created: SystemTime::UNIX_EPOCH + Duration::from_secs(86_400),
+    };
+
+    // Prevent optimization
+    std::hint::black_box(&timer);
+    std::hint::black_box(&deadline);
+    std::hint::black_box(&remaining);
+    std::hint::black_box(&doubled);
+    std::hint::black_box(&stopwatch);
+
+    inspect_variables();
+
+    println!("timer: {:?}", timer);
+    println!("deadline: {:?}", deadline);
+    println!("remaining: {:?}", remaining);
+    println!("doubled: {:?}", doubled);
+    println!("stopwatch: {:?}", stopwatch);
+}
@@ -0,0 +1,31 @@
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+
+#[derive(Debug)]
+struct Token {
+    text: SmolStr,
+    spans: SmallVec<u32, 4>,
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let token = Token {
+        text: SmolStr::new("ident"),
+        spans: SmallVec::from([3, 8]),
+    };
+    let spilled = Token {
+        text: SmolStr::new("a token text longer than the inline limit"),
+        spans: SmallVec::from([1, 2, 3, 4, 5]),
+    };
+    let pair: (SmolStr, SmallVec<u32, 4>) = (SmolStr::new("pair"), SmallVec::from([9]));
+
+    inspect_variables();
+
+    println!("token: {:?}", token);
+    println!("spilled: {:?}", spilled);
+    println!("pair: {:?}", pair);
+}
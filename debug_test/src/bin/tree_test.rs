@@ -0,0 +1,43 @@
+#[derive(Debug)]
+enum Tree<T> {
+    Leaf(T),
+    Node(Box<Tree<T>>, Box<Tree<T>>),
+}
+
+impl<T> Tree<T> {
+    fn node(left: Tree<T>, right: Tree<T>) -> Self {
+        Tree::Node(Box::new(left), Box::new(right))
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(dead_code)]
+enum Level {
+    Info,
+    Warning,
+    Error(u32),
+}
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    let leaf = Tree::Leaf(1);
+    let tree = Tree::node(Tree::Leaf(1), Tree::node(Tree::Leaf(2), Tree::Leaf(3)));
+    let options: Tree<Option<u8>> = Tree::node(Tree::Leaf(Some(4)), Tree::Leaf(None));
+    let level = Level::Warning;
+
+    // Deeper than the formatter renders
+    let mut deep = Tree::Leaf(0);
+    for i in 1..20 {
+        deep = Tree::node(deep, Tree::Leaf(i));
+    }
+
+    inspect_variables();
+
+    println!("{:?} {:?} {:?} {:?}", leaf, tree, options, level);
+    println!("{:?}", deep);
+}
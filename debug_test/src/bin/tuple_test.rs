@@ -0,0 +1,23 @@
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on, the values live in main
+    std::hint::black_box(());
+}
+
+fn main() {
+    // An association list
+    let pairs: Vec<(String, i32)> = vec![(String::from("alpha"), 1), (String::from("beta"), -2)];
+    let single: (u8,) = (5,);
+    let nested: ((i32, i32), &str) = ((3, 4), "point");
+
+    // Prevent optimization
+    std::hint::black_box(&pairs);
+    std::hint::black_box(&single);
+    std::hint::black_box(&nested);
+
+    inspect_variables();
+
+    println!("pairs: {:?}", pairs);
+    println!("single: {:?}", single);
+    println!("nested: {:?}", nested);
+}
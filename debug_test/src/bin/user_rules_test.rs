@@ -0,0 +1,83 @@
+/// Rendered as its `inner` field through the rule in `test.rules`
+struct Wrapper {
+    inner: String,
+    _meta: u64,
+}
+
+/// Packed, so `value` and `label` are not at their natural offsets
+#[repr(packed)]
+struct PackedRecord {
+    _tag: u8,
+    _value: u32,
+    label: &'static str,
+}
+
+/// Over-aligned, so the struct carries trailing padding
+#[repr(align(32))]
+struct AlignedRecord {
+    _tag: u8,
+    label: String,
+}
+
+/// Summarized by its `label` through a rule, also as a Vec element
+struct Item {
+    label: String,
+    _weight: u32,
+}
+
+/// Single-variant enum wrapper, rendered as its String through a rule
+enum Handle {
+    V(String),
+}
+
+#[inline(never)]
+fn inspect_variables(
+    wrapper: &Wrapper,
+    packed: &PackedRecord,
+    aligned: &AlignedRecord,
+    items: &Vec<Item>,
+) {
+    // Dummy function to set breakpoint on
+    std::hint::black_box((wrapper, packed, aligned, items));
+}
+
+fn main() {
+    let wrapper = Wrapper {
+        inner: String::from("wrapped"),
+        _meta: 0xdead_beef,
+    };
+    let packed = PackedRecord {
+        _tag: 0xff,
+        _value: 0x1234_5678,
+        label: "packed",
+    };
+    let aligned = AlignedRecord {
+        _tag: 0xff,
+        label: String::from("aligned"),
+    };
+
+    let handle = Handle::V(String::from("handled"));
+
+    let items = vec![
+        Item {
+            label: String::from("first"),
+            _weight: 1,
+        },
+        Item {
+            label: String::from("second"),
+            _weight: 2,
+        },
+    ];
+
+    inspect_variables(&wrapper, &packed, &aligned, &items);
+
+    let label = packed.label;
+    println!("wrapper: {}", wrapper.inner);
+    println!("packed: {}", label);
+    println!("aligned: {}", aligned.label);
+    let Handle::V(name) = &handle;
+    println!("handle: {}", name);
+    for item in &items {
+        println!("item: {}", item.label);
+    }
+}
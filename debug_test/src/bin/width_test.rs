@@ -0,0 +1,26 @@
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+use std::rc::Rc;
+
+#[inline(never)]
+fn inspect_variables() {
+    // Dummy function to set breakpoint on
+}
+
+fn main() {
+    // Every usize sized field below is 4 bytes when built for a 32-bit
+    // target such as i686-unknown-linux-gnu
+    let inline = SmolStr::new("short");
+    let heap = SmolStr::new("a string too long to be stored inline");
+    let small_inline: SmallVec<u32, 2> = SmallVec::from([1, 2]);
+    let small_heap: SmallVec<u32, 2> = SmallVec::from([1, 2, 3, 4, 5]);
+    let numbers: Vec<u64> = vec![10, 20, 30];
+    let text = String::from("usize sized");
+    let shared = Rc::new(7u32);
+    let shared_clone = Rc::clone(&shared);
+
+    inspect_variables();
+
+    println!("{} {} {:?} {:?}", inline, heap, small_inline, small_heap);
+    println!("{:?} {} {}", numbers, text, shared_clone);
+}
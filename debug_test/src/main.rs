@@ -39,6 +39,9 @@ fn main() {
     let heap_long = SmolStr::new("this is a very long string that will be heap allocated");
     let heap_repeated = SmolStr::new(&"x".repeat(100));
 
+    // Heap variant shared through the Arc - strong count is 2
+    let heap_shared = heap_long.clone();
+
     // Additional test cases
     let inline_inline = SmolStr::new_inline("inline");
     let from_string = SmolStr::from(String::from("from string"));
@@ -49,6 +52,12 @@ fn main() {
     let empty_smallvec: SmallVec<u32, 4> = SmallVec::new();
     let single_smallvec: SmallVec<i32, 3> = SmallVec::from([42]);
 
+    // Edge inline capacities: N=0 has no inline slot, N=1 exactly one
+    let zero_cap_empty: SmallVec<u64, 0> = SmallVec::new();
+    let zero_cap_spilled: SmallVec<u64, 0> = SmallVec::from([7, 8, 9]);
+    let one_cap_inline: SmallVec<u8, 1> = SmallVec::from([1u8]);
+    let one_cap_spilled: SmallVec<u8, 1> = SmallVec::from([1u8, 2]);
+
     // Regular Vec for comparison
     let test_vec: Vec<u64> = vec![10, 20, 30];
 
@@ -89,12 +98,17 @@ fn main() {
     println!("static_str: {}", static_str);
     println!("heap_long: {}", heap_long);
     println!("heap_repeated: {}", heap_repeated);
+    println!("heap_shared: {}", heap_shared);
     println!("inline_inline: {}", inline_inline);
     println!("from_string: {}", from_string);
     println!("inline_smallvec: {:?}", inline_smallvec);
     println!("heap_smallvec: {:?}", heap_smallvec);
     println!("empty_smallvec: {:?}", empty_smallvec);
     println!("single_smallvec: {:?}", single_smallvec);
+    println!("zero_cap_empty: {:?}", zero_cap_empty);
+    println!("zero_cap_spilled: {:?}", zero_cap_spilled);
+    println!("one_cap_inline: {:?}", one_cap_inline);
+    println!("one_cap_spilled: {:?}", one_cap_spilled);
     println!("test_vec: {:?}", test_vec);
     println!("some_f32: {:?}", some_f32);
     println!("none_f32: {:?}", none_f32);
@@ -113,6 +127,7 @@ fn main() {
         static_str,
         heap_long,
         heap_repeated,
+        heap_shared,
         inline_inline,
         from_string,
     ];
@@ -122,6 +137,10 @@ fn main() {
         heap_smallvec.len(),
         empty_smallvec.len(),
         single_smallvec.len(),
+        zero_cap_empty.len(),
+        zero_cap_spilled.len(),
+        one_cap_inline.len(),
+        one_cap_spilled.len(),
         test_vec.len(),
     ];
 
@@ -4,8 +4,17 @@ LLDB debugging support for third-party Rust types
 
 from __future__ import annotations
 
+import decimal
+import errno
+import functools
+import json
+import math
+import os
 import re
+import struct
 import sys
+import time
+from collections import namedtuple
 from typing import TYPE_CHECKING
 
 import lldb
@@ -16,842 +25,8361 @@ if TYPE_CHECKING:
 
 PY3 = sys.version_info[0] == 3
 
+# Formatter version, printed by `rust-formatter-version`
+VERSION = "0.1.0"
 
-def SmolStrSummaryProvider(valobj: SBValue, _dict) -> str:
-    """
-    Summary provider for smol_str::SmolStr
-
-    SmolStr uses an internal Repr enum with three variants:
-    - Inline (discriminant 0-23): small strings stored inline, discriminant is the length
-    - Static (discriminant 0x18=24): reference to static string
-    - Heap (discriminant >= 0x19=25): Arc-allocated string on heap
+# LLDB does not let scripts register their own `settings` entries, so the
+# formatter keeps a small registry of its own. Values are changed with
+# `rust-formatter-set <name> <value>`, where the name may be given short
+# (`metadata`) or fully qualified (`target.rust-formatters.metadata`).
+SETTINGS_PREFIX = "target.rust-formatters."
 
-    Args:
-        valobj: The SmolStr value to format
-        _dict: LLDB internal bookkeeping parameter
 
-    Returns:
-        A string representation like "hello" with quotes
+class FormatterSetting:
     """
-    # Get the internal Repr enum
-    valobj = valobj.GetNonSyntheticValue()
-    repr_enum = valobj.GetChildAtIndex(0)
+    A single formatter setting with its default and current value.
 
-    # Get the $variants$ field (LLDB encoded enum)
-    variants = repr_enum.GetChildMemberWithName("$variants$")
-    if not variants.IsValid():
-        return '""'
+    The type of the default value decides how new values are parsed.
+    on_change, if given, is called with the debugger and the setting after
+    the value changed and may raise ValueError to reject the new value.
+    """
 
-    # Get discriminant from $variant$24 (which contains $discr$ field)
-    variant24 = variants.GetChildMemberWithName("$variant$24")
-    if not variant24.IsValid():
-        return '""'
+    def __init__(self, name: str, default, description: str, on_change=None):
+        self.name = name
+        self.default = default
+        self.value = default
+        self.description = description
+        self.on_change = on_change
 
-    discr_field = variant24.GetChildMemberWithName("$discr$")
-    if not discr_field.IsValid():
-        return '""'
+    def parse(self, text: str):
+        text = text.strip()
+        if isinstance(self.default, bool):
+            lowered = text.lower()
+            if lowered in ("1", "true", "on", "yes"):
+                return True
+            if lowered in ("0", "false", "off", "no"):
+                return False
+            raise ValueError("expected a boolean, got %r" % text)
+        if isinstance(self.default, int):
+            return int(text, 0)
+        if len(text) >= 2 and text[0] == text[-1] and text[0] in "'\"":
+            text = text[1:-1]
+        return text
 
-    discriminant = discr_field.GetValueAsUnsigned()
-
-    # Inline variant: discriminant is 0-23 (the length)
-    if discriminant <= 23:
-        length = discriminant
-        if length == 0:
-            return '""'
-
-        # Get the inline buffer from $variant$
-        variant0 = variants.GetChildMemberWithName("$variant$")
-        if not variant0.IsValid():
-            return '""'
-
-        value = variant0.GetChildMemberWithName("value")
-        if not value.IsValid():
-            return '""'
-
-        buf = value.GetChildMemberWithName("buf")
-        if not buf.IsValid():
-            return '""'
-
-        # Read bytes from the buffer
-        error = SBError()
-        process = buf.GetProcess()
-        data = process.ReadMemory(buf.GetLoadAddress(), length, error)
-        if error.Success():
-            if PY3:
-                try:
-                    data = data.decode("utf-8", "replace")
-                except Exception:
-                    return '""'
-            return '"%s"' % data
-        return '""'
 
-    # Static variant: discriminant is 0x18 (24)
-    elif discriminant == 24:
-        # Get &str from $variant$24.value.__0
-        value = variant24.GetChildMemberWithName("value")
-        if not value.IsValid():
-            return '""'
+SETTINGS = {}
 
-        str_ref = value.GetChildMemberWithName("__0")
-        if not str_ref.IsValid():
-            return '""'
 
-        data_ptr = str_ref.GetChildMemberWithName("data_ptr")
-        length_field = str_ref.GetChildMemberWithName("length")
+def define_setting(
+    name: str, default, description: str, on_change=None
+) -> FormatterSetting:
+    setting = FormatterSetting(name, default, description, on_change)
+    SETTINGS[name] = setting
+    return setting
 
-        if not data_ptr.IsValid() or not length_field.IsValid():
-            return '""'
 
-        length = length_field.GetValueAsUnsigned()
-        if length == 0:
-            return '""'
-
-        ptr = data_ptr.GetValueAsUnsigned()
-        error = SBError()
-        process = data_ptr.GetProcess()
-        data = process.ReadMemory(ptr, length, error)
-        if error.Success():
-            if PY3:
-                try:
-                    data = data.decode("utf-8", "replace")
-                except Exception:
-                    return '""'
-            return '"%s"' % data
-        return '""'
+def get_setting(name: str):
+    return SETTINGS[name].value
 
-    # Heap variant: discriminant >= 0x19 (25)
-    else:
-        # Get Arc<str> from $variant$25.value.__0.ptr.pointer
-        variant25 = variants.GetChildMemberWithName("$variant$25")
-        if not variant25.IsValid():
-            return '""'
 
-        value = variant25.GetChildMemberWithName("value")
-        if not value.IsValid():
-            return '""'
+def setting_name(name: str) -> str:
+    """Strips the optional `target.rust-formatters.` prefix from a setting name"""
+    if name.startswith(SETTINGS_PREFIX):
+        return name[len(SETTINGS_PREFIX) :]
+    return name
 
-        inner = value.GetChildMemberWithName("__0")
-        if not inner.IsValid():
-            return '""'
 
-        ptr_field = inner.GetChildMemberWithName("ptr")
-        if not ptr_field.IsValid():
-            return '""'
+define_setting(
+    "metadata",
+    False,
+    "Append representation details such as the string variant and "
+    "reference counts to summaries",
+)
 
-        pointer = ptr_field.GetChildMemberWithName("pointer")
-        if not pointer.IsValid():
-            return '""'
 
-        data_ptr = pointer.GetChildMemberWithName("data_ptr")
-        length_field = pointer.GetChildMemberWithName("length")
+define_setting(
+    "preview-budget",
+    80,
+    "Maximum number of characters used for element previews in collection "
+    "summaries",
+)
 
-        if not data_ptr.IsValid() or not length_field.IsValid():
-            return '""'
 
-        length = length_field.GetValueAsUnsigned()
-        if length == 0:
-            return '""'
-
-        ptr = data_ptr.GetValueAsUnsigned()
-
-        # Arc<str> pointer points to ArcInner which has:
-        # - strong: AtomicUsize (8 bytes)
-        # - weak: AtomicUsize (8 bytes)
-        # - data: [u8] (the actual string)
-        # So we need to skip 16 bytes to get to the string data
-        arc_header_size = 16
-        string_data_ptr = ptr + arc_header_size
-
-        error = SBError()
-        process = data_ptr.GetProcess()
-        data = process.ReadMemory(string_data_ptr, length, error)
-        if error.Success():
-            if PY3:
-                try:
-                    data = data.decode("utf-8", "replace")
-                except Exception:
-                    return '""'
-            return '"%s"' % data
-        return '""'
+define_setting(
+    "compact",
+    False,
+    "Render summaries as tight as possible for narrow panes: no spaces after "
+    "commas and colons and half the preview-budget",
+)
 
 
-class SmolStrSyntheticProvider:
-    """
-    Synthetic provider for smol_str::SmolStr
+define_setting(
+    "format-budget-ms",
+    1000,
+    "Time in milliseconds a collection summary may take before it stops "
+    "reading and shows what it has so far, 0 for no limit",
+)
 
-    Exposes internal structure as children:
-    - variant: "Inline", "Static", or "Heap"
-    - length: the string length
-    - content: the string content
-    - pointer: the pointer address (for Static and Heap variants)
-    """
 
-    def __init__(self, valobj: SBValue, _dict):
-        self.valobj = valobj
-        self.variant_name = ""
-        self.length = 0
-        self.content = ""
-        self.pointer = 0
-        self.content_address = 0  # Store address of string data
-        self.update()
+define_setting(
+    "none-word",
+    "None",
+    "Word shown for Option's None",
+)
 
-    def num_children(self):
-        # Always show: variant, length, content
-        # Show pointer for Static and Heap variants
-        if self.variant_name in ("Static", "Heap"):
-            return 4
-        return 3
 
-    def get_child_index(self, name: str):
-        if name == "variant":
-            return 0
-        elif name == "length":
-            return 1
-        elif name == "content":
-            return 2
-        elif name == "pointer":
-            return 3
-        return -1
+define_setting(
+    "some-word",
+    "Some",
+    "Word shown for Option's Some, rendered as `Some(value)`",
+)
 
-    def get_child_at_index(self, index: int):
-        if index < 0:
-            return None
 
-        try:
-            if index == 0:
-                # variant field - return as string summary
-                # Create a value that will display the variant name
-                return self.valobj.CreateValueFromExpression(
-                    "variant", '(const char*)"%s"' % self.variant_name
-                )
-            elif index == 1:
-                # length field - create as unsigned integer
-                return self.valobj.CreateValueFromExpression(
-                    "length", "(unsigned long long)%d" % self.length
-                )
-            elif index == 2:
-                # content field - create as char array pointing to actual data
-                if self.content_address != 0 and self.length > 0:
-                    # Create char[length] type
-                    char_type = self.valobj.GetTarget().GetBasicType(
-                        lldb.eBasicTypeChar
-                    )
-                    char_array_type = char_type.GetArrayType(self.length)
+for _style, _open, _close in (("sequence", "[", "]"), ("map", "{", "}")):
+    define_setting(
+        _style + "-open",
+        _open,
+        "Opening delimiter of %s previews in collection summaries" % _style,
+    )
+    define_setting(
+        _style + "-close",
+        _close,
+        "Closing delimiter of %s previews in collection summaries" % _style,
+    )
 
-                    # Create value from address
-                    return self.valobj.CreateValueFromAddress(
-                        "content", self.content_address, char_array_type
-                    )
-                else:
-                    # Empty string
-                    return self.valobj.CreateValueFromExpression(
-                        "content", '(const char*)""'
-                    )
-            elif index == 3 and self.variant_name in ("Static", "Heap"):
-                # pointer field - create as hex pointer
-                return self.valobj.CreateValueFromExpression(
-                    "pointer", "(void*)0x%x" % self.pointer
-                )
-        except Exception:
-            return None
 
-        return None
+define_setting(
+    "bools-as-bits",
+    False,
+    "Show collections of bool as a string of bits like 10110 in summaries",
+)
 
-    def update(self):
-        self.variant_name = ""
-        self.length = 0
-        self.content = ""
-        self.pointer = 0
-        self.content_address = 0
 
-        try:
-            valobj = self.valobj.GetNonSyntheticValue()
-            repr_enum = valobj.GetChildAtIndex(0)
+define_setting(
+    "key-summaries",
+    True,
+    "Label map entries with their key's summary; when off, or the key has "
+    "no summary, a compact form like `(1, 2)` or `Key { .. }` is used",
+)
 
-            # Get the $variants$ field (LLDB encoded enum)
-            variants = repr_enum.GetChildMemberWithName("$variants$")
-            if not variants.IsValid():
-                return
 
-            # Get discriminant from $variant$24 (which contains $discr$ field)
-            variant24 = variants.GetChildMemberWithName("$variant$24")
-            if not variant24.IsValid():
-                return
+define_setting(
+    "max-children",
+    0,
+    "Maximum number of children shown for collections, 0 for no limit",
+)
 
-            discr_field = variant24.GetChildMemberWithName("$discr$")
-            if not discr_field.IsValid():
-                return
 
-            discriminant = discr_field.GetValueAsUnsigned()
+MaxChildrenRule = namedtuple("MaxChildrenRule", ["regex", "limit", "specificity"])
 
-            # Inline variant: discriminant is 0-23 (the length)
-            if discriminant <= 23:
-                self.variant_name = "Inline"
-                self.length = discriminant
-                if self.length == 0:
-                    self.content = ""
-                    return
+MAX_CHILDREN_RULES = []
 
-                # Get the inline buffer from $variant$
-                variant0 = variants.GetChildMemberWithName("$variant$")
-                if not variant0.IsValid():
-                    return
 
-                value = variant0.GetChildMemberWithName("value")
-                if not value.IsValid():
-                    return
+def _rule_specificity(pattern: str) -> int:
+    """Number of literal characters in pattern, used to rank overlapping rules"""
+    return len(re.sub(r"\\.|[.*+?^$()\[\]{}|]", "", pattern))
 
-                buf = value.GetChildMemberWithName("buf")
-                if not buf.IsValid():
-                    return
 
-                # Store the address of the inline buffer
-                self.content_address = buf.GetLoadAddress()
-
-                # Read bytes from the buffer for summary
-                error = SBError()
-                process = buf.GetProcess()
-                data = process.ReadMemory(self.content_address, self.length, error)
-                if error.Success():
-                    if PY3:
-                        try:
-                            self.content = data.decode("utf-8", "replace")
-                        except Exception:
-                            self.content = ""
-                    else:
-                        self.content = data
-
-            # Static variant: discriminant is 0x18 (24)
-            elif discriminant == 24:
-                self.variant_name = "Static"
-
-                # Get &str from $variant$24.value.__0
-                value = variant24.GetChildMemberWithName("value")
-                if not value.IsValid():
-                    return
+def _parse_max_children_overrides(
+    _debugger: lldb.SBDebugger, setting: FormatterSetting
+) -> None:
+    """
+    Parses the `max-children-overrides` setting into MAX_CHILDREN_RULES
 
-                str_ref = value.GetChildMemberWithName("__0")
-                if not str_ref.IsValid():
-                    return
+    The value is a `;` separated list of `regex=limit` pairs. Raises
+    ValueError so a malformed value is rejected by `rust-formatter-set`.
+    """
+    rules = []
+    for item in setting.value.split(";"):
+        if not item.strip():
+            continue
+        pattern, sep, limit = item.rpartition("=")
+        pattern = pattern.strip()
+        if not sep or not pattern:
+            raise ValueError("expected regex=limit, got %r" % item.strip())
+        try:
+            rules.append(
+                MaxChildrenRule(
+                    re.compile(pattern),
+                    int(limit.strip(), 0),
+                    _rule_specificity(pattern),
+                )
+            )
+        except (re.error, ValueError) as e:
+            raise ValueError("invalid override %r: %s" % (item.strip(), e))
+    MAX_CHILDREN_RULES[:] = rules
 
-                data_ptr = str_ref.GetChildMemberWithName("data_ptr")
-                length_field = str_ref.GetChildMemberWithName("length")
 
-                if not data_ptr.IsValid() or not length_field.IsValid():
-                    return
+define_setting(
+    "max-children-overrides",
+    "",
+    "Per type limits overriding max-children, as `regex=limit` pairs "
+    "separated by `;`. The most specific matching regex wins",
+    on_change=_parse_max_children_overrides,
+)
 
-                self.length = length_field.GetValueAsUnsigned()
-                self.pointer = data_ptr.GetValueAsUnsigned()
-                self.content_address = self.pointer
 
-                if self.length == 0:
-                    self.content = ""
-                    return
+define_setting(
+    "page-size",
+    0,
+    "Group the elements of Vecs, slices and arrays longer than this into "
+    "page children like `[page 0..1000]`, 0 to show the elements directly. "
+    "max-children doesn't limit the pages",
+)
 
-                error = SBError()
-                process = data_ptr.GetProcess()
-                data = process.ReadMemory(self.pointer, self.length, error)
-                if error.Success():
-                    if PY3:
-                        try:
-                            self.content = data.decode("utf-8", "replace")
-                        except Exception:
-                            self.content = ""
-                    else:
-                        self.content = data
-
-            # Heap variant: discriminant >= 0x19 (25)
-            else:
-                self.variant_name = "Heap"
 
-                # Get Arc<str> from $variant$25.value.__0.ptr.pointer
-                variant25 = variants.GetChildMemberWithName("$variant$25")
-                if not variant25.IsValid():
-                    return
+def _page_size(count: int) -> int:
+    """Elements per page child for a collection of count elements, 0 for none"""
+    size = get_setting("page-size")
+    if size <= 0 or count <= size:
+        return 0
+    return size
 
-                value = variant25.GetChildMemberWithName("value")
-                if not value.IsValid():
-                    return
 
-                inner = value.GetChildMemberWithName("__0")
-                if not inner.IsValid():
-                    return
+def _max_children(valobj: SBValue, count: int) -> int:
+    """
+    Caps a collection's child count by the max-children settings
 
-                ptr_field = inner.GetChildMemberWithName("ptr")
-                if not ptr_field.IsValid():
-                    return
+    Args:
+        valobj: The collection, whose type name is matched against the overrides
+        count: The number of elements in the collection
 
-                pointer = ptr_field.GetChildMemberWithName("pointer")
-                if not pointer.IsValid():
-                    return
+    Returns:
+        The number of children to show
+    """
+    limit = get_setting("max-children")
+    type_name = valobj.GetType().GetUnqualifiedType().GetName() or ""
+    matching = [rule for rule in MAX_CHILDREN_RULES if rule.regex.search(type_name)]
+    if matching:
+        limit = max(matching, key=lambda rule: rule.specificity).limit
+    if limit <= 0:
+        return count
+    return min(count, limit)
+
+
+def _check_radix(_debugger, setting):
+    if setting.value not in (10, 16):
+        raise ValueError("%s must be 10 or 16, got %d" % (setting.name, setting.value))
+
+
+define_setting(
+    "index-radix",
+    10,
+    "Radix of the index labels of collection children, 10 for [10] or 16 "
+    "for [0x0a]; the elements themselves are not affected",
+    _check_radix,
+)
+
+define_setting(
+    "int-radix",
+    10,
+    "Radix of the integers inside summaries, 10 or 16 for 0xff; negative "
+    "numbers show their two's complement in hex like {:#x}",
+    _check_radix,
+)
+
+
+def _index_name(index: int) -> str:
+    """Child name of the element at index, `[10]` or `[0x0a]` by index-radix"""
+    if get_setting("index-radix") == 16:
+        return "[0x%02x]" % index
+    return "[%d]" % index
+
+
+def _index_from_name(name: str) -> int:
+    """The index in a child name made by _index_name, -1 for other names"""
+    try:
+        return int(name.lstrip("[").rstrip("]"), 0)
+    except Exception:
+        return -1
 
-                data_ptr = pointer.GetChildMemberWithName("data_ptr")
-                length_field = pointer.GetChildMemberWithName("length")
 
-                if not data_ptr.IsValid() or not length_field.IsValid():
-                    return
+# Memory reads made by the providers, so the tests can keep an eye on how
+# much target traffic formatting a value costs
+STATS = {"memory_reads": 0}
 
-                self.length = length_field.GetValueAsUnsigned()
-                self.pointer = data_ptr.GetValueAsUnsigned()
 
-                if self.length == 0:
-                    self.content = ""
-                    return
+def _read_memory(process, address: int, size: int):
+    """
+    Reads size bytes at address and counts the read in STATS
 
-                # Arc<str> pointer points to ArcInner which has:
-                # - strong: AtomicUsize (8 bytes)
-                # - weak: AtomicUsize (8 bytes)
-                # - data: [u8] (the actual string)
-                # So we need to skip 16 bytes to get to the string data
-                arc_header_size = 16
-                string_data_ptr = self.pointer + arc_header_size
-                self.content_address = string_data_ptr
-
-                error = SBError()
-                process = data_ptr.GetProcess()
-                data = process.ReadMemory(string_data_ptr, self.length, error)
-                if error.Success():
-                    if PY3:
-                        try:
-                            self.content = data.decode("utf-8", "replace")
-                        except Exception:
-                            self.content = ""
-                    else:
-                        self.content = data
-
-        except Exception:
-            self.variant_name = ""
-            self.length = 0
-            self.content = ""
-            self.pointer = 0
-            self.content_address = 0
+    Returns:
+        The bytes, or None if the memory can't be read
+    """
+    STATS["memory_reads"] += 1
+    error = SBError()
+    data = process.ReadMemory(address, size, error)
+    if not error.Success():
+        return None
+    return data
 
-    def has_children(self):
-        return True
 
+def _decode_utf8(data: bytes) -> str:
+    """
+    Decodes string bytes read from the target
 
-def SmallVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    The bytes of a string that is partially overwritten or in the middle
+    of a mutation need not be valid UTF-8. Decoding is lossy, invalid
+    sequences become U+FFFD like in String::from_utf8_lossy, so a summary
+    never fails on them.
     """
-    Summary provider for smallvec::SmallVec<T, N>
+    if PY3:
+        return data.decode("utf-8", "replace")
+    return data
 
-    SmallVec stores up to N elements inline, larger collections on heap.
-    Uses a discriminant bit in the len field:
-    - len & 1 == 0: inline storage
-    - len & 1 == 1: heap storage
-    - Actual length is len >> 1
 
-    Args:
-        valobj: The SmallVec value to format
-        _dict: LLDB internal bookkeeping parameter
+def _read_string(process, address: int, length: int):
+    """
+    Reads length bytes at address and decodes them with _decode_utf8
 
     Returns:
-        A string representation showing size like "size=4"
+        The decoded string, or None if the memory can't be read
     """
-    valobj = valobj.GetNonSyntheticValue()
+    if length == 0:
+        return ""
 
-    # Get the len field
-    len_field = valobj.GetChildMemberWithName("len")
-    if not len_field.IsValid():
-        return "size=?"
+    data = _read_memory(process, address, length)
+    if data is None:
+        return None
+    return _decode_utf8(data)
 
-    len_inner = len_field.GetChildMemberWithName("__0")
-    if not len_inner.IsValid():
-        return "size=?"
 
-    len_value = len_inner.GetValueAsUnsigned()
+class ValueReader:
+    """
+    Reads the bytes of a single value
 
-    # Extract actual length (len >> 1)
-    actual_length = len_value >> 1
+    The string decoders take a reader rather than an SBValue. The same
+    reads then work for a value in target memory and for one LLDB only has
+    as bytes (a register or an expression result), and the decoders can be
+    tested against hand-built layouts without a debugger.
 
-    return "size=%d" % actual_length
+    `process` only needs ReadMemory and is used for memory outside the
+    value, like the text behind a heap string.
+    """
 
+    def __init__(self, process, address: int, byteorder: str, word_size: int, raw=None):
+        self.process = process
+        self.address = address
+        self.byteorder = byteorder
+        self.word_size = word_size
+        self.raw = raw
+
+    @staticmethod
+    def for_value(valobj: SBValue) -> ValueReader:
+        valobj = valobj.GetNonSyntheticValue()
+        process = valobj.GetProcess()
+        byteorder = "big" if process.GetByteOrder() == lldb.eByteOrderBig else "little"
+        address = valobj.GetLoadAddress()
+        raw = None
+        if address == lldb.LLDB_INVALID_ADDRESS:
+            raw = _raw_bytes(valobj)
+        return ValueReader(process, address, byteorder, _usize_size(valobj), raw)
+
+    @property
+    def in_memory(self) -> bool:
+        return self.raw is None
+
+    def read(self, offset: int, size: int):
+        """size bytes at offset into the value, or None if unreadable"""
+        if self.raw is not None:
+            if offset + size > len(self.raw):
+                return None
+            return self.raw[offset : offset + size]
+        return _read_memory(self.process, self.address + offset, size)
+
+    def read_int(self, data: bytes, offset: int = 0, size: int = 0) -> int:
+        """Decodes an unsigned integer of size bytes, a word by default"""
+        size = size or self.word_size
+        return int.from_bytes(data[offset : offset + size], self.byteorder)
+
+    def read_string(self, address: int, length: int):
+        return _read_string(self.process, address, length)
+
+
+def _unwrap_pointer(valobj: SBValue) -> int:
+    """
+    Follows Unique/NonNull style wrappers down to the raw pointer
 
-class SmallVecSyntheticProvider:
+    Returns:
+        The pointer value, or 0 if no pointer was found
     """
-    Synthetic provider for smallvec::SmallVec<T, N>
+    for _ in range(4):
+        if not valobj.IsValid():
+            return 0
+        if valobj.TypeIsPointerType():
+            return valobj.GetValueAsUnsigned()
+        inner = valobj.GetChildMemberWithName("pointer")
+        if not inner.IsValid():
+            inner = valobj.GetChildAtIndex(0)
+        valobj = inner
+    return 0
 
-    Provides access to individual elements as children in the debugger.
+
+def _referent(valobj: SBValue) -> SBValue:
     """
+    Follows references (`&T`, `&mut T`) to the value they point at
 
-    def __init__(self, valobj: SBValue, _dict):
-        self.valobj = valobj
-        self.length = 0
-        self.is_heap = False
-        self.heap_ptr = 0
-        self.inline_data_address = 0
-        self.element_size = 0
-        self.element_type = None
-        self.update()
+    LLDB offers a type's providers to references to the type as well, this
+    lets the providers work on the value itself. Raw pointers are kept.
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    while valobj.TypeIsPointerType() and (valobj.GetTypeName() or "").startswith("&"):
+        pointee = valobj.Dereference()
+        if not pointee.IsValid() or not pointee.GetError().Success():
+            break
+        valobj = pointee.GetNonSyntheticValue()
+    return valobj
 
-    def num_children(self):
-        return self.length
 
-    def get_child_index(self, name: str):
-        try:
-            return int(name.lstrip("[").rstrip("]"))
-        except Exception:
-            return -1
+def _address_suffix(valobj: SBValue, address=None) -> str:
+    """
+    The ` @0x...` suffix of the show-addresses setting
 
-    def get_child_at_index(self, index: int):
-        if index < 0 or index >= self.length:
-            return None
+    Args:
+        valobj: The value being summarized; only pointers get a suffix
+        address: Address to show for pointer-like structs such as fat
+            pointers and Rc, whose type isn't a pointer type to LLDB
 
-        if not self.element_type or not self.element_type.IsValid():
-            return None
+    Returns:
+        The suffix, or "" if the setting is off or valobj is a value type
+    """
+    if not get_setting("show-addresses"):
+        return ""
+    if address is None:
+        valobj = valobj.GetNonSyntheticValue()
+        if not valobj.TypeIsPointerType():
+            return ""
+        address = valobj.GetValueAsUnsigned()
+    return " @0x%x" % address
 
-        if self.element_size == 0:
-            return None
 
-        try:
-            if self.is_heap:
-                # Read from heap - calculate address and create value
-                if self.heap_ptr == 0:
-                    return None
-                address = self.heap_ptr + index * self.element_size
-                element = self.valobj.CreateValueFromAddress(
-                    "[%d]" % index, address, self.element_type
-                )
-                return element
-            else:
-                # Read from inline storage - calculate address from inline buffer
-                if self.inline_data_address == 0:
-                    return None
-                address = self.inline_data_address + index * self.element_size
-                element = self.valobj.CreateValueFromAddress(
-                    "[%d]" % index, address, self.element_type
-                )
-                return element
+def _usize_size(valobj: SBValue) -> int:
+    """
+    Byte size of usize and thin pointers on the target of valobj
 
-        except Exception:
-            return None
+    Lengths, capacities and counters are usize: 8 bytes on 64-bit targets,
+    4 on 32-bit ones like wasm32 and most embedded targets. Raw reads of
+    such fields use this instead of assuming 64 bits.
+    """
+    return valobj.GetTarget().GetAddressByteSize() or 8
 
-    def update(self):
-        self.length = 0
-        self.is_heap = False
-        self.heap_ptr = 0
-        self.inline_data_address = 0
-        self.element_size = 0
-        self.element_type = None
 
-        try:
-            valobj = self.valobj.GetNonSyntheticValue()
+def _pointee_field_offset(pointer: SBValue, name: str, default: int) -> int:
+    """
+    Offset of a field in the type a pointer points to
 
-            # Get the len field
-            len_field = valobj.GetChildMemberWithName("len")
-            if not len_field.IsValid():
-                return
+    The offset comes from the debug info, so it is right for packed and
+    over-aligned layouts. default is used if the field isn't described.
+    """
+    pointee = pointer.GetType().GetPointeeType()
+    for i in range(pointee.GetNumberOfFields()):
+        field = pointee.GetFieldAtIndex(i)
+        if field.GetName() == name:
+            return field.GetOffsetInBytes()
+    return default
 
-            len_inner = len_field.GetChildMemberWithName("__0")
-            if not len_inner.IsValid():
-                return
 
-            len_value = len_inner.GetValueAsUnsigned()
+def _read_refcounts(pointer: SBValue, address: int):
+    """
+    Reads the strong and weak counts of an RcInner/ArcInner at address
 
-            # Check discriminant bit (bit 0)
-            self.is_heap = (len_value & 1) == 1
+    pointer is a pointer to the inner type and provides the field offsets.
+    Both counters are fetched with one plain memory read, without any
+    atomic semantics. While other threads clone or drop an Arc the counts
+    are in flux and the pair may be torn or inconsistent; that is fine for
+    display, so nothing here assumes strong >= 1 or weak >= 1.
 
-            # Extract actual length (len >> 1)
-            self.length = len_value >> 1
+    Returns:
+        A (strong, weak) tuple where weak excludes the implicit weak
+        reference held by the strong references, or None if unreadable
+    """
+    size = _usize_size(pointer)
+    strong_offset = _pointee_field_offset(pointer, "strong", 0)
+    weak_offset = _pointee_field_offset(pointer, "weak", size)
 
-            # Get element type from SmallVec<T, N> template argument
-            self.element_type = valobj.GetType().GetTemplateArgumentType(0)
-            if not self.element_type.IsValid():
-                self.length = 0
-                return
+    process = pointer.GetProcess()
+    header = _read_memory(process, address, max(strong_offset, weak_offset) + size)
+    if header is None:
+        return None
 
-            self.element_size = self.element_type.GetByteSize()
-            if self.element_size == 0:
-                self.length = 0
-                return
+    byteorder = "big" if process.GetByteOrder() == lldb.eByteOrderBig else "little"
+    strong = int.from_bytes(header[strong_offset : strong_offset + size], byteorder)
+    weak = int.from_bytes(header[weak_offset : weak_offset + size], byteorder)
+    if strong > 0 and weak > 0:
+        weak -= 1
+    return strong, weak
 
-            # Get raw union
-            raw = valobj.GetChildMemberWithName("raw")
-            if not raw.IsValid():
+
+def _shortest_float_repr(value: float, single: bool) -> str:
+    """Shortest decimal text that reads back as the same f32 or f64"""
+    if not single:
+        return repr(value)
+    for precision in range(1, 10):
+        text = "%.*g" % (precision, value)
+        try:
+            if struct.unpack("<f", struct.pack("<f", float(text)))[0] == value:
+                return text
+        except OverflowError:
+            # Rounded past the f32 range
+            continue
+    return repr(value)
+
+
+def _format_float(value: float, single: bool = False) -> str:
+    """
+    Formats a float like Rust's Debug does
+
+    Uses the shortest digits that round-trip, so 0.1 renders as `0.1`
+    instead of LLDB's `0.10000000000000001`. Whole numbers keep a `.0`,
+    very large and very small magnitudes switch to exponent notation
+    (`1e16`, `1.5e-7`).
+    """
+    if math.isnan(value):
+        return "NaN"
+    if math.isinf(value):
+        return "inf" if value > 0 else "-inf"
+    if value == 0:
+        return "-0.0" if math.copysign(1.0, value) < 0 else "0.0"
+
+    sign, digits, exponent = decimal.Decimal(
+        _shortest_float_repr(value, single)
+    ).as_tuple()
+    while len(digits) > 1 and digits[-1] == 0:
+        digits = digits[:-1]
+        exponent += 1
+    prefix = "-" if sign else ""
+
+    if 1e-4 <= abs(value) < 1e16:
+        text = format(decimal.Decimal((0, digits, exponent)), "f")
+        if "." not in text:
+            text += ".0"
+        return prefix + text
+
+    mantissa = "".join(str(d) for d in digits)
+    if len(mantissa) > 1:
+        mantissa = mantissa[0] + "." + mantissa[1:]
+    return "%s%se%d" % (prefix, mantissa, exponent + len(digits) - 1)
+
+
+def _float_value_summary(valobj: SBValue):
+    """
+    Renders an f32 or f64 value with _format_float
+
+    Returns:
+        The formatted float, or None if valobj isn't a float
+    """
+    basic_type = valobj.GetType().GetCanonicalType().GetBasicType()
+    if basic_type not in (lldb.eBasicTypeFloat, lldb.eBasicTypeDouble):
+        return None
+    raw = _raw_bytes(valobj)
+    if len(raw) not in (4, 8):
+        return None
+    order = ">" if valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig else "<"
+    single = len(raw) == 4
+    value = struct.unpack(order + ("f" if single else "d"), raw)[0]
+    return _format_float(value, single)
+
+
+# Rust's integer types by name, with their signedness
+RUST_INTEGERS = {
+    "u8": False,
+    "u16": False,
+    "u32": False,
+    "u64": False,
+    "u128": False,
+    "usize": False,
+    "i8": True,
+    "i16": True,
+    "i32": True,
+    "i64": True,
+    "i128": True,
+    "isize": True,
+}
+
+
+def _integer_value(valobj: SBValue, signed: bool = True):
+    """
+    The value of a Rust integer as a Python int
+
+    The value is decoded from its bytes in target order rather than through
+    GetValueAsUnsigned, which only has 64 bits and cuts u128 and i128 short.
+    signed=False reads signed integers as their two's complement bits.
+
+    Returns:
+        The integer, or None if valobj isn't a Rust integer
+    """
+    is_signed = RUST_INTEGERS.get(valobj.GetType().GetCanonicalType().GetName())
+    if is_signed is None:
+        return None
+    raw = _raw_bytes(valobj)
+    if not raw:
+        return None
+    big = valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig
+    byteorder = "big" if big else "little"
+    return int.from_bytes(raw, byteorder, signed=signed and is_signed)
+
+
+def _integer_value_summary(valobj: SBValue):
+    """
+    Renders an integer in the int-radix setting
+
+    Returns:
+        The formatted integer, or None if valobj isn't a Rust integer
+    """
+    if get_setting("int-radix") == 16:
+        value = _integer_value(valobj, signed=False)
+        return None if value is None else "0x%x" % value
+    value = _integer_value(valobj)
+    return None if value is None else str(value)
+
+
+def _value_summary(valobj: SBValue) -> str:
+    """
+    Renders a value for use inside another summary
+
+    Goes through LLDB's normal formatter lookup so nested types use their
+    own providers. Box and references are followed to their pointee, raw
+    pointers keep showing the address. Floats without a summary of their
+    own use _format_float rather than LLDB's full precision, integers
+    _integer_value_summary, the unit type shows as `()`.
+
+    Returns:
+        The summary or value, or "" if LLDB has neither
+    """
+    if _is_disabled_type(valobj.GetType()):
+        return valobj.GetValue() or ""
+    summary = valobj.GetSummary()
+    if summary:
+        return summary
+
+    text = _float_value_summary(valobj)
+    if text is None:
+        text = _integer_value_summary(valobj)
+    if text is not None:
+        return text
+    if valobj.GetTypeName() == "()":
+        return "()"
+
+    if valobj.TypeIsPointerType() and not (valobj.GetTypeName() or "").startswith(
+        "*"
+    ):
+        pointee = valobj.Dereference()
+        if pointee.IsValid() and pointee.GetError().Success():
+            return _value_summary(pointee)
+
+    return valobj.GetValue() or ""
+
+
+def _compact_label(valobj: SBValue) -> str:
+    """
+    Short rendering of a value without a summary of its own
+
+    Scalars show their value and tuples their elements, other aggregates
+    only their type name, e.g. `(1, Point { .. })`.
+    """
+    value = valobj.GetValue()
+    if value is not None and valobj.GetNumChildren() == 0:
+        return value
+
+    type_name = valobj.GetType().GetUnqualifiedType().GetName() or ""
+    if type_name.startswith("("):
+        return "(%s)" % _join(
+            _key_label(valobj.GetChildAtIndex(i))
+            for i in range(valobj.GetNumChildren())
+        )
+    name = _strip_generics(type_name).rsplit("::", 1)[-1]
+    return "%s { .. }" % name if name else "{...}"
+
+
+def _key_label(key: SBValue) -> str:
+    """
+    Renders a map key for a child name like `[(1, 2)]` or a preview entry
+
+    The key goes through LLDB's normal formatter lookup like any value, so
+    its own summary is used unless `key-summaries` is off; keys without one
+    fall back to _compact_label. SmolStr and CompactString keys are read
+    directly by _string_key_text.
+    """
+    if get_setting("key-summaries"):
+        text = _string_key_text(key) or _value_summary(key)
+        if text:
+            return text
+    return _compact_label(key)
+
+
+def _join(parts) -> str:
+    """Joins list items as `1, 2` or, with the compact setting, `1,2`"""
+    return ("," if get_setting("compact") else ", ").join(parts)
+
+
+def _field(name: str, text: str) -> str:
+    """Renders a field or map entry as `name: text`, `name:text` if compact"""
+    return ("%s:%s" if get_setting("compact") else "%s: %s") % (name, text)
+
+
+def _braced(name: str, body: str) -> str:
+    """Renders `Name { body }`, `Name{body}` if compact"""
+    return ("%s{%s}" if get_setting("compact") else "%s { %s }") % (name, body)
+
+
+# Nesting level of the preview being built and the characters left for a
+# preview nested in it, see _preview
+_PREVIEW_DEPTH = [0]
+_PREVIEW_ROOM = [0]
+
+# Rc and Arc allocations already rendered in the outermost preview being
+# built, see _preview and RefCountedSummaryProvider
+_PREVIEW_SHARED = set()
+
+# Deadline of the summary being formatted, see _budget_begin
+_FORMAT_DEADLINE = [None]
+
+# Appended to summaries that ran out of format-budget-ms
+BUDGET_MARKER = "…(budget exceeded)"
+
+
+def _budget_begin() -> bool:
+    """
+    Starts the format-budget-ms deadline unless one is running already
+
+    Summaries call into each other, so the outermost call owns the
+    deadline and the nested ones share it. Corrupted lengths and pointers
+    can make a collection look enormous; the loops reading it check
+    _budget_exceeded and stop.
+
+    Returns:
+        True if this call started the deadline and has to pass that on to
+        _budget_end
+    """
+    milliseconds = get_setting("format-budget-ms")
+    if _FORMAT_DEADLINE[0] is not None or milliseconds <= 0:
+        return False
+    _FORMAT_DEADLINE[0] = time.monotonic() + milliseconds / 1000.0
+    return True
+
+
+def _budget_end(started: bool):
+    if started:
+        _FORMAT_DEADLINE[0] = None
+
+
+def _budget_exceeded() -> bool:
+    deadline = _FORMAT_DEADLINE[0]
+    return deadline is not None and time.monotonic() > deadline
+
+
+def _preview_budget() -> int:
+    """
+    Characters the preview being built may use, see _preview
+
+    A collection nested in another preview gets what is left of the outer
+    budget, and 0 below `max-depth` levels, where only a placeholder fits.
+    """
+    max_depth = get_setting("max-depth")
+    if max_depth > 0 and _PREVIEW_DEPTH[0] >= max_depth:
+        return 0
+    budget = get_setting("preview-budget")
+    if get_setting("compact"):
+        budget //= 2
+    if _PREVIEW_DEPTH[0] > 0:
+        budget = min(budget, _PREVIEW_ROOM[0])
+    return budget
+
+
+def _preview(provider, style: str = "sequence") -> str:
+    """
+    Builds an element preview like `[1, 2, 3]` from a synthetic provider
+
+    Elements are rendered with _value_summary, each with the summary LLDB
+    resolves for its type (ours, a user rule or LLDB's default); aggregates
+    without any show as `{...}`. The provider's `length` elements are listed
+    until the preview budget, halved by the compact setting, is used up and
+    the rest is elided as `...`; max-children doesn't cap them. BUDGET_MARKER
+    ends the preview instead once format-budget-ms runs out, here or while
+    the provider read its elements (its `truncated` flag).
+
+    Collections nested in the preview list their elements too, within
+    what is left of the outer budget, down to `max-depth` levels. Deeper
+    ones, and those that don't fit, show a placeholder such as
+    `size=3 [...]`; their elements are still there when the child is
+    expanded, with its own provider. An Rc or Arc allocation shared by
+    several elements is rendered once, then as `<shared @0x...>` (see
+    _PREVIEW_SHARED).
+
+    Maps provide get_child_key and are previewed as `key: value` pairs.
+    style picks the delimiters, from the `sequence-open`/`sequence-close`
+    or the `map-open`/`map-close` settings.
+    """
+    open_bracket = get_setting(style + "-open")
+    close_bracket = get_setting(style + "-close")
+    max_depth = get_setting("max-depth")
+    if max_depth > 0 and _PREVIEW_DEPTH[0] >= max_depth:
+        return open_bracket + "..." + close_bracket
+
+    budget = _preview_budget()
+    # Paged providers have pages as their children, not the elements
+    element_at = getattr(provider, "element_at", provider.get_child_at_index)
+    parts = []
+    used = 0
+    room = _PREVIEW_ROOM[0]
+    started = _budget_begin()
+    _PREVIEW_DEPTH[0] += 1
+    try:
+        for i in range(provider.length):
+            if _budget_exceeded():
+                parts.append(BUDGET_MARKER)
+                break
+            if used >= budget:
+                parts.append("...")
+                break
+            _PREVIEW_ROOM[0] = budget - used
+            child = element_at(i)
+            text = _value_summary(child) if child is not None else ""
+            text = text or "{...}"
+            if hasattr(provider, "get_child_key"):
+                text = _field(provider.get_child_key(i), text)
+            parts.append(text)
+            used += len(parts[-1]) + 2
+        else:
+            if getattr(provider, "truncated", False):
+                parts.append(BUDGET_MARKER)
+    finally:
+        _PREVIEW_DEPTH[0] -= 1
+        _PREVIEW_ROOM[0] = room
+        if _PREVIEW_DEPTH[0] == 0:
+            _PREVIEW_SHARED.clear()
+        _budget_end(started)
+    return open_bracket + _join(parts) + close_bracket
+
+
+def _bool_bits(provider, address: int):
+    """
+    Renders the elements of a bool collection as bits, e.g. `10110`
+
+    Only with the bools-as-bits setting on and only for collections whose
+    element type is bool. provider is the collection's synthetic provider,
+    address where its elements start; they are fetched with one read, as
+    many as _preview_budget leaves room for.
+
+    Returns:
+        The bits, or None to fall back to the regular preview
+    """
+    if not get_setting("bools-as-bits"):
+        return None
+    element_type = provider.element_type
+    if element_type is None or not element_type.IsValid():
+        return None
+    if element_type.GetCanonicalType().GetBasicType() != lldb.eBasicTypeBool:
+        return None
+
+    count = min(provider.length, _preview_budget())
+    if count == 0:
+        return None
+    data = _read_memory(provider.valobj.GetProcess(), address, count)
+    if data is None:
+        return None
+
+    bits = "".join("1" if byte else "0" for byte in bytearray(data))
+    if count < provider.length:
+        bits += "..."
+    return bits
+
+
+# Byte sizes of the third-party types whose providers read raw bytes, by
+# pointer size. A dependency with another layout must not be decoded with
+# these offsets.
+EXPECTED_SIZES = {
+    "smol_str::SmolStr": {4: 24, 8: 24},
+    "compact_str::CompactString": {4: 12, 8: 24},
+    "ecow::string::EcoString": {4: 8, 8: 16},
+}
+
+# Diagnostics for mismatching types, by type name, see _layout_mismatch
+LAYOUT_WARNINGS = {}
+
+
+def _layout_mismatch(valobj: SBValue, type_name: str) -> bool:
+    """
+    Checks valobj's size against the one expected for type_name
+
+    A mismatch is reported once per type on stderr and kept for
+    `rust-formatter-debug`; the caller falls back to the default rendering.
+
+    Returns:
+        True if the size differs and the value must not be decoded
+    """
+    expected = EXPECTED_SIZES[type_name].get(_usize_size(valobj))
+    size = valobj.GetNonSyntheticValue().GetByteSize()
+    if expected is None or size == expected:
+        return False
+
+    actual_name = valobj.GetType().GetUnqualifiedType().GetName() or type_name
+    if actual_name not in LAYOUT_WARNINGS:
+        LAYOUT_WARNINGS[actual_name] = (
+            "%s is %d bytes, the provider expects %d; showing it unformatted"
+            % (actual_name, size, expected)
+        )
+        sys.stderr.write("rust_bonus_types: %s\n" % LAYOUT_WARNINGS[actual_name])
+    return True
+
+
+# Layouts of SmolStr types, keyed by type name and size
+_SMOLSTR_LAYOUTS = {}
+
+# SmolStr decoded down to where its text lives. data holds the inline
+# bytes for values that aren't in target memory, otherwise it is None.
+SmolStrValue = namedtuple(
+    "SmolStrValue", ("variant", "length", "content_address", "pointer", "data")
+)
+
+
+def _type_field_offset(sbtype, path):
+    """
+    Offset of a nested field, following path through the field types
+
+    Returns:
+        An (offset, type) tuple for the last field, or None if a field
+        along the path does not exist
+    """
+    offset = 0
+    for name in path:
+        for i in range(sbtype.GetNumberOfFields()):
+            member = sbtype.GetFieldAtIndex(i)
+            if member.GetName() == name:
+                break
+        else:
+            return None
+        offset += member.GetOffsetInBytes()
+        sbtype = member.GetType()
+    return offset, sbtype
+
+
+class SmolStrLayout:
+    """
+    Where the parts of a SmolStr live, relative to the start of the value
+
+    Resolved from the debug info once per type. With it a SmolStr is
+    decoded with a read of the tag byte and one read of exactly the bytes
+    its variant needs, instead of walking the Repr enum child by child.
+
+    The Repr variants are recognized by their fields rather than their
+    discriminants, which differ between smol_str versions: 0.2 and 0.3
+    keep up to 23 bytes inline with Static at 24 and Heap at 25, 0.1 keeps
+    22 with Heap at 23 and Static at 24. inline_capacity, static_discr and
+    heap_discr hold what this type uses; static_discr is None for versions
+    without a Static variant.
+    """
+
+    def __init__(self, sbtype):
+        repr_member = sbtype.GetFieldAtIndex(0)
+        base = repr_member.GetOffsetInBytes()
+        variants = _type_field_offset(repr_member.GetType(), ("$variants$",))
+        if variants is None:
+            raise ValueError("unexpected SmolStr layout: no $variants$")
+        variants_type = variants[1]
+
+        self.discr_offset = None
+        self.inline_offset = None
+        self.static_discr = None
+        self.heap_discr = None
+        heap_type = static_type = None
+
+        def locate(*path):
+            found = _type_field_offset(variants_type, path)
+            return None if found is None else (base + found[0], found[1])
+
+        for i in range(variants_type.GetNumberOfFields()):
+            name = variants_type.GetFieldAtIndex(i).GetName() or ""
+            discr = locate(name, "$discr$")
+            if discr is not None:
+                self.discr_offset, discr_type = discr
+                self.discr_size = discr_type.GetByteSize()
+
+            buf = locate(name, "value", "buf")
+            heap = locate(name, "value", "__0", "ptr", "pointer")
+            static = locate(name, "value", "__0")
+            if buf is not None:
+                self.inline_offset = buf[0]
+                self.inline_capacity = buf[1].GetByteSize()
+            elif heap is not None:
+                self.heap_discr = _variant_discriminant(name)
+                self.heap_offset, heap_type = heap
+            elif static is not None:
+                self.static_discr = _variant_discriminant(name)
+                self.static_offset, static_type = static
+
+        if None in (self.discr_offset, self.inline_offset, self.heap_discr):
+            raise ValueError("unexpected SmolStr layout: %s" % sbtype.GetName())
+
+        # Static strings hold a &str, heap strings an Arc<str>; both are
+        # fat pointers read in one go
+        self.heap_fields = self._fat_pointer_fields(heap_type)
+        if static_type is not None:
+            self.static_fields = self._fat_pointer_fields(static_type)
+
+        # The Arc<str> data pointer points at the ArcInner, the text
+        # follows its strong and weak counts
+        data_ptr = _type_field_offset(heap_type, ("data_ptr",))
+        self.arc_inner_type = data_ptr[1]
+        self.pointer_size = self.arc_inner_type.GetByteSize()
+        data = _type_field_offset(self.arc_inner_type.GetPointeeType(), ("data",))
+        self.arc_data_offset = data[0] if data else 2 * self.pointer_size
+
+    @staticmethod
+    def _fat_pointer_fields(sbtype):
+        data_ptr = _type_field_offset(sbtype, ("data_ptr",))
+        length = _type_field_offset(sbtype, ("length",))
+        if data_ptr is None or length is None:
+            raise ValueError("unexpected SmolStr layout: %s" % sbtype.GetName())
+        return data_ptr[0], length[0], sbtype.GetByteSize()
+
+
+def _variant_discriminant(name: str):
+    """The N of a `$variant$N` member name, None for the bare `$variant$`"""
+    digits = name[len("$variant$") :]
+    return int(digits) if digits.isdigit() else None
+
+
+# A SmolStrLayout that isn't read from the debug info
+SmolStrFixedLayout = namedtuple(
+    "SmolStrFixedLayout",
+    (
+        "discr_offset",
+        "discr_size",
+        "inline_offset",
+        "inline_capacity",
+        "static_discr",
+        "static_offset",
+        "static_fields",
+        "heap_discr",
+        "heap_offset",
+        "heap_fields",
+        "arc_data_offset",
+        "arc_inner_type",
+    ),
+)
+
+
+def smolstr_fallback_layout(size: int, word: int):
+    """
+    The SmolStr layout of smol_str 0.2 and 0.3, for when the debug info
+    doesn't describe the Repr variants
+
+    The value is 24 bytes on every target: the tag byte, then either up to
+    23 inline bytes or, aligned to a word, the fat pointer of the &str or
+    Arc<str>. Without the ArcInner type the counts are assumed to be two
+    words in front of the text.
+
+    Returns:
+        A SmolStrFixedLayout, or None for another size
+    """
+    if size != 24:
+        return None
+    fat = (0, word, 2 * word)
+    return SmolStrFixedLayout(
+        discr_offset=0,
+        discr_size=1,
+        inline_offset=1,
+        inline_capacity=23,
+        static_discr=24,
+        static_offset=word,
+        static_fields=fat,
+        heap_discr=25,
+        heap_offset=word,
+        heap_fields=fat,
+        arc_data_offset=2 * word,
+        arc_inner_type=None,
+    )
+
+
+def _smolstr_layout(valobj: SBValue):
+    sbtype = valobj.GetType().GetUnqualifiedType()
+    key = (sbtype.GetName(), sbtype.GetByteSize())
+    if key not in _SMOLSTR_LAYOUTS:
+        try:
+            _SMOLSTR_LAYOUTS[key] = SmolStrLayout(sbtype)
+        except ValueError:
+            _SMOLSTR_LAYOUTS[key] = smolstr_fallback_layout(
+                sbtype.GetByteSize(), _usize_size(valobj)
+            )
+    return _SMOLSTR_LAYOUTS[key]
+
+
+def _smolstr_decode(valobj: SBValue):
+    """Decodes a SmolStr value with _smolstr_decode_from"""
+    layout = _smolstr_layout(valobj.GetNonSyntheticValue())
+    if layout is None:
+        return None
+    return _smolstr_decode_from(ValueReader.for_value(valobj), layout)
+
+
+def _smolstr_decode_from(reader: ValueReader, layout):
+    """
+    Decodes a SmolStr without reading more than needed
+
+    SmolStr uses an internal Repr enum with three variants:
+    - Inline (discriminant 0-23): small strings stored inline, discriminant is the length
+    - Static (discriminant 0x18=24): reference to static string
+    - Heap (discriminant 0x19=25): Arc-allocated string on heap
+    Older versions number them differently, the layout has the values.
+
+    Only the discriminant byte is read up front. Inline strings need no
+    further read until their text is wanted, static and heap strings need
+    their fat pointer.
+
+    The layout is a SmolStrLayout, or anything with its attributes.
+
+    Returns:
+        A SmolStrValue, or None if the value can't be decoded
+    """
+    tag = reader.read(layout.discr_offset, layout.discr_size)
+    if tag is None:
+        return None
+    discriminant = reader.read_int(tag, 0, layout.discr_size)
+
+    if discriminant <= layout.inline_capacity:
+        if not reader.in_memory:
+            # Not in target memory, work on the bytes LLDB already has
+            data = reader.read(layout.inline_offset, discriminant)
+            return SmolStrValue("Inline", discriminant, 0, 0, data or b"")
+        return SmolStrValue(
+            "Inline", discriminant, reader.address + layout.inline_offset, 0, None
+        )
+
+    if discriminant == layout.static_discr:
+        variant, offset, fields = "Static", layout.static_offset, layout.static_fields
+    elif discriminant == layout.heap_discr:
+        variant, offset, fields = "Heap", layout.heap_offset, layout.heap_fields
+    else:
+        return None
+
+    ptr_offset, length_offset, size = fields
+    fat = reader.read(offset, size)
+    if fat is None:
+        return None
+    pointer = reader.read_int(fat, ptr_offset)
+    length = reader.read_int(fat, length_offset)
+    content_address = pointer
+    if variant == "Heap":
+        content_address += layout.arc_data_offset
+    return SmolStrValue(variant, length, content_address, pointer, None)
+
+
+def _smolstr_text(valobj: SBValue):
+    """Reads the text of a SmolStr value with _smolstr_text_from"""
+    layout = _smolstr_layout(valobj.GetNonSyntheticValue())
+    if layout is None:
+        return None, None
+    return _smolstr_text_from(ValueReader.for_value(valobj), layout)
+
+
+def _smolstr_text_from(reader: ValueReader, layout):
+    """
+    Reads the text of a SmolStr, see _smolstr_decode_from
+
+    Returns:
+        A (text, value) tuple with the decoded SmolStrValue, text being None
+        if the string can't be read
+    """
+    value = _smolstr_decode_from(reader, layout)
+    if value is None:
+        return None, None
+    if value.data is not None:
+        return _decode_utf8(value.data), value
+    return reader.read_string(value.content_address, value.length), value
+
+
+def SmolStrSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for smol_str::SmolStr
+
+    See _smolstr_decode for the representation.
+
+    Args:
+        valobj: The SmolStr value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation like "hello" with quotes
+    """
+    if _layout_mismatch(valobj, "smol_str::SmolStr"):
+        return ""
+    data, value = _smolstr_text(valobj)
+    if data is None:
+        return '""'
+
+    if not get_setting("metadata"):
+        return '"%s"' % data
+    layout = _smolstr_layout(valobj.GetNonSyntheticValue())
+    if value.variant == "Heap" and layout.arc_inner_type is not None:
+        # _read_refcounts only takes the ArcInner field offsets from the
+        # pointer, the counts are read at value.pointer
+        valobj = valobj.GetNonSyntheticValue()
+        data_ptr = valobj.CreateValueFromAddress(
+            "data_ptr",
+            valobj.GetLoadAddress() + layout.heap_offset + layout.heap_fields[0],
+            layout.arc_inner_type,
+        )
+        counts = _read_refcounts(data_ptr, value.pointer)
+        if counts is not None:
+            return '"%s" (heap, strong=%d)' % (data, counts[0])
+        return '"%s" (heap)' % data
+    return '"%s" (%s)' % (data, value.variant.lower())
+
+
+class SmolStrSyntheticProvider:
+    """
+    Synthetic provider for smol_str::SmolStr
+
+    Exposes internal structure as children:
+    - variant: "Inline", "Static", or "Heap"
+    - length: the string length
+    - content: the string content
+    - pointer: the pointer address (for Static and Heap variants)
+
+    Types of an unexpected size keep their raw children.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.variant_name = ""
+        self.length = 0
+        self.pointer = 0
+        self.content_address = 0  # Store address of string data
+        self.raw = None
+        self.update()
+
+    def num_children(self):
+        if self.raw is not None:
+            return self.raw.GetNumChildren()
+        # Always show: variant, length, content
+        # Show pointer for Static and Heap variants
+        if self.variant_name in ("Static", "Heap"):
+            return 4
+        return 3
+
+    def get_child_index(self, name: str):
+        if self.raw is not None:
+            return self.raw.GetIndexOfChildWithName(name)
+        if name == "variant":
+            return 0
+        elif name == "length":
+            return 1
+        elif name == "content":
+            return 2
+        elif name == "pointer":
+            return 3
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if self.raw is not None:
+            return self.raw.GetChildAtIndex(index)
+        if index < 0:
+            return None
+
+        try:
+            if index == 0:
+                # variant field - return as string summary
+                # Create a value that will display the variant name
+                return self.valobj.CreateValueFromExpression(
+                    "variant", '(const char*)"%s"' % self.variant_name
+                )
+            elif index == 1:
+                # length field - create as unsigned integer
+                return self.valobj.CreateValueFromExpression(
+                    "length", "(unsigned long long)%d" % self.length
+                )
+            elif index == 2:
+                # content field - create as char array pointing to actual data
+                if self.content_address != 0 and self.length > 0:
+                    # Create char[length] type
+                    char_type = self.valobj.GetTarget().GetBasicType(
+                        lldb.eBasicTypeChar
+                    )
+                    char_array_type = char_type.GetArrayType(self.length)
+
+                    # Create value from address
+                    return self.valobj.CreateValueFromAddress(
+                        "content", self.content_address, char_array_type
+                    )
+                else:
+                    # Empty string
+                    return self.valobj.CreateValueFromExpression(
+                        "content", '(const char*)""'
+                    )
+            elif index == 3 and self.variant_name in ("Static", "Heap"):
+                # pointer field - create as hex pointer
+                return self.valobj.CreateValueFromExpression(
+                    "pointer", "(void*)0x%x" % self.pointer
+                )
+        except Exception:
+            return None
+
+        return None
+
+    def update(self):
+        self.variant_name = ""
+        self.length = 0
+        self.pointer = 0
+        self.content_address = 0
+        self.raw = None
+
+        if _layout_mismatch(self.valobj, "smol_str::SmolStr"):
+            self.raw = self.valobj.GetNonSyntheticValue()
+            return
+
+        try:
+            value = _smolstr_decode(self.valobj)
+        except Exception:
+            value = None
+        if value is None:
+            return
+
+        self.variant_name = value.variant
+        self.length = value.length
+        self.pointer = value.pointer
+        self.content_address = value.content_address
+
+    def has_children(self):
+        return True
+
+
+# How a small string tells its variants apart by a single tag byte, see
+# _tagged_string_repr. tag_offset counts from the end of the value when
+# negative. inline_length(tag, size) is the length of an inline string, or
+# None if the tag marks a string behind a pointer; pointer_variant(tag)
+# names that variant, or is None for a tag the crate doesn't use.
+#
+# SmolStr keeps its own decoder, _smolstr_decode: it reads the tag byte
+# first and then only the bytes its variant needs, at offsets taken from the
+# debug info of each smol_str version, where a scheme reads the whole value.
+TaggedStringScheme = namedtuple(
+    "TaggedStringScheme", ["tag_offset", "inline_length", "pointer_variant"]
+)
+
+# CompactString tags stored in the last byte of its buffer
+COMPACT_STR_INLINE_TAG = 0xC0
+COMPACT_STR_HEAP_TAG = 0xD8
+COMPACT_STR_STATIC_TAG = 0xD9
+
+
+def _compact_str_inline_length(tag: int, size: int):
+    """
+    Inline length from the tag of a compact_str::CompactString
+
+    Below 0xC0 the string uses the whole buffer and the last byte is part
+    of the text, which works because the last byte of UTF-8 text is never
+    0xC0 or above. 0xC0 to 0xD7 is an inline string of length `tag - 0xC0`.
+    """
+    if tag < COMPACT_STR_INLINE_TAG:
+        return size
+    if tag < COMPACT_STR_HEAP_TAG:
+        return tag - COMPACT_STR_INLINE_TAG
+    return None
+
+
+# CompactString is three words; heap strings and &'static str keep their
+# pointer and length in the first two
+COMPACT_STR_SCHEME = TaggedStringScheme(
+    -1,
+    _compact_str_inline_length,
+    {COMPACT_STR_HEAP_TAG: "heap", COMPACT_STR_STATIC_TAG: "static"}.get,
+)
+
+# ecow::EcoString is a union of an inline buffer, whose last byte holds the
+# length with the top bit set, and an EcoVec<u8> { ptr, len }. ptr points
+# at the bytes themselves, the refcount header is in front of them.
+ECOW_LEN_TAG = 0x80
+
+ECOW_SCHEME = TaggedStringScheme(
+    -1,
+    lambda tag, size: tag & ~ECOW_LEN_TAG if tag & ECOW_LEN_TAG else None,
+    lambda tag: "heap",
+)
+
+
+def _tagged_string_repr(data: bytes, byteorder: str, word_size: int, scheme):
+    """
+    Decodes the raw bytes of a small string with a TaggedStringScheme
+
+    Inline strings start at the beginning of the value, strings behind a
+    pointer have the pointer in the first word and the length in the next.
+
+    Returns:
+        A (variant, pointer, length) tuple. pointer is None for inline
+        strings, whose text is data[:length]. None for unknown tags.
+    """
+    size = len(data)
+    if size == 0:
+        return None
+
+    tag = bytearray(data)[scheme.tag_offset]
+    length = scheme.inline_length(tag, size)
+    if length is not None:
+        if length > size:
+            return None
+        return "inline", None, length
+
+    variant = scheme.pointer_variant(tag)
+    if variant is None or size < 2 * word_size:
+        return None
+    pointer = int.from_bytes(data[:word_size], byteorder)
+    length = int.from_bytes(data[word_size : 2 * word_size], byteorder)
+    return variant, pointer, length
+
+
+def _tagged_string_text_from(reader: ValueReader, size: int, scheme):
+    """
+    Reads the text of a small string of size bytes, see _tagged_string_repr
+
+    Returns:
+        A (text, variant) tuple, (None, None) if the string can't be read
+    """
+    data = reader.read(0, size)
+    if data is None:
+        return None, None
+
+    decoded = _tagged_string_repr(data, reader.byteorder, reader.word_size, scheme)
+    if decoded is None:
+        return None, None
+
+    variant, pointer, length = decoded
+    if pointer is None:
+        return _decode_utf8(data[:length]), variant
+    return reader.read_string(pointer, length), variant
+
+
+def CompactStringSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for compact_str::CompactString
+
+    Args:
+        valobj: The CompactString value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation like "hello" with quotes
+    """
+    if _layout_mismatch(valobj, "compact_str::CompactString"):
+        return ""
+    text, variant = _compact_string_text(valobj)
+    if text is None:
+        return '""'
+
+    if get_setting("metadata"):
+        return '"%s" (%s)' % (text, variant)
+    return '"%s"' % text
+
+
+def _compact_string_text(valobj: SBValue):
+    """Reads the text of a CompactString value with _compact_string_text_from"""
+    return _compact_string_text_from(
+        ValueReader.for_value(valobj), valobj.GetNonSyntheticValue().GetByteSize()
+    )
+
+
+def _compact_string_text_from(reader: ValueReader, size: int):
+    """
+    Reads the text of a CompactString of size bytes, see COMPACT_STR_SCHEME
+
+    Returns:
+        A (text, variant) tuple, (None, None) if the string can't be read
+    """
+    return _tagged_string_text_from(reader, size, COMPACT_STR_SCHEME)
+
+
+def _eco_string_text(valobj: SBValue):
+    """Reads the text of an EcoString value, see ECOW_SCHEME"""
+    return _tagged_string_text_from(
+        ValueReader.for_value(valobj),
+        valobj.GetNonSyntheticValue().GetByteSize(),
+        ECOW_SCHEME,
+    )
+
+
+def EcoStringSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for ecow::EcoString
+
+    Args:
+        valobj: The EcoString value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation like "hello" with quotes
+    """
+    if _layout_mismatch(valobj, "ecow::string::EcoString"):
+        return ""
+    text, variant = _eco_string_text(valobj)
+    if text is None:
+        return '""'
+
+    if get_setting("metadata"):
+        return '"%s" (%s)' % (text, variant)
+    return '"%s"' % text
+
+
+def _string_key_text(key: SBValue):
+    """
+    Reads a SmolStr, CompactString or EcoString map key directly
+
+    Maps keyed by these strings are common, e.g. in parsers. Their keys
+    skip the formatter lookup and the metadata of the summary providers,
+    so every key is one length-first read and renders as plain `"text"`.
+
+    Returns:
+        The quoted key, or None for other types, for a disabled crate or
+        type, or a layout that doesn't match
+    """
+    if _is_disabled_type(key.GetType()):
+        return None
+    type_name = key.GetType().GetUnqualifiedType().GetName()
+    if type_name == "smol_str::SmolStr":
+        crate, read = "smol-str", _smolstr_text
+    elif type_name == "compact_str::CompactString":
+        crate, read = "compact-str", _compact_string_text
+    elif type_name == "ecow::string::EcoString":
+        crate, read = "ecow", _eco_string_text
+    else:
+        return None
+
+    if not (get_setting("enable-thirdparty") and get_setting("enable-" + crate)):
+        return None
+    if _layout_mismatch(key, type_name):
+        return None
+    text = read(key)[0]
+    return None if text is None else '"%s"' % text
+
+
+def _inline_slots(array: SBValue, element_size: int) -> int:
+    """Number of elements the inline array holds, counted by type for ZSTs"""
+    if element_size > 0:
+        return array.GetByteSize() // element_size
+    return array.GetNonSyntheticValue().GetNumChildren()
+
+
+def _inline_length(length: int, array: SBValue, element_size: int) -> int:
+    """
+    Bounds the length of a container backed by an inline array
+
+    Only the first `length` slots of the array are initialized, the others
+    are MaybeUninit (or stale) and never read. A length outside 0 to the
+    array's slot count comes from a corrupted value and is cut to that
+    range, so no read leaves the array; zero-sized elements occupy no bytes
+    but are bounded by the slot count all the same.
+    """
+    return min(max(length, 0), _inline_slots(array, element_size))
+
+
+def _assert_inline_span(array: SBValue, address: int, length: int, element_size: int):
+    """
+    Asserts that `length` elements at address lie within the inline array
+
+    The bound _inline_length establishes, checked again right before the
+    elements are rendered.
+    """
+    start = array.GetLoadAddress()
+    assert 0 <= length <= _inline_slots(array, element_size)
+    assert start <= address
+    assert address + length * element_size <= start + array.GetByteSize()
+
+
+def ArrayStringSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for arrayvec::ArrayString<N>
+
+    ArrayString { xs: [MaybeUninit<u8>; N], len: u32 } keeps its text in
+    the first `len` bytes of the inline buffer; the rest is uninitialized.
+
+    Args:
+        valobj: The ArrayString value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation like "hello" with quotes
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    xs = valobj.GetChildMemberWithName("xs")
+    length = valobj.GetChildMemberWithName("len")
+    if not xs.IsValid() or not length.IsValid():
+        return ""
+
+    length = _inline_length(length.GetValueAsUnsigned(), xs, 1)
+    _assert_inline_span(xs, xs.GetLoadAddress(), length, 1)
+    text = _read_string(valobj.GetProcess(), xs.GetLoadAddress(), length)
+    if text is None:
+        return '""'
+    return '"%s"' % text
+
+
+# MaybeUninit<T> is a union { uninit: (), value: ManuallyDrop<T> }
+MAYBE_UNINIT_PREFIX = "core::mem::maybe_uninit::MaybeUninit<"
+
+
+def _maybe_uninit_payload_type(sbtype):
+    """The T of a MaybeUninit<T>, other types are returned as they are"""
+    if not (sbtype.GetName() or "").startswith(MAYBE_UNINIT_PREFIX):
+        return sbtype
+    payload = sbtype.GetTemplateArgumentType(0)
+    if payload.IsValid():
+        return payload
+    # MaybeUninit -> ManuallyDrop -> T
+    for _ in range(2):
+        for i in range(sbtype.GetNumberOfFields()):
+            field = sbtype.GetFieldAtIndex(i)
+            if field.GetName() == "value":
+                sbtype = field.GetType()
+                break
+        else:
+            return None
+    return sbtype
+
+
+class ArrayVecSyntheticProvider:
+    """
+    Synthetic provider for arrayvec's ArrayVec<T, CAP> and tinyvec's
+    ArrayVec<[T; N]>
+
+    arrayvec keeps { len: u32, xs: [MaybeUninit<T>; CAP] }, tinyvec
+    { len: u16, data: [T; N] } with defaulted or stale values past `len`.
+    Only the first `len` slots are exposed, the tail is never read.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.length = 0
+        self.data_address = 0
+        self.element_size = 0
+        self.element_type = None
+        self.update()
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+        try:
+            return self.valobj.CreateValueFromAddress(
+                _index_name(index),
+                self.data_address + index * self.element_size,
+                self.element_type,
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.length = 0
+        self.data_address = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = _referent(self.valobj)
+            array = valobj.GetChildMemberWithName("xs")
+            if not array.IsValid():
+                array = valobj.GetChildMemberWithName("data")
+            length = valobj.GetChildMemberWithName("len")
+            if not array.IsValid() or not length.IsValid():
+                return
+
+            element_type = _maybe_uninit_payload_type(
+                array.GetType().GetArrayElementType()
+            )
+            if element_type is None or not element_type.IsValid():
+                return
+            self.element_type = element_type
+            self.element_size = element_type.GetByteSize()
+            self.data_address = array.GetLoadAddress()
+            if self.data_address in (0, lldb.LLDB_INVALID_ADDRESS):
+                return
+            self.length = _inline_length(
+                length.GetValueAsUnsigned(), array, self.element_size
+            )
+            _assert_inline_span(
+                array, self.data_address, self.length, self.element_size
+            )
+        except Exception:
+            self.length = 0
+
+    def has_children(self):
+        return self.length > 0
+
+
+def ArrayVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for arrayvec's ArrayVec<T, CAP> and tinyvec's
+    ArrayVec<[T; N]>
+
+    Args:
+        valobj: The ArrayVec to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The length and the elements like "size=2 [1, 2]"
+    """
+    suffix = _address_suffix(valobj)
+    provider = ArrayVecSyntheticProvider(valobj, _dict)
+    if provider.element_type is None:
+        return ""
+    bits = _bool_bits(provider, provider.data_address)
+    if bits is not None:
+        return "size=%d %s%s" % (provider.length, bits, suffix)
+    return "size=%d %s%s" % (provider.length, _preview(provider), suffix)
+
+
+define_setting(
+    "hexdump",
+    False,
+    "Show the bytes of byte strings that aren't valid UTF-8 as \\xNN escapes "
+    "instead of decoding them lossily",
+)
+
+
+def BStringSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for bstr's BString and &BStr
+
+    BString { bytes: Vec<u8> } owns its bytes, &BStr is a fat pointer to
+    them. The bytes are conventionally but not necessarily UTF-8; invalid
+    sequences are decoded lossily, or shown as `\\xNN` with the hexdump
+    setting: `"ok\\xffbytes"`.
+
+    Args:
+        valobj: The BString or &BStr value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The bytes as a quoted string, e.g. "hello"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    vec = valobj.GetChildMemberWithName("bytes")
+    if vec.IsValid():
+        provider = VecSyntheticProvider(vec, None)
+        address, length = provider.data_ptr, provider.length
+    else:
+        data_ptr = valobj.GetChildMemberWithName("data_ptr")
+        length_field = valobj.GetChildMemberWithName("length")
+        if not data_ptr.IsValid() or not length_field.IsValid():
+            return ""
+        address = data_ptr.GetValueAsUnsigned()
+        length = length_field.GetValueAsUnsigned()
+
+    if length == 0 or address == 0:
+        return '""'
+    data = _read_memory(valobj.GetProcess(), address, length)
+    if data is None:
+        return '""'
+    if get_setting("hexdump") and PY3:
+        return '"%s"' % data.decode("utf-8", "backslashreplace")
+    return '"%s"' % _decode_utf8(data)
+
+
+# Head bit index bits kept in the low bits of a BitSpan's len
+BITSPAN_LEN_HEAD_BITS = 3
+
+
+class BitVecSyntheticProvider:
+    """
+    Synthetic provider for bitvec's BitVec<T, O> and &BitSlice<T, O>
+
+    Both are a BitSpan, the `bitspan` field of a BitVec and the fat pointer
+    of a &BitSlice. It encodes the bit index of the first bit in the first
+    element, the head, in its low bits: `len` is `bits << 3 | head & 7`,
+    and for elements wider than a byte the pointer's alignment bits hold
+    the rest of the head. Bit i is bit head + i of the elements, counted
+    from the least significant bit of each element with Lsb0 and from the
+    most significant one with Msb0.
+
+    Each bit is exposed as a bool child, `[0]`, `[1]`, ...
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.length = 0
+        self.address = 0
+        self.head = 0
+        self.element_size = 0
+        self.msb0 = False
+        self.update()
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+        bits = self.read_bits(index, 1)
+        if bits is None:
+            return None
+        target = self.valobj.GetTarget()
+        data = lldb.SBData.CreateDataFromUInt8Array(
+            target.GetByteOrder(), target.GetAddressByteSize(), bits
+        )
+        return self.valobj.CreateValueFromData(
+            _index_name(index), data, target.GetBasicType(lldb.eBasicTypeBool)
+        )
+
+    def read_bits(self, start: int, count: int):
+        """Bits start to start + count as a list of 0 and 1, None if unreadable"""
+        width = 8 * self.element_size
+        first = (self.head + start) // width
+        last = (self.head + start + count - 1) // width
+        data = _read_memory(
+            self.valobj.GetProcess(),
+            self.address + first * self.element_size,
+            (last - first + 1) * self.element_size,
+        )
+        if data is None:
+            return None
+
+        big_endian = self.valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig
+        elements = [
+            int.from_bytes(
+                data[i : i + self.element_size], "big" if big_endian else "little"
+            )
+            for i in range(0, len(data), self.element_size)
+        ]
+        bits = []
+        for position in range(self.head + start, self.head + start + count):
+            element = elements[position // width - first]
+            shift = position % width
+            if self.msb0:
+                shift = width - 1 - shift
+            bits.append((element >> shift) & 1)
+        return bits
+
+    def update(self):
+        self.length = 0
+        self.address = 0
+        self.head = 0
+
+        try:
+            valobj = self.valobj.GetNonSyntheticValue()
+            sbtype = valobj.GetType()
+            if sbtype.IsPointerType() or sbtype.IsReferenceType():
+                sbtype = sbtype.GetPointeeType()
+            span = valobj.GetChildMemberWithName("bitspan")
+            if span.IsValid():
+                pointer = _unwrap_pointer(span.GetChildMemberWithName("ptr"))
+                encoded_len = span.GetChildMemberWithName("len").GetValueAsUnsigned()
+            else:
+                pointer = valobj.GetChildMemberWithName("data_ptr").GetValueAsUnsigned()
+                length = valobj.GetChildMemberWithName("length")
+                encoded_len = length.GetValueAsUnsigned()
+
+            element_type = sbtype.GetTemplateArgumentType(0)
+            self.element_size = element_type.GetByteSize()
+            if self.element_size not in (1, 2, 4, 8) or pointer == 0:
+                return
+            self.msb0 = "Msb0" in (sbtype.GetName() or "")
+
+            pointer_head_mask = self.element_size - 1
+            head_mask = (1 << BITSPAN_LEN_HEAD_BITS) - 1
+            self.head = ((pointer & pointer_head_mask) << BITSPAN_LEN_HEAD_BITS) | (
+                encoded_len & head_mask
+            )
+            self.address = pointer & ~pointer_head_mask
+            self.length = encoded_len >> BITSPAN_LEN_HEAD_BITS
+        except Exception:
+            self.length = 0
+
+    def has_children(self):
+        return self.length > 0
+
+
+def BitVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for bitvec's BitVec<T, O> and &BitSlice<T, O>
+
+    Args:
+        valobj: The BitVec or bit slice to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The bit count and the bits in index order, as many as _preview_budget
+        leaves room for: `size=5 0b10110`
+    """
+    provider = BitVecSyntheticProvider(valobj, _dict)
+    if provider.element_size == 0:
+        return ""
+    # A &BitSlice is a fat pointer, its address is the one of the elements
+    address = None
+    if not valobj.GetNonSyntheticValue().GetChildMemberWithName("bitspan").IsValid():
+        address = provider.address
+    suffix = _address_suffix(valobj, address)
+
+    if provider.length == 0:
+        return "size=0" + suffix
+    count = min(provider.length, _preview_budget())
+    bits = provider.read_bits(0, count) if count > 0 else []
+    if bits is None:
+        return "size=%d%s" % (provider.length, suffix)
+    text = "".join(str(bit) for bit in bits)
+    if count < provider.length:
+        text += "..."
+    return "size=%d 0b%s%s" % (provider.length, text, suffix)
+
+
+def _smallvec_tagged_len(len_value: int, element_size: int):
+    """
+    Splits SmallVec's tagged len field into (length, is_heap)
+
+    For zero-sized element types the len field is stored untagged, such a
+    SmallVec never spills.
+    """
+    if element_size == 0:
+        return len_value, False
+    return len_value >> 1, (len_value & 1) == 1
+
+
+def _smallvec_element_type(valobj: SBValue):
+    """
+    The T of a SmallVec<T, N>
+
+    Taken from the template arguments, or if LLDB doesn't list them from
+    the inline array `raw.inline.value.value.value` of [T; N], which the
+    union describes in either state. Elements of any type are read as
+    values of this type, so strings and structs get their own formatting.
+    """
+    element_type = valobj.GetType().GetTemplateArgumentType(0)
+    if element_type.IsValid():
+        return element_type
+    inline = valobj.GetChildMemberWithName("raw").GetChildMemberWithName("inline")
+    for name in ("value", "value", "value"):
+        inline = inline.GetChildMemberWithName(name)
+    return inline.GetType().GetArrayElementType()
+
+
+def SmallVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for smallvec::SmallVec<T, N>
+
+    SmallVec stores up to N elements inline, larger collections on heap.
+    Uses a discriminant bit in the len field:
+    - len & 1 == 0: inline storage
+    - len & 1 == 1: heap storage
+    - Actual length is len >> 1
+
+    SmallVec<T, 0> has no inline slots, so any element lives on the heap.
+    The metadata setting adds where the elements are, `(inline)` or
+    `(heap)` once spilled, like the inline string types.
+
+    Args:
+        valobj: The SmallVec value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation showing size and elements like "size=2 [1, 2]"
+    """
+    suffix = _address_suffix(valobj)
+    valobj = _referent(valobj)
+
+    # Get the len field
+    len_field = valobj.GetChildMemberWithName("len")
+    if not len_field.IsValid():
+        return "size=?"
+
+    len_inner = len_field.GetChildMemberWithName("__0")
+    if not len_inner.IsValid():
+        return "size=?"
+
+    len_value = len_inner.GetValueAsUnsigned()
+
+    element_type = _smallvec_element_type(valobj)
+    element_size = element_type.GetByteSize() if element_type.IsValid() else 1
+    actual_length, is_heap = _smallvec_tagged_len(len_value, element_size)
+    if get_setting("metadata"):
+        suffix = (" (heap)" if is_heap else " (inline)") + suffix
+
+    provider = SmallVecSyntheticProvider(valobj, _dict)
+    bits = _bool_bits(
+        provider,
+        provider.heap_ptr if provider.is_heap else provider.inline_data_address,
+    )
+    if bits is not None:
+        return "size=%d %s%s" % (actual_length, bits, suffix)
+    return "size=%d %s%s" % (actual_length, _preview(provider), suffix)
+
+
+class SmallVecSyntheticProvider:
+    """
+    Synthetic provider for smallvec::SmallVec<T, N>
+
+    Provides access to individual elements as children in the debugger.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.length = 0
+        self.is_heap = False
+        self.heap_ptr = 0
+        self.inline_data_address = 0
+        self.element_size = 0
+        self.element_type = None
+        self.update()
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+
+        if not self.element_type or not self.element_type.IsValid():
+            return None
+
+        try:
+            if self.is_heap:
+                # Read from heap - calculate address and create value
+                if self.heap_ptr == 0:
+                    return None
+                address = self.heap_ptr + index * self.element_size
+                element = self.valobj.CreateValueFromAddress(
+                    _index_name(index), address, self.element_type
+                )
+                return element
+            else:
+                # Read from inline storage - calculate address from inline buffer
+                if self.inline_data_address == 0:
+                    return None
+                address = self.inline_data_address + index * self.element_size
+                element = self.valobj.CreateValueFromAddress(
+                    _index_name(index), address, self.element_type
+                )
+                return element
+
+        except Exception:
+            return None
+
+    def update(self):
+        self.length = 0
+        self.is_heap = False
+        self.heap_ptr = 0
+        self.inline_data_address = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = _referent(self.valobj)
+
+            # Get the len field
+            len_field = valobj.GetChildMemberWithName("len")
+            if not len_field.IsValid():
+                return
+
+            len_inner = len_field.GetChildMemberWithName("__0")
+            if not len_inner.IsValid():
+                return
+
+            len_value = len_inner.GetValueAsUnsigned()
+
+            self.element_type = _smallvec_element_type(valobj)
+            if not self.element_type.IsValid():
+                return
+
+            self.element_size = self.element_type.GetByteSize()
+
+            # Check discriminant bit (bit 0) and extract actual length (len >> 1)
+            self.length, self.is_heap = _smallvec_tagged_len(
+                len_value, self.element_size
+            )
+
+            # Get raw union
+            raw = valobj.GetChildMemberWithName("raw")
+            if not raw.IsValid():
+                self.length = 0
+                return
+
+            if self.is_heap:
+                # Get heap pointer address
+                heap = raw.GetChildMemberWithName("heap")
+                if not heap.IsValid():
+                    self.length = 0
+                    return
+
+                ptr_wrapper = heap.GetChildMemberWithName("__0")
+                if not ptr_wrapper.IsValid():
+                    self.length = 0
+                    return
+
+                pointer = ptr_wrapper.GetChildMemberWithName("pointer")
+                if not pointer.IsValid():
+                    self.length = 0
+                    return
+
+                self.heap_ptr = pointer.GetValueAsUnsigned()
+                if self.heap_ptr == 0:
+                    self.length = 0
+                    return
+
+                # The allocation holds the capacity, no more
+                capacity = heap.GetChildMemberWithName("__1")
+                if capacity.IsValid():
+                    self.length = min(self.length, capacity.GetValueAsUnsigned())
+            else:
+                # Get inline storage address
+                # Navigate to the inline array: raw.inline.value.value.value
+                inline = raw.GetChildMemberWithName("inline")
+                if not inline.IsValid():
+                    self.length = 0
+                    return
+
+                value1 = inline.GetChildMemberWithName("value")
+                if not value1.IsValid():
+                    self.length = 0
+                    return
+
+                value2 = value1.GetChildMemberWithName("value")
+                if not value2.IsValid():
+                    self.length = 0
+                    return
+
+                value_array = value2.GetChildMemberWithName("value")
+                if not value_array.IsValid():
+                    self.length = 0
+                    return
+
+                # Get the address of the inline array
+                self.inline_data_address = value_array.GetLoadAddress()
+                if self.inline_data_address == 0:
+                    self.length = 0
+                    return
+
+                # Never read past the N inline slots. With N=0 (or a
+                # corrupted len) nothing valid can be stored inline.
+                self.length = _inline_length(
+                    self.length, value_array, self.element_size
+                )
+                _assert_inline_span(
+                    value_array,
+                    self.inline_data_address,
+                    self.length,
+                    self.element_size,
+                )
+
+        except Exception as e:
+            self.length = 0
+            self.is_heap = False
+            self.heap_ptr = 0
+            self.inline_data_address = 0
+
+    def has_children(self):
+        return self.length > 0
+
+
+class VecSyntheticProvider:
+    """
+    Synthetic provider for alloc::vec::Vec<T>
+
+    Exposes the first `len` elements of the buffer as indexed children.
+    Buggy unsafe code can set a `len` beyond the capacity; then only the
+    `cap` allocated elements are read, and `declared_length` keeps `len`.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.length = 0
+        self.declared_length = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+        self.update()
+
+    def num_children(self):
+        page_size = _page_size(self.length)
+        if page_size:
+            return (self.length + page_size - 1) // page_size
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        match = re.match(r"^\[page (\d+)\.\.\d+\]$", name)
+        if match:
+            page_size = _page_size(self.length)
+            return int(match.group(1)) // page_size if page_size else -1
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        page_size = _page_size(self.length)
+        if page_size:
+            return self.page_at(index, page_size)
+        return self.element_at(index)
+
+    def page_at(self, index: int, page_size: int):
+        """
+        The page child holding elements index * page_size onwards
+
+        A page is an array value over that part of the buffer, expanding it
+        reads only its own elements. Its children are numbered from 0, the
+        page's name tells where it starts.
+        """
+        start = index * page_size
+        if index < 0 or start >= self.length or self.element_size <= 0:
+            return None
+        end = min(start + page_size, self.length)
+        try:
+            return self.valobj.CreateValueFromAddress(
+                "[page %d..%d]" % (start, end),
+                self.data_ptr + start * self.element_size,
+                self.element_type.GetArrayType(end - start),
+            )
+        except Exception:
+            return None
+
+    def element_at(self, index: int):
+        """The element at index, whether or not the children are paged"""
+        if index < 0 or index >= self.length:
+            return None
+
+        if not self.element_type or not self.element_type.IsValid():
+            return None
+
+        try:
+            address = self.data_ptr + index * self.element_size
+            return self.valobj.CreateValueFromAddress(
+                _index_name(index), address, self.element_type
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.length = 0
+        self.declared_length = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = _referent(self.valobj)
+
+            len_field = valobj.GetChildMemberWithName("len")
+            if not len_field.IsValid():
+                return
+
+            # Vec { buf: RawVec { inner: RawVecInner { ptr, cap }, .. }, len }
+            # Before Rust 1.83 the pointer lived directly in RawVec
+            buf = valobj.GetChildMemberWithName("buf")
+            inner = buf.GetChildMemberWithName("inner")
+            if inner.IsValid():
+                buf = inner
+
+            self.data_ptr = _unwrap_pointer(buf.GetChildMemberWithName("ptr"))
+            if self.data_ptr == 0:
+                return
+
+            # Get element type from Vec<T, A> template argument
+            self.element_type = valobj.GetType().GetTemplateArgumentType(0)
+            if not self.element_type.IsValid():
+                return
+
+            self.element_size = self.element_type.GetByteSize()
+            self.length = len_field.GetValueAsUnsigned()
+            self.declared_length = self.length
+
+            # Zero-sized elements store no capacity
+            cap = _innermost(buf.GetChildMemberWithName("cap"))
+            if self.element_size > 0 and cap.IsValid():
+                self.length = min(self.length, cap.GetValueAsUnsigned())
+
+        except Exception:
+            self.length = 0
+            self.declared_length = 0
+            self.data_ptr = 0
+            self.element_size = 0
+            self.element_type = None
+
+    def has_children(self):
+        return self.length > 0
+
+
+def VecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::vec::Vec<T>
+
+    Args:
+        valobj: The Vec value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation showing size and elements like "size=3 [1, 2, 3]",
+        or "size=9 (len>cap, truncated) [1, 2, 3]" for a len beyond the capacity
+    """
+    provider = VecSyntheticProvider(valobj, _dict)
+    size = "size=%d" % provider.declared_length
+    if provider.declared_length > provider.length:
+        size += " (len>cap, truncated)"
+    suffix = _address_suffix(valobj)
+    bits = _bool_bits(provider, provider.data_ptr)
+    if bits is not None:
+        return "%s %s%s" % (size, bits, suffix)
+    return "%s %s%s" % (size, _preview(provider), suffix)
+
+
+class VecDequeSyntheticProvider:
+    """
+    Synthetic provider for alloc::collections::VecDeque<T>
+
+    The deque is a ring buffer: `len` elements starting at slot `head`,
+    wrapping around at the capacity. Toolchains before Rust 1.67 stored
+    `tail` and `head` slots instead, with the elements in between. Like
+    for Vec, a `len` beyond the capacity is clamped to the `cap` allocated
+    slots, and `declared_length` keeps `len`.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.length = 0
+        self.declared_length = 0
+        self.head = 0
+        self.capacity = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+        self.update()
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+
+        try:
+            slot = (self.head + index) % self.capacity
+            return self.valobj.CreateValueFromAddress(
+                _index_name(index),
+                self.data_ptr + slot * self.element_size,
+                self.element_type,
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.length = 0
+        self.declared_length = 0
+        self.head = 0
+        self.capacity = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = _referent(self.valobj)
+            self.element_type = valobj.GetType().GetTemplateArgumentType(0)
+            if not self.element_type.IsValid():
+                return
+
+            buf = valobj.GetChildMemberWithName("buf")
+            inner = buf.GetChildMemberWithName("inner")
+            if inner.IsValid():
+                buf = inner
+            cap = buf.GetChildMemberWithName("cap")
+            # Newer toolchains wrap the capacity in a niche type
+            if cap.GetNumChildren() > 0:
+                cap = cap.GetChildAtIndex(0)
+
+            data_ptr = _unwrap_pointer(buf.GetChildMemberWithName("ptr"))
+            capacity = cap.GetValueAsUnsigned()
+            if data_ptr == 0 or capacity == 0:
+                return
+
+            head = valobj.GetChildMemberWithName("head").GetValueAsUnsigned()
+            len_field = valobj.GetChildMemberWithName("len")
+            if len_field.IsValid():
+                length = len_field.GetValueAsUnsigned()
+            else:
+                tail = valobj.GetChildMemberWithName("tail").GetValueAsUnsigned()
+                length = (head - tail) & (capacity - 1)
+                head = tail
+
+            self.data_ptr = data_ptr
+            self.capacity = capacity
+            self.head = head
+            self.element_size = self.element_type.GetByteSize()
+            self.declared_length = length
+            self.length = min(length, capacity)
+        except Exception:
+            self.length = 0
+            self.declared_length = 0
+
+    def has_children(self):
+        return self.length > 0
+
+
+def VecDequeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::collections::VecDeque<T>
+
+    Args:
+        valobj: The VecDeque value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and the elements front to back, e.g. "size=3 [1, 2, 3]",
+        or "size=9 (len>cap, truncated) [1, 2, 3]" for a len beyond the capacity
+    """
+    provider = VecDequeSyntheticProvider(valobj, _dict)
+    size = "size=%d" % provider.declared_length
+    if provider.declared_length > provider.length:
+        size += " (len>cap, truncated)"
+    return "%s %s%s" % (size, _preview(provider), _address_suffix(valobj))
+
+
+# The element type inside the brackets of Box<[T]>, &[T] and &mut [T]. It
+# must not end in `; N`, those are the thin pointers to arrays [T; N]. LLDB
+# matches with POSIX extended regexes, so this can't use a lookahead.
+_SLICE_ELEMENT = r"\[.*([^0-9]|[^ 0-9][0-9]+|[^;] [0-9]+)\]"
+
+SLICE_TYPE_PATTERN = (
+    r"^(alloc::boxed::Box<" + _SLICE_ELEMENT + r"(, [^,]+)?>"
+    r"|&(mut )?" + _SLICE_ELEMENT + r")$"
+)
+
+
+class SliceSyntheticProvider(VecSyntheticProvider):
+    """
+    Synthetic provider for slices behind fat pointers: Box<[T]>, &[T]
+
+    The fat pointer carries the element count as its `length` metadata
+    next to `data_ptr`; children are the elements like those of a Vec.
+    """
+
+    def update(self):
+        self.length = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = self.valobj.GetNonSyntheticValue()
+            data_ptr = valobj.GetChildMemberWithName("data_ptr")
+            length = valobj.GetChildMemberWithName("length")
+            if not data_ptr.IsValid() or not length.IsValid():
+                return
+
+            self.element_type = data_ptr.GetType().GetPointeeType()
+            if not self.element_type.IsValid():
+                return
+
+            self.data_ptr = data_ptr.GetValueAsUnsigned()
+            self.element_size = self.element_type.GetByteSize()
+            self.length = length.GetValueAsUnsigned()
+            if self.data_ptr == 0:
                 self.length = 0
+        except Exception:
+            self.length = 0
+            self.data_ptr = 0
+            self.element_size = 0
+            self.element_type = None
+
+
+def SliceSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for Box<[T]> and &[T]
+
+    Args:
+        valobj: The boxed slice or slice reference to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation showing size and elements like "size=3 [1, 2, 3]",
+        followed by `(slice)` or `(boxed slice)` with the metadata setting
+    """
+    provider = SliceSyntheticProvider(valobj, _dict)
+    suffix = _address_suffix(valobj, provider.data_ptr)
+    if get_setting("metadata"):
+        boxed = (valobj.GetTypeName() or "").startswith("alloc::boxed::Box<")
+        suffix = (" (boxed slice)" if boxed else " (slice)") + suffix
+    bits = _bool_bits(provider, provider.data_ptr)
+    if bits is not None:
+        return "size=%d %s%s" % (provider.length, bits, suffix)
+    return "size=%d %s%s" % (provider.length, _preview(provider), suffix)
+
+
+# Fixed size arrays [T; N], also nested ones like [[i32; 2]; 3]
+ARRAY_TYPE_PATTERN = r"^\[.+; [0-9]+\]$"
+
+
+class ArraySyntheticProvider(VecSyntheticProvider):
+    """
+    Synthetic provider for fixed size arrays [T; N]
+
+    The elements are read from the array's own address like those of a
+    Vec, so max-children and page-size apply to a [u8; 4096] as well.
+    Arrays that live in no memory, such as values built from register
+    data, keep LLDB's own children.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.raw = None
+        super().__init__(valobj, _dict)
+
+    def num_children(self):
+        if self.raw is not None:
+            return _max_children(self.valobj, self.raw.GetNumChildren())
+        return super().num_children()
+
+    def get_child_at_index(self, index: int):
+        if self.raw is not None:
+            return self.element_at(index)
+        return super().get_child_at_index(index)
+
+    def element_at(self, index: int):
+        if self.raw is not None:
+            return self.raw.GetChildAtIndex(index) if index < self.length else None
+        return super().element_at(index)
+
+    def update(self):
+        self.length = 0
+        self.declared_length = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+        self.raw = None
+
+        try:
+            valobj = self.valobj.GetNonSyntheticValue()
+            self.element_type = valobj.GetType().GetArrayElementType()
+            if not self.element_type.IsValid():
+                return
+            self.element_size = self.element_type.GetByteSize()
+            self.length = valobj.GetNumChildren()
+            self.declared_length = self.length
+
+            address = valobj.GetLoadAddress()
+            if address in (0, lldb.LLDB_INVALID_ADDRESS):
+                self.raw = valobj
+            else:
+                self.data_ptr = address
+        except Exception:
+            self.length = 0
+            self.declared_length = 0
+            self.raw = None
+
+
+def ArraySummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for fixed size arrays [T; N]
+
+    Previews the elements within the preview budget like a Vec, so a
+    [u8; 1024] lists its first elements rather than all of them; expanding
+    the array still shows every element.
+
+    Args:
+        valobj: The array to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The length and the first elements like "size=1024 [0, 1, 2, ...]"
+    """
+    provider = ArraySyntheticProvider(valobj, _dict)
+    if provider.element_type is None:
+        return ""
+    if provider.raw is None:
+        bits = _bool_bits(provider, provider.data_ptr)
+        if bits is not None:
+            return "size=%d %s" % (provider.length, bits)
+    return "size=%d %s" % (provider.length, _preview(provider))
+
+
+def StringSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::string::String
+
+    String wraps a Vec<u8> holding the UTF-8 bytes.
+
+    Args:
+        valobj: The String value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The string content in quotes, e.g. "hello"
+    """
+    vec = _referent(valobj).GetChildMemberWithName("vec")
+    if not vec.IsValid():
+        return '""'
+
+    provider = VecSyntheticProvider(vec, None)
+    data = _read_string(valobj.GetProcess(), provider.data_ptr, provider.length)
+    return '"%s"%s' % (data or "", _address_suffix(valobj))
+
+
+# Box<str> is the same fat pointer as &str, the allocator parameter is only
+# spelled out by newer compilers
+STR_TYPE_PATTERN = r"^(&(mut )?str|alloc::boxed::Box<str(, [^,]+)?>)$"
+
+
+def StrSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for &str and Box<str>
+
+    Both are fat pointers made of data_ptr and length. The bytes are not
+    nul-terminated and often point into the middle of a larger string, so
+    exactly `length` bytes are read.
+
+    Args:
+        valobj: The &str or Box<str> value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The string content in quotes, e.g. "hello"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+
+    data_ptr = valobj.GetChildMemberWithName("data_ptr")
+    length_field = valobj.GetChildMemberWithName("length")
+    if not data_ptr.IsValid() or not length_field.IsValid():
+        return '""'
+
+    data = _read_string(
+        valobj.GetProcess(),
+        data_ptr.GetValueAsUnsigned(),
+        length_field.GetValueAsUnsigned(),
+    )
+    return '"%s"%s' % (
+        data or "",
+        _address_suffix(valobj, data_ptr.GetValueAsUnsigned()),
+    )
+
+
+def _indexmap_entries(valobj: SBValue):
+    """
+    The entries Vec of an indexmap::IndexMap or IndexSet
+
+    Returns:
+        The Vec<Bucket<K, V>> value, or None if the layout isn't recognized
+    """
+    valobj = _referent(valobj)
+    # IndexSet<T> wraps an IndexMap<T, ()>
+    map_value = valobj.GetChildMemberWithName("map")
+    if map_value.IsValid():
+        valobj = map_value
+    core = valobj.GetChildMemberWithName("core")
+    entries = core.GetChildMemberWithName("entries")
+    return entries if entries.IsValid() else None
+
+
+class IndexSetSyntheticProvider:
+    """
+    Synthetic provider for indexmap::set::IndexSet<T>
+
+    The set keeps its values in a Vec of buckets in insertion order, next
+    to a hash table of indices into that Vec. Only the Vec is read, the
+    children are the values in insertion order.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.entries = None
+        self.update()
+
+    @property
+    def length(self):
+        return self.entries.length if self.entries is not None else 0
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if self.entries is None:
+            return None
+        bucket = self.entries.element_at(index)
+        if bucket is None:
+            return None
+
+        # Bucket { hash, key, value }, the set's values are the keys
+        key = bucket.GetChildMemberWithName("key")
+        if not key.IsValid():
+            return None
+        return self.valobj.CreateValueFromAddress(
+            _index_name(index), key.GetLoadAddress(), key.GetType()
+        )
+
+    def update(self):
+        self.entries = None
+
+        try:
+            entries = _indexmap_entries(self.valobj)
+            if entries is not None:
+                self.entries = VecSyntheticProvider(entries, None)
+        except Exception:
+            self.entries = None
+
+    def has_children(self):
+        return self.num_children() > 0
+
+
+def IndexSetSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for indexmap::set::IndexSet<T>
+
+    Args:
+        valobj: The IndexSet value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and the values in insertion order, e.g. "size=3 [30, 10, 20]"
+    """
+    provider = IndexSetSyntheticProvider(valobj, _dict)
+    return "size=%d %s%s" % (
+        provider.length,
+        _preview(provider),
+        _address_suffix(valobj),
+    )
+
+
+class IndexMapSyntheticProvider:
+    """
+    Synthetic provider for indexmap::map::IndexMap<K, V>
+
+    Like IndexSetSyntheticProvider, only the Vec of buckets is read. Each
+    value is exposed as a child named after its key, in insertion order.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.entries = None
+        self.update()
+
+    @property
+    def length(self):
+        return self.entries.length if self.entries is not None else 0
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _keyed_child_index(self, self.length, name)
+
+    def _bucket_field(self, index: int, name: str):
+        if self.entries is None:
+            return None
+        bucket = self.entries.element_at(index)
+        if bucket is None:
+            return None
+        field = bucket.GetChildMemberWithName(name)
+        return field if field.IsValid() else None
+
+    def get_child_key(self, index: int) -> str:
+        key = self._bucket_field(index, "key")
+        return _key_label(key) if key is not None else "{...}"
+
+    def get_child_at_index(self, index: int):
+        value = self._bucket_field(index, "value")
+        if value is None:
+            return None
+        return self.valobj.CreateValueFromAddress(
+            "[%s]" % self.get_child_key(index), value.GetLoadAddress(), value.GetType()
+        )
+
+    def update(self):
+        self.entries = None
+
+        try:
+            entries = _indexmap_entries(self.valobj)
+            if entries is not None:
+                self.entries = VecSyntheticProvider(entries, None)
+        except Exception:
+            self.entries = None
+
+    def has_children(self):
+        return self.length > 0
+
+
+def IndexMapSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for indexmap::map::IndexMap<K, V>
+
+    Args:
+        valobj: The IndexMap value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and the entries in insertion order, e.g. `size=1 {"a": 1}`
+    """
+    provider = IndexMapSyntheticProvider(valobj, _dict)
+    return "size=%d %s%s" % (
+        provider.length,
+        _preview(provider, "map"),
+        _address_suffix(valobj),
+    )
+
+
+def _iterator_span(valobj: SBValue, element_type):
+    """
+    Number of elements between the `ptr` and `end` of a slice style iterator
+
+    Newer toolchains name the end field `end_or_len`: for zero-sized
+    element types it holds the remaining count instead of a pointer.
+
+    Returns:
+        The remaining element count, or None if the fields aren't found
+    """
+    ptr = _unwrap_pointer(valobj.GetChildMemberWithName("ptr"))
+    end = valobj.GetChildMemberWithName("end_or_len")
+    counted = end.IsValid()
+    if not counted:
+        end = valobj.GetChildMemberWithName("end")
+    if not end.IsValid() or not element_type.IsValid():
+        return None
+
+    end = end.GetValueAsUnsigned()
+    size = element_type.GetByteSize()
+    if size == 0:
+        return end if counted else end - ptr
+    if end < ptr:
+        return None
+    return (end - ptr) // size
+
+
+def SliceIterSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::slice::Iter<T> and IterMut<T>
+
+    Args:
+        valobj: The iterator to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The elements left, e.g. `Iter { remaining=3 }`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    name = _strip_generics(valobj.GetType().GetUnqualifiedType().GetName() or "")
+    remaining = _iterator_span(valobj, valobj.GetType().GetTemplateArgumentType(0))
+    if remaining is None:
+        return ""
+    return _braced(name.rsplit("::", 1)[-1], "remaining=%d" % remaining)
+
+
+def VecIntoIterSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::vec::IntoIter<T>
+
+    The iterator keeps the Vec's buffer in `buf`, so besides the elements
+    left this also shows the position of the next one in the buffer.
+
+    Args:
+        valobj: The iterator to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        Position and elements left, e.g. `IntoIter { position=1, remaining=3 }`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    element_type = valobj.GetType().GetTemplateArgumentType(0)
+    remaining = _iterator_span(valobj, element_type)
+    if remaining is None:
+        return ""
+
+    buf = _unwrap_pointer(valobj.GetChildMemberWithName("buf"))
+    ptr = _unwrap_pointer(valobj.GetChildMemberWithName("ptr"))
+    size = element_type.GetByteSize()
+    if buf == 0 or size == 0 or ptr < buf:
+        return _braced("IntoIter", "remaining=%d" % remaining)
+    return _braced(
+        "IntoIter",
+        _join(["position=%d" % ((ptr - buf) // size), "remaining=%d" % remaining]),
+    )
+
+
+def _find_member(valobj: SBValue, name: str, depth: int = 4):
+    """Finds a field by name in valobj or the fields nested in it"""
+    member = valobj.GetChildMemberWithName(name)
+    if member.IsValid() or depth == 0:
+        return member
+    for i in range(valobj.GetNumChildren()):
+        member = _find_member(valobj.GetChildAtIndex(i), name, depth - 1)
+        if member.IsValid():
+            return member
+    return lldb.SBValue()
+
+
+def HashMapIterSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for the std::collections::hash_map iterators
+
+    They wrap hashbrown's RawIter, whose `items` field counts the entries
+    still to be yielded.
+
+    Args:
+        valobj: The iterator to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The entries left, e.g. `Iter { remaining=2 }`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    items = _find_member(valobj, "items")
+    if not items.IsValid():
+        return ""
+    name = _strip_generics(valobj.GetType().GetUnqualifiedType().GetName() or "")
+    return _braced(
+        name.rsplit("::", 1)[-1], "remaining=%d" % items.GetValueAsUnsigned()
+    )
+
+
+def _keyed_child_index(provider, count: int, name: str) -> int:
+    """
+    Index of the child of a map provider named `[key]`, or -1
+
+    Compares the labels from get_child_key, the children themselves
+    needn't be built.
+    """
+    for i in range(count):
+        if "[%s]" % provider.get_child_key(i) == name:
+            return i
+    return -1
+
+
+# Keys and values per B-tree node, 2 * B - 1 with the B = 6 of std
+BTREE_CAPACITY = 11
+
+
+class BTreeMapSyntheticProvider:
+    """
+    Synthetic provider for alloc::collections::btree::map::BTreeMap<K, V>
+
+    The map is a tree of nodes; each node holds up to BTREE_CAPACITY keys
+    and values in separate arrays, internal nodes also their child edges.
+    The tree is walked in key order and each value is exposed as a child
+    named after its key, e.g. `[5]`.
+
+    Field offsets come from the LeafNode and InternalNode types, so keys
+    and values with padding or a large size are read at the right place.
+    The walk stops when format-budget-ms runs out, setting `truncated`.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.entries = []
+        self.truncated = False
+        self.key_type = None
+        self.value_type = None
+        self.update()
+
+    @property
+    def length(self):
+        return len(self.entries)
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _keyed_child_index(self, len(self.entries), name)
+
+    def get_child_key(self, index: int) -> str:
+        key = self.valobj.CreateValueFromAddress(
+            "key", self.entries[index][0], self.key_type
+        )
+        return _key_label(key)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= len(self.entries):
+            return None
+        try:
+            return self.valobj.CreateValueFromAddress(
+                "[%s]" % self.get_child_key(index),
+                self.entries[index][1],
+                self.value_type,
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.entries = []
+        self.truncated = False
+
+        started = _budget_begin()
+        try:
+            valobj = _referent(self.valobj)
+            self.key_type = valobj.GetType().GetTemplateArgumentType(0)
+            self.value_type = valobj.GetType().GetTemplateArgumentType(1)
+            length = valobj.GetChildMemberWithName("length").GetValueAsUnsigned()
+
+            name, root = _option_payload(valobj.GetChildMemberWithName("root"))
+            if name != "Some" or length == 0:
+                return
+
+            height = root.GetChildMemberWithName("height").GetValueAsUnsigned()
+            node = root.GetChildMemberWithName("node")
+            leaf_type = node.GetChildMemberWithName("pointer").GetType()
+            leaf_type = leaf_type.GetPointeeType()
+
+            self._walk(valobj, leaf_type, _unwrap_pointer(node), height, length)
+        except Exception:
+            self.entries = []
+        finally:
+            _budget_end(started)
+
+    def _walk(self, valobj: SBValue, leaf_type, root: int, height: int, length):
+        len_field = _type_field_offset(leaf_type, ("len",))
+        keys = _type_field_offset(leaf_type, ("keys",))
+        vals = _type_field_offset(leaf_type, ("vals",))
+        if len_field is None or keys is None or vals is None:
+            return
+
+        # InternalNode starts with the LeafNode, followed by the edges
+        target = valobj.GetTarget()
+        internal_type = target.FindFirstType(
+            (leaf_type.GetName() or "").replace("LeafNode", "InternalNode", 1)
+        )
+        edges = _type_field_offset(internal_type, ("edges",))
+        edges_offset = edges[0] if edges else leaf_type.GetByteSize()
+
+        process = valobj.GetProcess()
+        byteorder = "big" if process.GetByteOrder() == lldb.eByteOrderBig else "little"
+        pointer_size = _usize_size(valobj)
+        key_size = self.key_type.GetByteSize()
+        value_size = self.value_type.GetByteSize()
+
+        def walk(address: int, height: int):
+            if address == 0 or len(self.entries) >= length or self.truncated:
+                return
+            if _budget_exceeded():
+                self.truncated = True
+                return
+            raw = _read_memory(process, address + len_field[0], 2)
+            count = int.from_bytes(raw, byteorder) if raw else 0
+            if count > BTREE_CAPACITY:
+                # Not a node, stop rather than read garbage
+                return
+
+            edge_pointers = []
+            if height > 0:
+                raw = _read_memory(
+                    process, address + edges_offset, (count + 1) * pointer_size
+                )
+                if raw is None:
+                    return
+                edge_pointers = [
+                    int.from_bytes(raw[i : i + pointer_size], byteorder)
+                    for i in range(0, len(raw), pointer_size)
+                ]
+
+            for i in range(count + 1):
+                if height > 0:
+                    walk(edge_pointers[i], height - 1)
+                if i < count and len(self.entries) < length:
+                    self.entries.append(
+                        (
+                            address + keys[0] + i * key_size,
+                            address + vals[0] + i * value_size,
+                        )
+                    )
+
+        walk(root, height)
+
+    def has_children(self):
+        return len(self.entries) > 0
+
+
+def BTreeMapSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::collections::btree::map::BTreeMap<K, V>
+
+    Args:
+        valobj: The BTreeMap value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and a preview of the entries, e.g. `size=2 {1: "a", 2: "b"}`
+    """
+    started = _budget_begin()
+    try:
+        provider = BTreeMapSyntheticProvider(valobj, _dict)
+        return "size=%d %s%s" % (
+            provider.length,
+            _preview(provider, "map"),
+            _address_suffix(valobj),
+        )
+    finally:
+        _budget_end(started)
+
+
+class BTreeSetSyntheticProvider:
+    """
+    Synthetic provider for alloc::collections::btree::set::BTreeSet<T>
+
+    The set wraps a BTreeMap<T, SetValZST>; its keys are the children, in
+    order, as `[0]`, `[1]`, ...
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.map = None
+        self.update()
+
+    @property
+    def length(self):
+        return self.map.length if self.map is not None else 0
+
+    @property
+    def truncated(self):
+        return self.map is not None and self.map.truncated
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+        try:
+            return self.valobj.CreateValueFromAddress(
+                _index_name(index), self.map.entries[index][0], self.map.key_type
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.map = None
+
+        try:
+            map_value = _referent(self.valobj).GetChildMemberWithName("map")
+            if map_value.IsValid():
+                self.map = BTreeMapSyntheticProvider(map_value, None)
+        except Exception:
+            self.map = None
+
+    def has_children(self):
+        return self.length > 0
+
+
+def BTreeSetSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::collections::btree::set::BTreeSet<T>
+
+    Args:
+        valobj: The BTreeSet value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and the values in order, e.g. "size=3 [1, 2, 3]"
+    """
+    started = _budget_begin()
+    try:
+        provider = BTreeSetSyntheticProvider(valobj, _dict)
+        return "size=%d %s%s" % (
+            provider.length,
+            _preview(provider),
+            _address_suffix(valobj),
+        )
+    finally:
+        _budget_end(started)
+
+
+class HashMapSyntheticProvider:
+    """
+    Synthetic provider for std::collections::HashMap<K, V>
+
+    The map is a hashbrown RawTable: `bucket_mask + 1` buckets of (K, V)
+    pairs laid out backwards in front of `ctrl`, and one control byte per
+    bucket after it whose top bit is clear for the occupied ones. The
+    control bytes are read at once and each value is exposed as a child
+    named after its key, e.g. `[(1, 2)]`, in bucket order.
+
+    A HashSet<T> is a HashMap<T, ()> inside, HashSetSyntheticProvider
+    uses this provider to find its values. The hashbrown types std wraps
+    and ahash's wrappers around the std types are read the same way; type
+    aliases such as FxHashMap are the std types with another hasher.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.entries = []
+        self.truncated = False
+        self.key_type = None
+        self.value_type = None
+        self.update()
+
+    @property
+    def length(self):
+        return len(self.entries)
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _keyed_child_index(self, len(self.entries), name)
+
+    def get_child_key(self, index: int) -> str:
+        key = self.valobj.CreateValueFromAddress(
+            "key", self.entries[index][0], self.key_type
+        )
+        return _key_label(key)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= len(self.entries):
+            return None
+        try:
+            return self.valobj.CreateValueFromAddress(
+                "[%s]" % self.get_child_key(index),
+                self.entries[index][1],
+                self.value_type,
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.entries = []
+        self.truncated = False
+
+        started = _budget_begin()
+        try:
+            valobj = _referent(self.valobj)
+            # ahash's AHashMap and AHashSet wrap the std collection
+            wrapped = valobj.GetChildMemberWithName("__0")
+            if wrapped.IsValid():
+                valobj = wrapped
+            base = valobj.GetChildMemberWithName("base")
+            if not base.IsValid():
+                # hashbrown's own types are the std ones without the wrapper
+                base = valobj
+            set_map = base.GetChildMemberWithName("map")
+            if set_map.IsValid():
+                base = set_map
+            table = base.GetChildMemberWithName("table")
+            pair_type = table.GetType().GetTemplateArgumentType(0)
+            # Newer hashbrown keeps the untyped fields in a RawTableInner
+            inner = table.GetChildMemberWithName("table")
+            if inner.IsValid():
+                table = inner
+
+            items = table.GetChildMemberWithName("items").GetValueAsUnsigned()
+            buckets = table.GetChildMemberWithName("bucket_mask").GetValueAsUnsigned()
+            ctrl = _unwrap_pointer(table.GetChildMemberWithName("ctrl"))
+            if items == 0 or ctrl == 0 or not pair_type.IsValid():
+                # Empty maps point ctrl at a static group, don't read it
+                return
+            buckets += 1
+            if items > buckets:
+                return
+
+            key_field = pair_type.GetFieldAtIndex(0)
+            value_field = pair_type.GetFieldAtIndex(1)
+            self.key_type = key_field.GetType()
+            self.value_type = value_field.GetType()
+            pair_size = pair_type.GetByteSize()
+
+            control = _read_memory(valobj.GetProcess(), ctrl, buckets)
+            if control is None:
+                return
+            for i in range(buckets):
+                # EMPTY (0xFF) and DELETED (0x80) both have the top bit set
+                if control[i] & 0x80:
+                    continue
+                if _budget_exceeded():
+                    self.truncated = True
+                    break
+                address = ctrl - (i + 1) * pair_size
+                self.entries.append(
+                    (
+                        address + key_field.GetOffsetInBytes(),
+                        address + value_field.GetOffsetInBytes(),
+                    )
+                )
+                if len(self.entries) == items:
+                    break
+        except Exception:
+            self.entries = []
+        finally:
+            _budget_end(started)
+
+    def has_children(self):
+        return len(self.entries) > 0
+
+
+def HashMapSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::collections::HashMap<K, V>
+
+    Args:
+        valobj: The HashMap value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and a preview of the entries, e.g. `size=1 {(1, 2): "a"}`
+    """
+    started = _budget_begin()
+    try:
+        provider = HashMapSyntheticProvider(valobj, _dict)
+        return "size=%d %s%s" % (
+            provider.length,
+            _preview(provider, "map"),
+            _address_suffix(valobj),
+        )
+    finally:
+        _budget_end(started)
+
+
+class HashSetSyntheticProvider:
+    """
+    Synthetic provider for std::collections::HashSet<T>
+
+    Exposes the values as `[0]`, `[1]`, ... in bucket order.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.map = None
+        self.update()
+
+    @property
+    def length(self):
+        return self.map.length if self.map is not None else 0
+
+    @property
+    def truncated(self):
+        return self.map is not None and self.map.truncated
+
+    def num_children(self):
+        return _max_children(self.valobj, self.length)
+
+    def get_child_index(self, name: str):
+        return _index_from_name(name)
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.length:
+            return None
+        try:
+            return self.valobj.CreateValueFromAddress(
+                _index_name(index), self.map.entries[index][0], self.map.key_type
+            )
+        except Exception:
+            return None
+
+    def update(self):
+        self.map = None
+
+        try:
+            self.map = HashMapSyntheticProvider(self.valobj, None)
+        except Exception:
+            self.map = None
+
+    def has_children(self):
+        return self.length > 0
+
+
+def HashSetSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::collections::HashSet<T>
+
+    Args:
+        valobj: The HashSet value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The size and a preview of the values, e.g. "size=2 [3, 1]"
+    """
+    started = _budget_begin()
+    try:
+        provider = HashSetSyntheticProvider(valobj, _dict)
+        return "size=%d %s%s" % (
+            provider.length,
+            _preview(provider),
+            _address_suffix(valobj),
+        )
+    finally:
+        _budget_end(started)
+
+
+def _refcounted_parts(valobj: SBValue):
+    """
+    Finds the allocation behind an Rc<T> or Arc<T>
+
+    Rc { ptr: NonNull<RcInner<T>> } and Arc { ptr: NonNull<ArcInner<T>> }
+    point at a header with the strong and weak counts, followed by the
+    value in a field named `value` (Rc) or `data` (Arc).
+
+    Returns:
+        A (pointer, address, payload) tuple, pointer being the thin pointer
+        to the inner type. payload is None if it can't be resolved.
+        (None, 0, None) if the layout isn't recognized.
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    ptr = valobj.GetChildMemberWithName("ptr")
+    pointer = ptr.GetChildMemberWithName("pointer")
+    if not pointer.IsValid():
+        return None, 0, None
+
+    # Unsized payloads make this a fat pointer
+    data_ptr = pointer.GetChildMemberWithName("data_ptr")
+    if data_ptr.IsValid():
+        return data_ptr, data_ptr.GetValueAsUnsigned(), None
+
+    address = pointer.GetValueAsUnsigned()
+    if address == 0:
+        return None, 0, None
+
+    inner = pointer.Dereference()
+    payload = inner.GetChildMemberWithName("value")
+    if not payload.IsValid():
+        payload = inner.GetChildMemberWithName("data")
+    if not payload.IsValid():
+        payload = None
+    return pointer, address, payload
+
+
+def _refcounted_str(valobj: SBValue):
+    """
+    Reads the text behind an Rc<str> or Arc<str>
+
+    The pointer to the inner type is a fat pointer whose metadata is the
+    string length; the bytes follow the refcount header.
+
+    Returns:
+        The string, or None if valobj doesn't point at a str
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    pointer = valobj.GetChildMemberWithName("ptr").GetChildMemberWithName("pointer")
+    data_ptr = pointer.GetChildMemberWithName("data_ptr")
+    length = pointer.GetChildMemberWithName("length")
+    if not data_ptr.IsValid() or not length.IsValid():
+        return None
+
+    inner = data_ptr.GetType().GetPointeeType()
+    field = _type_field_offset(inner, ("value",)) or _type_field_offset(
+        inner, ("data",)
+    )
+    if field is None or field[1].GetUnqualifiedType().GetName() != "str":
+        return None
+
+    return _read_string(
+        valobj.GetProcess(),
+        data_ptr.GetValueAsUnsigned() + field[0],
+        length.GetValueAsUnsigned(),
+    )
+
+
+class RefCountedSliceProvider(VecSyntheticProvider):
+    """
+    The elements behind an Rc<[T]> or Arc<[T]>
+
+    Like Rc<str>, the pointer to the inner type is a fat pointer with the
+    element count as its metadata; the elements follow the refcount header
+    in the same allocation. length stays 0 for any other Rc or Arc.
+    """
+
+    def update(self):
+        self.length = 0
+        self.declared_length = 0
+        self.data_ptr = 0
+        self.element_size = 0
+        self.element_type = None
+
+        try:
+            valobj = self.valobj.GetNonSyntheticValue()
+            pointer = valobj.GetChildMemberWithName("ptr").GetChildMemberWithName(
+                "pointer"
+            )
+            data_ptr = pointer.GetChildMemberWithName("data_ptr")
+            length = pointer.GetChildMemberWithName("length")
+            if not data_ptr.IsValid() or not length.IsValid():
+                return
+
+            inner = data_ptr.GetType().GetPointeeType()
+            field = _type_field_offset(inner, ("value",)) or _type_field_offset(
+                inner, ("data",)
+            )
+            if field is None or not field[1].IsArrayType():
+                return
+            element_type = field[1].GetArrayElementType()
+            if not element_type.IsValid() or data_ptr.GetValueAsUnsigned() == 0:
+                return
+
+            self.element_type = element_type
+            self.element_size = element_type.GetByteSize()
+            self.data_ptr = data_ptr.GetValueAsUnsigned() + field[0]
+            self.length = length.GetValueAsUnsigned()
+            self.declared_length = self.length
+        except Exception:
+            self.length = 0
+            self.data_ptr = 0
+            self.element_type = None
+
+
+def _refcount_prefix(pointer: SBValue, address: int) -> str:
+    """Renders the counts as `(strong=1)` or `(strong=1, weak=2)`"""
+    counts = _read_refcounts(pointer, address)
+    if counts is None:
+        return "(strong=?)"
+    strong, weak = counts
+    if weak:
+        return "(%s)" % _join(["strong=%d" % strong, "weak=%d" % weak])
+    return "(strong=%d)" % strong
+
+
+def RefCountedSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::rc::Rc<T> and alloc::sync::Arc<T>
+
+    Args:
+        valobj: The Rc or Arc value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The counts followed by the value's own summary, e.g. `(strong=2) 42`,
+        or the elements of an Rc<[T]>: `(strong=1) size=3 [1, 2, 3]`.
+        Inside a collection's preview an allocation already rendered there
+        is `<shared @0x...>`, which bounds the work for shared graphs.
+    """
+    pointer, address, payload = _refcounted_parts(valobj)
+    if pointer is None:
+        return ""
+    if _PREVIEW_DEPTH[0] > 0:
+        if address in _PREVIEW_SHARED:
+            return "<shared @0x%x>" % address
+        _PREVIEW_SHARED.add(address)
+
+    prefix = _refcount_prefix(pointer, address)
+    suffix = _address_suffix(valobj, address)
+    if payload is None:
+        text = _refcounted_str(valobj)
+        if text is not None:
+            return '%s "%s"%s' % (prefix, text, suffix)
+        elements = RefCountedSliceProvider(valobj, _dict)
+        if elements.element_type is not None:
+            return "%s size=%d %s%s" % (
+                prefix,
+                elements.length,
+                _preview(elements),
+                suffix,
+            )
+        return prefix + suffix
+    return "%s %s%s" % (prefix, _value_summary(payload) or "{...}", suffix)
+
+
+class RefCountedSyntheticProvider:
+    """
+    Synthetic provider for alloc::rc::Rc<T> and alloc::sync::Arc<T>
+
+    Exposes the following children:
+    - value: the shared value
+    - strong: the strong count
+    - weak: the weak count, excluding the implicit one of the strong refs
+
+    Rc<[T]> and Arc<[T]> expose their elements instead, like a slice; the
+    counts are in the summary.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.payload = None
+        self.elements = None
+        self.strong = 0
+        self.weak = 0
+        self.update()
+
+    def num_children(self):
+        if self.elements is not None:
+            return self.elements.num_children()
+        return 3 if self.payload is not None else 2
+
+    def get_child_index(self, name: str):
+        if self.elements is not None:
+            return self.elements.get_child_index(name)
+        names = ["strong", "weak"]
+        if self.payload is not None:
+            names.insert(0, "value")
+        return names.index(name) if name in names else -1
+
+    def get_child_at_index(self, index: int):
+        if self.elements is not None:
+            return self.elements.get_child_at_index(index)
+        if self.payload is None:
+            index += 1
+
+        try:
+            if index == 0:
+                return self.payload
+            elif index == 1:
+                return self.valobj.CreateValueFromExpression(
+                    "strong", "(unsigned long long)%d" % self.strong
+                )
+            elif index == 2:
+                return self.valobj.CreateValueFromExpression(
+                    "weak", "(unsigned long long)%d" % self.weak
+                )
+        except Exception:
+            return None
+
+        return None
+
+    def update(self):
+        self.payload = None
+        self.elements = None
+        self.strong = 0
+        self.weak = 0
+
+        try:
+            pointer, address, payload = _refcounted_parts(self.valobj)
+            if pointer is None:
+                return
+
+            counts = _read_refcounts(pointer, address)
+            if counts is not None:
+                self.strong, self.weak = counts
+            self.payload = payload
+            if payload is None:
+                elements = RefCountedSliceProvider(self.valobj, None)
+                if elements.element_type is not None:
+                    self.elements = elements
+        except Exception:
+            self.payload = None
+            self.elements = None
+
+    def has_children(self):
+        return True
+
+
+def _refcell_parts(valobj: SBValue):
+    """
+    Reads the borrow flag and the value of a core::cell::RefCell<T>
+
+    RefCell { borrow: Cell<isize>, value: UnsafeCell<T> }; the flag counts
+    the shared borrows and is negative while the value is borrowed mutably.
+
+    Returns:
+        A (flag, value) tuple, or (None, None) if the layout isn't recognized
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    flag = _innermost(valobj.GetChildMemberWithName("borrow"))
+    value = valobj.GetChildMemberWithName("value").GetChildMemberWithName("value")
+    if not flag.IsValid() or not value.IsValid():
+        return None, None
+    return flag.GetValueAsSigned(), value
+
+
+def _borrow_state(flag: int) -> str:
+    """Renders a RefCell borrow flag as `<unborrowed>`, `<borrowed=2>`, ..."""
+    if flag == 0:
+        return "<unborrowed>"
+    if flag < 0:
+        return "<mutably borrowed>"
+    return "<borrowed=%d>" % flag
+
+
+def RefCellSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::cell::RefCell<T>
+
+    Args:
+        valobj: The RefCell value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The borrow state and the value's own summary, e.g.
+        `RefCell { <unborrowed> size=3 [1, 2, 3] }`
+    """
+    flag, value = _refcell_parts(valobj)
+    if value is None:
+        return ""
+    return _braced(
+        "RefCell", "%s %s" % (_borrow_state(flag), _value_summary(value) or "{...}")
+    )
+
+
+class RefCellSyntheticProvider:
+    """
+    Synthetic provider for core::cell::RefCell<T>
+
+    Exposes the following children:
+    - value: the wrapped value
+    - borrow: the borrow state, e.g. "<unborrowed>"
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.flag = 0
+        self.value = None
+        self.update()
+
+    def num_children(self):
+        return 2 if self.value is not None else 0
+
+    def get_child_index(self, name: str):
+        names = ["value", "borrow"]
+        return names.index(name) if name in names else -1
+
+    def get_child_at_index(self, index: int):
+        if self.value is None:
+            return None
+
+        try:
+            if index == 0:
+                return self.value
+            elif index == 1:
+                return self.valobj.CreateValueFromExpression(
+                    "borrow", '(const char*)"%s"' % _borrow_state(self.flag)
+                )
+        except Exception:
+            return None
+
+        return None
+
+    def update(self):
+        self.flag = 0
+        self.value = None
+
+        try:
+            flag, value = _refcell_parts(self.valobj)
+            if value is not None:
+                self.flag, self.value = flag, value
+        except Exception:
+            self.value = None
+
+    def has_children(self):
+        return self.value is not None
+
+
+# std::sync::Mutex<T> and RwLock<T>, at std::sync::poison:: since Rust 1.84
+LOCK_TYPE_PATTERN = r"^std::sync::(poison::)?(mutex::Mutex|rwlock::RwLock)<.+>$"
+
+
+def _lock_parts(valobj: SBValue):
+    """
+    Reads the poison flag and the value of a Mutex<T> or RwLock<T>
+
+    Both are { inner, poison: Flag { failed: AtomicBool }, data: UnsafeCell<T> }.
+    With panic=abort the Flag has no field and a lock is never poisoned.
+
+    Returns:
+        A (poisoned, data) tuple, or (False, None) if the layout isn't
+        recognized
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    data = valobj.GetChildMemberWithName("data").GetChildMemberWithName("value")
+    if not data.IsValid():
+        return False, None
+    failed = valobj.GetChildMemberWithName("poison").GetChildMemberWithName("failed")
+    poisoned = failed.IsValid() and _innermost(failed).GetValueAsUnsigned() != 0
+    return poisoned, data
+
+
+def LockSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::sync::Mutex<T> and std::sync::RwLock<T>
+
+    A thread that panicked while holding the lock poisoned it, which is
+    shown before the value since it usually explains the state of it.
+
+    Args:
+        valobj: The Mutex or RwLock value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The lock type and the value's summary, e.g. `Mutex { 5 }` or
+        `Mutex (poisoned) { size=2 [1, 2] }`
+    """
+    poisoned, data = _lock_parts(valobj)
+    if data is None:
+        return ""
+    name = _strip_generics(valobj.GetTypeName() or "").rsplit("::", 1)[-1]
+    return _braced(
+        name + (" (poisoned)" if poisoned else ""), _value_summary(data) or "{...}"
+    )
+
+
+class LockSyntheticProvider:
+    """
+    Synthetic provider for std::sync::Mutex<T> and std::sync::RwLock<T>
+
+    Exposes the following children:
+    - data: the protected value
+    - poisoned: whether a thread panicked while holding the lock
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.poisoned = False
+        self.data = None
+        self.update()
+
+    def num_children(self):
+        return 2 if self.data is not None else 0
+
+    def get_child_index(self, name: str):
+        names = ["data", "poisoned"]
+        return names.index(name) if name in names else -1
+
+    def get_child_at_index(self, index: int):
+        if self.data is None:
+            return None
+
+        try:
+            if index == 0:
+                return self.data
+            elif index == 1:
+                return self.valobj.CreateValueFromExpression(
+                    "poisoned", "(bool)%s" % ("true" if self.poisoned else "false")
+                )
+        except Exception:
+            return None
+
+        return None
+
+    def update(self):
+        self.poisoned = False
+        self.data = None
+
+        try:
+            self.poisoned, self.data = _lock_parts(self.valobj)
+        except Exception:
+            self.data = None
+
+    def has_children(self):
+        return self.data is not None
+
+
+# std::sync::OnceLock<T> and LazyLock<T, F>, core::cell::OnceCell<T> and
+# LazyCell<T, F>. once_cell's types are registered with the third-party ones.
+ONCE_TYPE_PATTERN = (
+    r"^(std::sync::(once_lock::OnceLock|lazy_lock::LazyLock)"
+    r"|core::cell::(once::OnceCell|lazy::LazyCell))<.+>$"
+)
+
+# The state word of std::sync::Once by field name, with the (mask, value)
+# it has once complete. The futex implementation has had two encodings, the
+# queue one (used without futexes) keeps the state in the low two bits.
+ONCE_COMPLETE = {
+    "state_and_queued": (0b11, 0),
+    "state": (0xFFFFFFFF, 4),
+    "state_and_queue": (0b11, 3),
+}
+
+
+def _once_completed(once: SBValue):
+    """
+    Tells whether a std::sync::Once has run to completion
+
+    Returns:
+        True or False, or None if the layout isn't recognized
+    """
+    inner = once.GetNonSyntheticValue().GetChildMemberWithName("inner")
+    for name, (mask, complete) in ONCE_COMPLETE.items():
+        state = inner.GetChildMemberWithName(name)
+        if state.IsValid():
+            return _innermost(state).GetValueAsUnsigned() & mask == complete
+    return None
+
+
+def _once_option(option: SBValue):
+    """The (initialized, value) of a cell keeping its value in an Option<T>"""
+    name, payload = _option_payload(option)
+    if name == "Some":
+        return True, payload
+    if name == "None":
+        return False, None
+    return None, None
+
+
+def _once_value(valobj: SBValue):
+    """
+    Finds the value of a once-initialized cell
+
+    OnceLock { once: Once, value: UnsafeCell<MaybeUninit<T>> } is set once
+    `once` completed. LazyLock { once: Once, data: UnsafeCell<Data<T, F>> }
+    holds the closure in the union until then; before Rust 1.80 it was
+    { cell: OnceLock<T>, init: Cell<Option<F>> }, like once_cell's Lazy.
+
+    The single threaded core::cell::OnceCell and once_cell's OnceCells keep
+    an UnsafeCell<Option<T>>, the sync one of once_cell next to its waiter
+    queue. LazyCell is { state: UnsafeCell<State<T, F>> } with the value in
+    the `Init` variant.
+
+    Returns:
+        An (initialized, value) tuple, (None, None) if the layout isn't
+        recognized; value is None while uninitialized
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    cell = valobj.GetChildMemberWithName("cell")
+    if cell.IsValid():
+        return _once_value(cell)
+
+    # once_cell::sync::OnceCell(imp::OnceCell)
+    imp = valobj.GetChildMemberWithName("__0")
+    if imp.IsValid():
+        return _once_option(imp.GetChildMemberWithName("value").GetChildAtIndex(0))
+
+    inner = valobj.GetChildMemberWithName("inner")
+    if inner.IsValid():
+        return _once_option(inner.GetChildAtIndex(0))
+
+    state = valobj.GetChildMemberWithName("state")
+    if state.IsValid():
+        name, fields = _enum_parts(state.GetChildAtIndex(0))
+        if name is None:
+            return None, None
+        if name == "Init" and fields:
+            return True, fields[0][1]
+        return False, None
+
+    initialized = _once_completed(valobj.GetChildMemberWithName("once"))
+    if initialized is None:
+        return None, None
+    if not initialized:
+        return False, None
+
+    storage = valobj.GetChildMemberWithName("value")
+    if not storage.IsValid():
+        storage = valobj.GetChildMemberWithName("data")
+    # UnsafeCell -> MaybeUninit or Data union -> ManuallyDrop -> T
+    value = storage.GetChildMemberWithName("value")
+    for _ in range(2):
+        value = value.GetChildMemberWithName("value")
+    if not value.IsValid():
+        return None, None
+    return True, value
+
+
+def OnceSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for OnceLock, LazyLock, OnceCell, LazyCell and Lazy
+
+    These mostly wrap statics, which read best as their value: the summary
+    is the value's own, or `<uninitialized>` until it is set.
+
+    Args:
+        valobj: The cell to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The value's summary like `size=3 [1, 2, 3]`, or `<uninitialized>`
+    """
+    initialized, value = _once_value(valobj)
+    if initialized is None:
+        return ""
+    if value is None:
+        return "<uninitialized>"
+    return _value_summary(value) or "{...}"
+
+
+class OnceSyntheticProvider:
+    """
+    Synthetic provider for OnceLock, LazyLock, OnceCell, LazyCell and Lazy
+
+    Exposes the value as the only child `value` once it is initialized, no
+    children before.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.value = None
+        self.update()
+
+    def num_children(self):
+        return 1 if self.value is not None else 0
+
+    def get_child_index(self, name: str):
+        return 0 if name == "value" else -1
+
+    def get_child_at_index(self, index: int):
+        return self.value if index == 0 else None
+
+    def update(self):
+        self.value = None
+
+        try:
+            self.value = _once_value(self.valobj)[1]
+        except Exception:
+            self.value = None
+
+    def has_children(self):
+        return self.value is not None
+
+
+def NonZeroSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::num::NonZero<T> and the NonZeroU32 style types
+
+    The integer is wrapped in one or more single-field structs (newer
+    toolchains add a `NonZeroU32Inner` layer), which are unwrapped here.
+
+    Args:
+        valobj: The NonZero value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The integer value, e.g. "7"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    for _ in range(4):
+        if valobj.GetNumChildren() == 0:
+            return valobj.GetValue() or ""
+        valobj = valobj.GetChildAtIndex(0)
+    return ""
+
+
+# Escapes used by char's Debug output
+CHAR_ESCAPES = {
+    "\t": "\\t",
+    "\r": "\\r",
+    "\n": "\\n",
+    "\0": "\\0",
+    "\\": "\\\\",
+    "'": "\\'",
+}
+
+
+def CharSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for char
+
+    LLDB shows char as a plain code point or a C++ style U'x' literal; this
+    quotes it the way Rust's Debug does, so chars inside collections render
+    as `'x'`. Control and other unprintable characters are escaped as
+    `\\u{..}`.
+
+    Args:
+        valobj: The char value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The quoted character, e.g. 'ß'
+    """
+    code = valobj.GetNonSyntheticValue().GetValueAsUnsigned()
+    if code > 0x10FFFF or 0xD800 <= code <= 0xDFFF:
+        # Not a Unicode scalar value, can only be a misread
+        return "'\\u{%x}' (invalid)" % code
+
+    char = chr(code)
+    if char in CHAR_ESCAPES:
+        return "'%s'" % CHAR_ESCAPES[char]
+    if not char.isprintable():
+        return "'\\u{%x}'" % code
+    return "'%s'" % char
+
+
+def _raw_bytes(valobj: SBValue) -> bytes:
+    """The bytes of a value as stored in the target, or b"" if unreadable"""
+    data = valobj.GetData()
+    error = SBError()
+    raw = data.ReadRawData(error, 0, data.GetByteSize())
+    if not error.Success() or raw is None:
+        return b""
+    return raw
+
+
+def _format_ipv4(octets: bytes) -> str:
+    return "%d.%d.%d.%d" % tuple(octets)
+
+
+def _format_ipv6(octets: bytes) -> str:
+    """
+    Formats 16 octets in network order exactly like Rust's Ipv6Addr Display
+
+    The longest run of two or more zero segments (the first one on ties) is
+    compressed to `::`, IPv4-mapped addresses end in dotted decimal.
+    """
+    segments = [(octets[i] << 8) | octets[i + 1] for i in range(0, 16, 2)]
+
+    if not any(segments):
+        return "::"
+    if segments == [0] * 7 + [1]:
+        return "::1"
+    if segments[:6] == [0, 0, 0, 0, 0, 0xFFFF]:
+        return "::ffff:" + _format_ipv4(octets[12:])
+
+    longest_start, longest_len = 0, 0
+    current_start, current_len = 0, 0
+    for i, segment in enumerate(segments):
+        if segment == 0:
+            if current_len == 0:
+                current_start = i
+            current_len += 1
+            if current_len > longest_len:
+                longest_start, longest_len = current_start, current_len
+        else:
+            current_len = 0
+
+    def hex_segments(chunk):
+        return ":".join("%x" % segment for segment in chunk)
+
+    if longest_len > 1:
+        return "%s::%s" % (
+            hex_segments(segments[:longest_start]),
+            hex_segments(segments[longest_start + longest_len :]),
+        )
+    return hex_segments(segments)
+
+
+def Ipv4AddrSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::net::Ipv4Addr
+
+    Args:
+        valobj: The Ipv4Addr value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The address in dotted decimal, e.g. "192.0.2.1"
+    """
+    octets = _raw_bytes(valobj.GetNonSyntheticValue())
+    if len(octets) != 4:
+        return ""
+    return _format_ipv4(octets)
+
+
+def Ipv6AddrSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::net::Ipv6Addr
+
+    Args:
+        valobj: The Ipv6Addr value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The address as Rust displays it, e.g. "fe80::1"
+    """
+    octets = _raw_bytes(valobj.GetNonSyntheticValue())
+    if len(octets) != 16:
+        return ""
+    return _format_ipv6(octets)
+
+
+def _socket_addr_field(valobj: SBValue, name: str, legacy_name: str):
+    """
+    Reads an integer field of a SocketAddrV4/V6
+
+    Current toolchains store the fields natively (`port`, `scope_id`),
+    older ones wrapped a libc sockaddr in `inner` with the port in network
+    byte order.
+
+    Returns:
+        The value, or None if neither layout matches
+    """
+    field = valobj.GetChildMemberWithName(name)
+    if field.IsValid():
+        return field.GetValueAsUnsigned()
+
+    field = valobj.GetChildMemberWithName("inner").GetChildMemberWithName(legacy_name)
+    if not field.IsValid():
+        return None
+    value = field.GetValueAsUnsigned()
+    if legacy_name.endswith("_port"):
+        value = ((value & 0xFF) << 8) | (value >> 8)
+    return value
+
+
+def _socket_addr_ip(valobj: SBValue, legacy_path) -> bytes:
+    ip = valobj.GetChildMemberWithName("ip")
+    if not ip.IsValid():
+        ip = valobj.GetChildMemberWithName("inner")
+        for name in legacy_path:
+            ip = ip.GetChildMemberWithName(name)
+    if not ip.IsValid():
+        return b""
+    return _raw_bytes(ip)
+
+
+def SocketAddrV4SummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::net::SocketAddrV4
+
+    Args:
+        valobj: The SocketAddrV4 value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The address and port, e.g. "192.0.2.1:80"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    octets = _socket_addr_ip(valobj, ("sin_addr",))
+    port = _socket_addr_field(valobj, "port", "sin_port")
+    if len(octets) != 4 or port is None:
+        return ""
+    return "%s:%d" % (_format_ipv4(octets), port)
+
+
+def SocketAddrV6SummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::net::SocketAddrV6
+
+    Matches Rust's Display: the scope id is only shown when non-zero, the
+    flow info never.
+
+    Args:
+        valobj: The SocketAddrV6 value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The address and port, e.g. "[fe80::1%3]:443"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    octets = _socket_addr_ip(valobj, ("sin6_addr",))
+    port = _socket_addr_field(valobj, "port", "sin6_port")
+    scope_id = _socket_addr_field(valobj, "scope_id", "sin6_scope_id")
+    if len(octets) != 16 or port is None:
+        return ""
+
+    if scope_id:
+        return "[%s%%%d]:%d" % (_format_ipv6(octets), scope_id, port)
+    return "[%s]:%d" % (_format_ipv6(octets), port)
+
+
+# Days before each month in a common year
+DAYS_BEFORE_MONTH = (0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365)
+
+
+def _is_leap_year(year: int) -> bool:
+    return year % 4 == 0 and (year % 100 != 0 or year % 400 == 0)
+
+
+def _format_naive_date(packed: int):
+    """
+    Decodes chrono's packed NaiveDate into `YYYY-MM-DD`
+
+    chrono stores a date in one i32 as `year << 13 | ordinal << 4 | flags`,
+    ordinal being the day of the year starting at 1. Years outside 0-9999
+    get a sign like chrono's Display: `+12345-01-01`, `-0044-03-15`.
+
+    Returns:
+        The formatted date, or None if the ordinal can't be valid
+    """
+    year = packed >> 13
+    ordinal = (packed >> 4) & 0x1FF
+    leap = 1 if _is_leap_year(year) else 0
+    if ordinal < 1 or ordinal > 365 + leap:
+        return None
+
+    for month in range(1, 13):
+        days_before = DAYS_BEFORE_MONTH[month] + (leap if month >= 2 else 0)
+        if ordinal <= days_before:
+            break
+    day = ordinal - DAYS_BEFORE_MONTH[month - 1] - (leap if month > 2 else 0)
+    return _format_ymd(year, month, day)
+
+
+def _format_ymd(year: int, month: int, day: int) -> str:
+    """`YYYY-MM-DD`, with a sign for years outside 0-9999 like chrono"""
+    if 0 <= year <= 9999:
+        return "%04d-%02d-%02d" % (year, month, day)
+    return "%+05d-%02d-%02d" % (year, month, day)
+
+
+def NaiveDateSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for chrono::NaiveDate
+
+    The packed i32 is read as a whole, so it works for both the older
+    `ymdf` and the newer `yof` field.
+
+    Args:
+        valobj: The NaiveDate value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The date like `2024-02-29`
+    """
+    packed = _naive_date_packed(valobj)
+    if packed is None:
+        return ""
+    return _format_naive_date(packed) or "<invalid date>"
+
+
+def _naive_date_packed(valobj: SBValue):
+    """The packed i32 of a NaiveDate, or None if it can't be read"""
+    raw = _raw_bytes(valobj.GetNonSyntheticValue())
+    if len(raw) != 4:
+        return None
+    big_endian = valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig
+    return int.from_bytes(raw, "big" if big_endian else "little", signed=True)
+
+
+def _format_duration(secs: int, nanos: int) -> str:
+    """
+    Formats seconds and nanoseconds like `1d 2h 3m 4.5s`
+
+    Zero components are left out, a zero duration is `0s`. chrono keeps
+    nanos in 0..1e9 also for negative durations, so -1.5s is stored as
+    secs=-2, nanos=500000000.
+    """
+    total = secs * 1000000000 + nanos
+    sign = "-" if total < 0 else ""
+    total = abs(total)
+
+    seconds, fraction = divmod(total, 1000000000)
+    minutes, seconds = divmod(seconds, 60)
+    hours, minutes = divmod(minutes, 60)
+    days, hours = divmod(hours, 24)
+
+    parts = []
+    for amount, unit in ((days, "d"), (hours, "h"), (minutes, "m")):
+        if amount:
+            parts.append("%d%s" % (amount, unit))
+    if seconds or fraction or not parts:
+        text = "%d" % seconds
+        if fraction:
+            text += (".%09d" % fraction).rstrip("0")
+        parts.append(text + "s")
+    return sign + " ".join(parts)
+
+
+def ChronoDurationSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for chrono::Duration (chrono::TimeDelta)
+
+    Args:
+        valobj: The Duration value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The duration like `1m 30.5s`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    secs = valobj.GetChildMemberWithName("secs")
+    nanos = valobj.GetChildMemberWithName("nanos")
+    if not secs.IsValid() or not nanos.IsValid():
+        return ""
+    return _format_duration(secs.GetValueAsSigned(), nanos.GetValueAsSigned())
+
+
+def _innermost(valobj: SBValue) -> SBValue:
+    """
+    Unwraps single-field newtypes down to the scalar they hold
+
+    Newer toolchains wrap integers with a restricted range, e.g. the nanos
+    of Duration in `Nanoseconds(u32)`.
+    """
+    for _ in range(4):
+        if valobj.GetNumChildren() != 1:
+            break
+        valobj = valobj.GetChildAtIndex(0)
+    return valobj
+
+
+def StdDurationSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::time::Duration
+
+    Args:
+        valobj: The Duration value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The duration like `1m 30.5s`, the same way chrono durations show
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    secs = valobj.GetChildMemberWithName("secs")
+    nanos = valobj.GetChildMemberWithName("nanos")
+    if not secs.IsValid() or not nanos.IsValid():
+        return ""
+    return _format_duration(
+        secs.GetValueAsUnsigned(), _innermost(nanos).GetValueAsUnsigned()
+    )
+
+
+def _timespec(valobj: SBValue):
+    """
+    The seconds and nanoseconds of an Instant or SystemTime on Unix targets
+
+    Both wrap a sys::time type holding a Timespec { tv_sec, tv_nsec }, with
+    tv_sec signed and tv_nsec in 0..1e9 also for times before the start.
+
+    Returns:
+        (secs, nanos), or None for other platforms' layouts
+    """
+    timespec = valobj.GetNonSyntheticValue()
+    for name in ("__0", "t"):
+        field = timespec.GetChildMemberWithName(name)
+        if field.IsValid():
+            timespec = field
+    secs = timespec.GetChildMemberWithName("tv_sec")
+    nanos = timespec.GetChildMemberWithName("tv_nsec")
+    if not secs.IsValid() or not nanos.IsValid():
+        return None
+    return secs.GetValueAsSigned(), _innermost(nanos).GetValueAsUnsigned()
+
+
+class TimespecSyntheticProvider:
+    """
+    Synthetic provider for std::time::Instant and SystemTime
+
+    Where the summary decodes the timespec, its platform fields add nothing
+    and are hidden, also when the value is a field of an expanded struct.
+    Other platforms' layouts keep their raw fields.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.raw = None
+        self.update()
+
+    def num_children(self):
+        return self.raw.GetNumChildren() if self.raw is not None else 0
+
+    def get_child_index(self, name: str):
+        return self.raw.GetIndexOfChildWithName(name) if self.raw is not None else -1
+
+    def get_child_at_index(self, index: int):
+        return self.raw.GetChildAtIndex(index) if self.raw is not None else None
+
+    def update(self):
+        self.raw = None
+        if _timespec(self.valobj) is None:
+            self.raw = self.valobj.GetNonSyntheticValue()
+
+    def has_children(self):
+        return self.raw is not None and self.raw.MightHaveChildren()
+
+
+def InstantSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::time::Instant
+
+    On Unix targets an Instant is a CLOCK_MONOTONIC timespec, the time
+    since an unspecified start, usually the boot.
+
+    Args:
+        valobj: The Instant value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The clock reading, e.g. `Instant(2h 3m 4.5s)`, or "" for other
+        platforms' layouts
+    """
+    timespec = _timespec(valobj)
+    if timespec is None:
+        return ""
+    return "Instant(%s)" % _format_duration(*timespec)
+
+
+def _civil_from_days(days: int):
+    """
+    The proleptic Gregorian (year, month, day) of a day count since 1970-01-01
+
+    Negative counts are days before the epoch; the arithmetic floors, so
+    the 400 year eras work the same on both sides.
+    """
+    days += 719468
+    era = days // 146097
+    day_of_era = days - era * 146097
+    year_of_era = (
+        day_of_era - day_of_era // 1460 + day_of_era // 36524 - day_of_era // 146096
+    ) // 365
+    day_of_year = day_of_era - (
+        365 * year_of_era + year_of_era // 4 - year_of_era // 100
+    )
+    month_index = (5 * day_of_year + 2) // 153
+    day = day_of_year - (153 * month_index + 2) // 5 + 1
+    month = month_index + 3 if month_index < 10 else month_index - 9
+    year = year_of_era + era * 400 + (1 if month <= 2 else 0)
+    return year, month, day
+
+
+def _format_system_time(secs: int, nanos: int) -> str:
+    """
+    Formats a time since the UNIX epoch like `1969-07-20 20:17:40.5 UTC`
+
+    Years outside 0-9999 get a sign like chrono's dates do.
+    """
+    days, seconds = divmod(secs, 86400)
+    date = _format_ymd(*_civil_from_days(days))
+
+    hours, seconds = divmod(seconds, 3600)
+    minutes, seconds = divmod(seconds, 60)
+    text = "%s %02d:%02d:%02d" % (date, hours, minutes, seconds)
+    if nanos:
+        text += (".%09d" % nanos).rstrip("0")
+    return text + " UTC"
+
+
+def SystemTimeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::time::SystemTime
+
+    On Unix targets a SystemTime is a CLOCK_REALTIME timespec. Times before
+    1970 have a negative tv_sec, which is read as signed.
+
+    Args:
+        valobj: The SystemTime value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The UTC time like `2024-02-29 12:00:00 UTC`, or "" for other
+        platforms' layouts
+    """
+    timespec = _timespec(valobj)
+    if timespec is None:
+        return ""
+    return _format_system_time(*timespec)
+
+
+def _days_from_civil(year: int, month: int, day: int) -> int:
+    """The day count since 1970-01-01 of a date, the inverse of _civil_from_days"""
+    year -= 1 if month <= 2 else 0
+    era = year // 400
+    year_of_era = year - era * 400
+    day_of_year = (153 * (month - 3 if month > 2 else month + 9) + 2) // 5 + day - 1
+    day_of_era = year_of_era * 365 + year_of_era // 4 - year_of_era // 100
+    return era * 146097 + day_of_era + day_of_year - 719468
+
+
+def _naive_datetime_parts(valobj: SBValue):
+    """
+    Reads a NaiveDateTime { date, time: NaiveTime { secs, frac } }
+
+    Returns:
+        (seconds since 1970-01-01 00:00:00, frac) or None if it can't be
+        read. frac is the nanoseconds, 1e9 or more during a leap second.
+    """
+    packed = _naive_date_packed(valobj.GetChildMemberWithName("date"))
+    time_of_day = valobj.GetChildMemberWithName("time")
+    secs = time_of_day.GetChildMemberWithName("secs")
+    frac = time_of_day.GetChildMemberWithName("frac")
+    if packed is None or not secs.IsValid() or not frac.IsValid():
+        return None
+
+    year = packed >> 13
+    ordinal = (packed >> 4) & 0x1FF
+    days = _days_from_civil(year, 1, 1) + ordinal - 1
+    return days * 86400 + secs.GetValueAsUnsigned(), frac.GetValueAsUnsigned()
+
+
+def _format_naive_datetime(secs: int, frac: int) -> str:
+    """
+    Formats a date and time like chrono's Display: `2024-02-29 12:30:00.500`
+
+    A fraction is shown in 3, 6 or 9 digits, whichever is exact; a leap
+    second shows as second 60.
+    """
+    if frac >= 1000000000:
+        frac -= 1000000000
+        leap = 1
+    else:
+        leap = 0
+    days, seconds = divmod(secs, 86400)
+    hours, seconds = divmod(seconds, 3600)
+    minutes, seconds = divmod(seconds, 60)
+    text = "%s %02d:%02d:%02d" % (
+        _format_ymd(*_civil_from_days(days)),
+        hours,
+        minutes,
+        seconds + leap,
+    )
+    if frac % 1000000 == 0:
+        return text + (".%03d" % (frac // 1000000) if frac else "")
+    if frac % 1000 == 0:
+        return text + ".%06d" % (frac // 1000)
+    return text + ".%09d" % frac
+
+
+def _format_fixed_offset(local_minus_utc: int) -> str:
+    """Formats a UTC offset like chrono's FixedOffset: `+02:00`, `-05:30`"""
+    sign = "-" if local_minus_utc < 0 else "+"
+    minutes, seconds = divmod(abs(local_minus_utc), 60)
+    hours, minutes = divmod(minutes, 60)
+    text = "%s%02d:%02d" % (sign, hours, minutes)
+    return text + (":%02d" % seconds if seconds else "")
+
+
+def NaiveDateTimeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for chrono::NaiveDateTime
+
+    Args:
+        valobj: The NaiveDateTime value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The date and time like `2024-02-29 12:30:00`
+    """
+    parts = _naive_datetime_parts(valobj.GetNonSyntheticValue())
+    if parts is None:
+        return ""
+    return _format_naive_datetime(*parts)
+
+
+def DateTimeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for chrono::DateTime<Tz>
+
+    The `datetime` field is the UTC time. The `offset` is the time zone's
+    Offset type: a zero-sized Utc, or a FixedOffset { local_minus_utc }
+    for DateTime<FixedOffset> and DateTime<Local>, whose local time is
+    shown with its offset like chrono's Display does. Other time zones
+    show the UTC time.
+
+    Args:
+        valobj: The DateTime value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The time like `2024-02-29 12:30:00 +02:00` or
+        `1999-12-31 23:59:59.500 UTC`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    parts = _naive_datetime_parts(valobj.GetChildMemberWithName("datetime"))
+    if parts is None:
+        return ""
+    secs, frac = parts
+
+    offset = valobj.GetChildMemberWithName("offset")
+    local_minus_utc = offset.GetChildMemberWithName("local_minus_utc")
+    if local_minus_utc.IsValid():
+        shift = local_minus_utc.GetValueAsSigned()
+        return "%s %s" % (
+            _format_naive_datetime(secs + shift, frac),
+            _format_fixed_offset(shift),
+        )
+    return _format_naive_datetime(secs, frac) + " UTC"
+
+
+define_setting(
+    "quat-axis-angle",
+    False,
+    "Show glam quaternions as an axis and an angle in degrees instead of "
+    "their (x, y, z, w) components",
+)
+
+
+def _read_f32s(valobj: SBValue, offset: int, count: int):
+    """
+    Reads count f32 values at offset into valobj
+
+    glam's SIMD types (Quat, Vec3A, Mat3A) wrap an `__m128` or
+    `float32x4_t` whose debug info differs between targets, while the scalar
+    fallback uses named fields. All of them store the components in order,
+    so they are read from memory rather than through the fields.
+
+    Returns:
+        The values, or None if the memory can't be read
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    address = valobj.GetLoadAddress() + offset
+    data = _read_memory(valobj.GetProcess(), address, 4 * count)
+    if data is None:
+        return None
+    order = ">" if valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig else "<"
+    return struct.unpack("%s%df" % (order, count), data)
+
+
+def _f32(value: float) -> float:
+    """Rounds a computed value to f32, for _format_float's single mode"""
+    return struct.unpack("<f", struct.pack("<f", value))[0]
+
+
+def _format_components(values) -> str:
+    return "(%s)" % _join(_format_float(value, True) for value in values)
+
+
+def GlamQuatSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for glam::Quat
+
+    With the quat-axis-angle setting the rotation is shown as its axis and
+    angle like `axis=(0.0, 0.0, 1.0) angle=90.0°`.
+
+    Args:
+        valobj: The Quat value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The components like `(0.0, 0.0, 0.70710677, 0.70710677)`
+    """
+    values = _read_f32s(valobj, 0, 4)
+    if values is None:
+        return ""
+    if not get_setting("quat-axis-angle"):
+        return _format_components(values)
+
+    x, y, z, w = values
+    w = max(-1.0, min(1.0, w))
+    scale = math.sqrt(1.0 - w * w)
+    if scale < 1e-6:
+        # No rotation, any axis will do; glam picks the x axis too
+        axis = (1.0, 0.0, 0.0)
+    else:
+        axis = (_f32(x / scale), _f32(y / scale), _f32(z / scale))
+    angle = _f32(math.degrees(2.0 * math.acos(w)))
+    return "axis=%s angle=%s°" % (_format_components(axis), _format_float(angle, True))
+
+
+def _glam_affine_parts(valobj: SBValue, matrix_name: str, rows: int, stride: int):
+    """
+    Reads the columns and the translation of a glam affine transform
+
+    Affine2 is { matrix2: Mat2, translation: Vec2 } and Affine3A is
+    { matrix3: Mat3A, translation: Vec3A }. The columns are `rows` floats
+    each, `stride` bytes apart: Vec3A columns are padded to 16 bytes.
+
+    Returns:
+        A (columns, translation) tuple, (None, None) if unreadable
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    matrix = valobj.GetChildMemberWithName(matrix_name)
+    translation = valobj.GetChildMemberWithName("translation")
+    if not matrix.IsValid() or not translation.IsValid():
+        return None, None
+
+    base = valobj.GetLoadAddress()
+    columns = []
+    for i in range(rows):
+        column = _read_f32s(valobj, matrix.GetLoadAddress() - base + i * stride, rows)
+        if column is None:
+            return None, None
+        columns.append(column)
+    offset = _read_f32s(valobj, translation.GetLoadAddress() - base, rows)
+    if offset is None:
+        return None, None
+    return columns, offset
+
+
+def _glam_affine_summary(valobj: SBValue, matrix_name: str, rows: int, stride: int):
+    columns, translation = _glam_affine_parts(valobj, matrix_name, rows, stride)
+    if columns is None:
+        return ""
+    return "translation=%s matrix=[%s]" % (
+        _format_components(translation),
+        _join(_format_components(column) for column in columns),
+    )
+
+
+def GlamAffine2SummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for glam::Affine2
+
+    Args:
+        valobj: The Affine2 value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The translation and the matrix columns like
+        `translation=(1.0, 2.0) matrix=[(1.0, 0.0), (0.0, 1.0)]`
+    """
+    return _glam_affine_summary(valobj, "matrix2", 2, 8)
+
+
+def GlamAffine3ASummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for glam::Affine3A
+
+    Args:
+        valobj: The Affine3A value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The translation and the matrix columns like
+        `translation=(1.0, 2.0, 3.0) matrix=[(1.0, 0.0, 0.0), ...]`
+    """
+    return _glam_affine_summary(valobj, "matrix3", 3, 16)
+
+
+def GlamIntVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for glam's integer vectors: IVec3, UVec4, I64Vec2, ...
+
+    Unlike the float types these have no SIMD variant, the components are
+    plain `x`, `y`, `z` and `w` fields. Each one is rendered as the integer
+    it is, in the int-radix setting.
+
+    Args:
+        valobj: The integer vector to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The components like `(1, -2, 3)`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    components = []
+    for name in ("x", "y", "z", "w"):
+        component = valobj.GetChildMemberWithName(name)
+        if not component.IsValid():
+            break
+        components.append(_value_summary(component))
+    return "(%s)" % _join(components) if components else ""
+
+
+def NalgebraMatrixSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for nalgebra matrices with an ArrayStorage
+
+    Vector3<T> and the other fixed size aliases are a Matrix whose `data`
+    is ArrayStorage([[T; R]; C]), the columns in order. Vectors have a
+    single column and show as `(1, 2, 3)`, other matrices list their
+    columns like glam's: `[(1.0, 0.0), (0.0, 1.0)]`. Components keep their
+    own formatting, integers honor int-radix.
+
+    Args:
+        valobj: The matrix to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The components, or "" if the storage isn't an ArrayStorage
+    """
+    storage = valobj.GetNonSyntheticValue().GetChildMemberWithName("data")
+    columns = storage.GetChildMemberWithName("__0")
+    if not columns.IsValid():
+        return ""
+    rendered = []
+    for i in range(columns.GetNumChildren()):
+        column = columns.GetChildAtIndex(i)
+        rendered.append(
+            "(%s)"
+            % _join(
+                _value_summary(column.GetChildAtIndex(j))
+                for j in range(column.GetNumChildren())
+            )
+        )
+    if len(rendered) == 1:
+        return rendered[0]
+    return "[%s]" % _join(rendered)
+
+
+def RatioSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for num_rational::Ratio<T>
+
+    Shows the fraction like Ratio's `Display`: `3/4`, or only the numerator
+    when the denominator is 1. Ratio::new keeps the sign on the numerator,
+    but new_raw can store a negative denominator; the sign is moved over so
+    `3/-4` shows as `-3/4`. The fraction isn't reduced, a `6/8` from new_raw
+    stays `6/8`. Primitive integers are shown in decimal, other components
+    such as BigInt with their own summaries.
+
+    Args:
+        valobj: The Ratio to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The fraction, or "" if the fields can't be found
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    numer = valobj.GetChildMemberWithName("numer")
+    denom = valobj.GetChildMemberWithName("denom")
+    if not numer.IsValid() or not denom.IsValid():
+        return ""
+
+    numer_value = _integer_value(numer)
+    denom_value = _integer_value(denom)
+    if numer_value is None or denom_value is None:
+        return "%s/%s" % (_value_summary(numer), _value_summary(denom))
+    if denom_value < 0:
+        numer_value, denom_value = -numer_value, -denom_value
+    if denom_value == 1:
+        return str(numer_value)
+    return "%d/%d" % (numer_value, denom_value)
+
+
+def _strip_generics(name: str) -> str:
+    """Removes a trailing generic argument list, e.g. `Some<i32>` -> `Some`"""
+    if not name.endswith(">"):
+        return name
+    depth = 0
+    for i in range(len(name) - 1, -1, -1):
+        if name[i] == ">":
+            depth += 1
+        elif name[i] == "<":
+            depth -= 1
+            if depth == 0:
+                return name[:i]
+    return name
+
+
+def _variant_name(variant: SBValue) -> str:
+    """
+    Rust name of an LLDB encoded enum variant, e.g. "Some"
+
+    LLDB names the variant members `$variant$N`; the name from the Rust
+    source is only available as the type name of the `value` payload.
+    """
+    value = variant.GetChildMemberWithName("value")
+    if not value.IsValid():
+        return ""
+    name = _strip_generics(value.GetType().GetUnqualifiedType().GetName() or "")
+    return name.rsplit("::", 1)[-1]
+
+
+def _active_variant(variants: SBValue):
+    """
+    Finds the active member of an LLDB encoded enum's `$variants$` union
+
+    A `$variant$N` member carries a `$discr$` field and is active when the
+    discriminant equals N. With niche layouts the dataful variant has no
+    discriminant value of its own and is active whenever no other variant
+    matches; depending on the LLDB version it's a bare `$variant$` that
+    still carries a `$discr$` field, which then holds the niche itself,
+    e.g. the pointer of an Option<Box<T>>.
+    An enum with a single variant has no tag to read, its only member is
+    always the active one.
+    #[repr(C)] enums keep their tag in a C int in front of the union of the
+    variants; LLDB encodes them the same way, but a negative explicit
+    discriminant is named after its value at the tag's width, so the tag
+    is also compared truncated to its size.
+
+    Returns:
+        The active `$variant$` member, or None if it can't be determined
+    """
+    if variants.GetNumChildren() == 1:
+        return variants.GetChildAtIndex(0)
+
+    default = None
+    for i in range(variants.GetNumChildren()):
+        variant = variants.GetChildAtIndex(i)
+        discr = variant.GetChildMemberWithName("$discr$")
+        if variant.GetName() == "$variant$":
+            default = variant
+        elif discr.IsValid():
+            width = (1 << (8 * discr.GetByteSize())) - 1
+            if variant.GetName() in (
+                "$variant$%d" % discr.GetValueAsUnsigned(),
+                "$variant$%d" % discr.GetValueAsSigned(),
+                "$variant$%d" % (discr.GetValueAsUnsigned() & width),
+            ):
+                return variant
+        else:
+            default = variant
+    return default
+
+
+define_setting(
+    "verbose",
+    False,
+    "Report values the providers can't decode on stderr, such as an enum "
+    "discriminant that matches none of the variants",
+)
+
+# (type name, discriminant) pairs already reported, see _unknown_variant
+_REPORTED_VARIANTS = set()
+
+
+def _unknown_variant(valobj: SBValue):
+    """
+    Marker for an enum whose discriminant matches none of its variants
+
+    That happens with memory that is corrupted, uninitialized or not an
+    enum of this type at all. Picking a variant anyway would show made up
+    fields, so the value is rendered as `<unknown variant #N>` with N the
+    discriminant that was read. With the verbose setting the first such
+    value of each type and discriminant is reported on stderr.
+
+    Returns:
+        The marker, or None if valobj has no discriminant to report
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    variants = valobj.GetChildMemberWithName("$variants$")
+    for i in range(variants.GetNumChildren()):
+        discr = variants.GetChildAtIndex(i).GetChildMemberWithName("$discr$")
+        if discr.IsValid():
+            break
+    else:
+        return None
+
+    value = discr.GetValueAsUnsigned()
+    if get_setting("verbose"):
+        type_name = valobj.GetType().GetUnqualifiedType().GetName() or ""
+        if (type_name, value) not in _REPORTED_VARIANTS:
+            _REPORTED_VARIANTS.add((type_name, value))
+            sys.stderr.write(
+                "rust_bonus_types: %s: discriminant %d at +0x%x matches no "
+                "variant\n"
+                % (
+                    type_name,
+                    value,
+                    discr.GetLoadAddress() - valobj.GetLoadAddress(),
+                )
+            )
+    return "<unknown variant #%d>" % value
+
+
+def _scalar_niche_option(valobj: SBValue):
+    """
+    Decodes Option<bool> and Option<char> from the stored value itself
+
+    Both keep None in a value the payload can never take, 2 for bool and
+    0x110000 for char, rather than in the zero or null pattern the other
+    niche layouts use. Depending on the LLDB version the encoded variants
+    don't describe that niche reliably, so the value is checked directly.
+
+    Returns:
+        A (name, payload) tuple like _option_payload, or None for any other
+        Option
+    """
+    payload_type = valobj.GetType().GetTemplateArgumentType(0)
+    if not payload_type.IsValid():
+        return None
+    if valobj.GetByteSize() != payload_type.GetByteSize():
+        # A separate tag, the regular path handles it
+        return None
+
+    basic_type = payload_type.GetCanonicalType().GetBasicType()
+    if basic_type == lldb.eBasicTypeBool:
+        max_value = 1
+    elif basic_type == lldb.eBasicTypeChar32 or payload_type.GetName() == "char":
+        max_value = 0x10FFFF
+    else:
+        return None
+
+    raw = _raw_bytes(valobj)
+    if not raw:
+        return None
+    big_endian = valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig
+    value = int.from_bytes(raw, "big" if big_endian else "little")
+    if value > max_value:
+        return "None", None
+    return "Some", valobj.CreateValueFromData("__0", valobj.GetData(), payload_type)
+
+
+def _reference_niche_option(valobj: SBValue):
+    """
+    Decodes Option<&T>, Option<&mut T> and Option<&[T]> from the pointer
+
+    References are never null, so None is a null data pointer: the whole
+    value for a thin reference, the first word of a fat one like &[T] or
+    &str, whose length is then undefined. Like the scalar niches, the
+    pointer is checked directly rather than through the encoded variants.
+
+    Returns:
+        A (name, payload) tuple like _option_payload, or None for any other
+        Option
+    """
+    payload_type = valobj.GetType().GetTemplateArgumentType(0)
+    if not payload_type.IsValid():
+        return None
+    if not (payload_type.GetName() or "").startswith("&"):
+        return None
+    if valobj.GetByteSize() != payload_type.GetByteSize():
+        return None
+
+    word = _usize_size(valobj)
+    raw = _raw_bytes(valobj)
+    if not raw or len(raw) < word:
+        return None
+    big_endian = valobj.GetTarget().GetByteOrder() == lldb.eByteOrderBig
+    if int.from_bytes(raw[:word], "big" if big_endian else "little") == 0:
+        return "None", None
+    return "Some", valobj.CreateValueFromData("__0", valobj.GetData(), payload_type)
+
+
+def _option_payload(valobj: SBValue):
+    """
+    Decodes an Option<T> into its variant name and payload
+
+    Returns:
+        A (name, payload) tuple. name is "Some", "None" or "" when the
+        layout isn't recognized; payload is the T of a Some, otherwise None.
+    """
+    valobj = valobj.GetNonSyntheticValue()
+
+    niche = _scalar_niche_option(valobj)
+    if niche is None:
+        niche = _reference_niche_option(valobj)
+    if niche is not None:
+        return niche
+
+    variants = valobj.GetChildMemberWithName("$variants$")
+    if variants.IsValid():
+        variant = _active_variant(variants)
+        if variant is None:
+            return "", None
+
+        value = variant.GetChildMemberWithName("value")
+        name = _variant_name(variant)
+        if name not in ("Some", "None"):
+            # Unnamed payload types: only Some carries a field
+            name = "Some" if value.GetNumChildren() > 0 else "None"
+
+        if name == "Some":
+            return name, value.GetChildAtIndex(0)
+        return name, None
+
+    # Some Rust versions encode the variants as direct members
+    none_variant = valobj.GetChildMemberWithName("None")
+    if none_variant.IsValid():
+        return "None", None
+    some_variant = valobj.GetChildMemberWithName("Some")
+    if some_variant.IsValid():
+        return "Some", some_variant.GetChildAtIndex(0)
+
+    return "", None
+
+
+def OptionSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::option::Option<T>
+
+    Formats Option<T> as "None" or "Some(value)", or the words set with
+    `none-word` and `some-word`. The payload is rendered through its own
+    summary, following Box and references, so nested types like
+    Option<Box<Vec<String>>> format end to end.
+
+    Niche layouts are decoded by _active_variant like any other: for
+    Option<NonZeroU32> the `$discr$` is the integer itself and None is the
+    zero value. For Option<&T> and Option<&[T]> None is a null (data)
+    pointer, which _reference_niche_option checks directly. A
+    payload without a niche, such as [u8; 33], gets a separate tag wherever
+    its alignment and padding leave room; `$discr$` is read at the offset
+    the debug info gives, never assumed at either end of the value.
+
+    Args:
+        valobj: The Option<T> value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        A string representation like "None" or "Some(1.3)"
+    """
+    name, payload = _option_payload(valobj)
+
+    if name == "None":
+        return get_setting("none-word")
+
+    if name == "Some":
+        some = get_setting("some-word")
+        if payload is not None and payload.IsValid():
+            return "%s(%s)" % (some, _value_summary(payload) or "...")
+        return "%s(...)" % some
+
+    unknown = _unknown_variant(valobj)
+    if unknown is not None:
+        return unknown
+
+    # Fallback: try to get summary from first child
+    valobj = valobj.GetNonSyntheticValue()
+    if valobj.GetNumChildren() > 0:
+        first_child = valobj.GetChildAtIndex(0)
+        if first_child.IsValid():
+            return first_child.GetSummary() or str(first_child.GetValue() or "")
+
+    return "<Option>"
+
+
+class OptionSyntheticProvider:
+    """
+    Synthetic provider for core::option::Option<T>
+
+    A Some exposes its payload as the single child `__0`, so expanding it
+    continues into the payload's own providers. None has no children.
+    Layouts that aren't recognized keep their raw children.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.payload = None
+        self.raw = None
+        self.update()
+
+    def num_children(self):
+        if self.raw is not None:
+            return self.raw.GetNumChildren()
+        return 1 if self.payload is not None else 0
+
+    def get_child_index(self, name: str):
+        if self.raw is not None:
+            return self.raw.GetIndexOfChildWithName(name)
+        if name == "__0" and self.payload is not None:
+            return 0
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if self.raw is not None:
+            return self.raw.GetChildAtIndex(index)
+        if index == 0:
+            return self.payload
+        return None
+
+    def update(self):
+        self.payload = None
+        self.raw = None
+
+        try:
+            name, payload = _option_payload(self.valobj)
+            if name == "Some" and payload is not None and payload.IsValid():
+                self.payload = payload
+            elif name == "":
+                self.raw = self.valobj.GetNonSyntheticValue()
+        except Exception:
+            self.payload = None
+            self.raw = None
+
+    def has_children(self):
+        return self.num_children() > 0
+
+
+def _is_thirdparty_type(name: str) -> bool:
+    """
+    Whether a type has a provider in THIRDPARTY_PROVIDERS, enabled or not
+
+    The generic recognizers leave these types alone, whatever the order of
+    the categories, so they keep their own provider or, with their crate
+    disabled, LLDB's default rendering.
+    """
+    return THIRDPARTY_TYPE_RE.search(name) is not None
+
+
+def _has_enum_layout(sbtype) -> bool:
+    """
+    Whether sbtype is laid out like a Rust enum
+
+    LLDB represents a Rust enum as a struct whose only member is the
+    `$variants$` union.
+    """
+    return (
+        sbtype.GetNumberOfFields() == 1
+        and sbtype.GetFieldAtIndex(0).GetName() == "$variants$"
+    )
+
+
+def is_rust_enum(sbtype, _dict) -> bool:
+    """
+    Type recognizer for Rust enums
+
+    Enums of third-party crates with providers of their own and those of
+    the disable-types setting aren't matched.
+    """
+    return (
+        _has_enum_layout(sbtype)
+        and not _is_thirdparty_type(sbtype.GetUnqualifiedType().GetName() or "")
+        and not _is_disabled_type(sbtype)
+    )
+
+
+def _enum_parts(valobj: SBValue):
+    """
+    Decodes a value of an LLDB encoded Rust enum
+
+    Returns:
+        A (name, fields) tuple with the active variant's Rust name and a
+        list of its fields as (name, value) pairs; (None, None) if the
+        active variant can't be determined
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    variants = valobj.GetChildMemberWithName("$variants$")
+    if not variants.IsValid():
+        return None, None
+
+    variant = _active_variant(variants)
+    if variant is None:
+        return None, None
+
+    name = _variant_name(variant) or variant.GetName()
+    payload = variant.GetChildMemberWithName("value")
+    fields = []
+    for i in range(payload.GetNumChildren()):
+        field = payload.GetChildAtIndex(i)
+        fields.append((field.GetName(), field))
+    return name, fields
+
+
+def _is_tuple_variant(fields) -> bool:
+    """Tuple variant fields are named __0, __1, ... in the debug info"""
+    return all(re.match(r"^__\d+$", name or "") for name, _ in fields)
+
+
+# Nesting level of enum and struct summaries; recursive types such as trees
+# render their fields through these providers again, see EnumSummaryProvider
+_ENUM_DEPTH = [0]
+
+ENUM_MAX_DEPTH = 8
+
+
+def EnumSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for Rust enums without a specific provider
+
+    Renders the active variant like Debug does: `Empty`, `Circle(1.5)` or
+    `Rect { w: 2.5, h: 4.0 }`. The variants come from the debug info, so
+    generic enums and enums with any number of variants are handled alike.
+    Recursive enums nested deeper than ENUM_MAX_DEPTH levels show their
+    fields as `...`, a discriminant matching no variant shows as
+    `<unknown variant #N>`.
+
+    Args:
+        valobj: The enum value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The rendered variant, or "" if it can't be determined
+    """
+    name, fields = _enum_parts(valobj)
+    if name is None:
+        return _unknown_variant(valobj) or ""
+    if not fields:
+        return name
+
+    tuple_variant = _is_tuple_variant(fields)
+    if _ENUM_DEPTH[0] >= ENUM_MAX_DEPTH:
+        return "%s(...)" % name if tuple_variant else "%s { ... }" % name
+
+    _ENUM_DEPTH[0] += 1
+    try:
+        if tuple_variant:
+            return "%s(%s)" % (
+                name,
+                _join(_value_summary(field) or "{...}" for _, field in fields),
+            )
+        return _braced(
+            name,
+            _join(
+                _field(field_name, _value_summary(field) or "{...}")
+                for field_name, field in fields
+            ),
+        )
+    finally:
+        _ENUM_DEPTH[0] -= 1
+
+
+class EnumSyntheticProvider:
+    """
+    Synthetic provider for Rust enums without a specific provider
+
+    Exposes the fields of the active variant as children, under their
+    names from the debug info (`w`, `h` for a struct variant, `__0` for a
+    tuple variant).
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.fields = []
+        self.update()
+
+    def num_children(self):
+        return len(self.fields)
+
+    def get_child_index(self, name: str):
+        for i, (field_name, _) in enumerate(self.fields):
+            if field_name == name:
+                return i
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= len(self.fields):
+            return None
+        return self.fields[index][1]
+
+    def update(self):
+        self.fields = []
+
+        try:
+            name, fields = _enum_parts(self.valobj)
+            if name is not None:
+                self.fields = fields
+        except Exception:
+            self.fields = []
+
+    def has_children(self):
+        return len(self.fields) > 0
+
+
+def is_rust_enum_ref(sbtype, _dict) -> bool:
+    """
+    Type recognizer for references to Rust enums, `&Shape` or `&mut Shape`
+
+    Raw pointers aren't matched, they may well dangle.
+    """
+    return (sbtype.GetName() or "").startswith("&") and is_rust_enum(
+        sbtype.GetPointeeType(), _dict
+    )
+
+
+def _enum_pointee(valobj: SBValue):
+    """The enum behind a reference, or None if it can't be read"""
+    pointee = valobj.GetNonSyntheticValue().Dereference()
+    if not pointee.IsValid() or not pointee.GetError().Success():
+        return None
+    return pointee
+
+
+def EnumRefSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for references to Rust enums
+
+    Args:
+        valobj: The reference to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The referenced enum's own summary, e.g. `Circle(1.5)` or `Some(5)`
+    """
+    pointee = _enum_pointee(valobj)
+    if pointee is None:
+        return ""
+    return _value_summary(pointee)
+
+
+class EnumRefSyntheticProvider(EnumSyntheticProvider):
+    """
+    Synthetic provider for references to Rust enums
+
+    Exposes the children the referenced enum itself has, so `&Shape` shows
+    the active variant's fields and `&Option<T>` the Option's children.
+    Subclasses forward to another value by overriding target.
+    """
+
+    def target(self):
+        return _enum_pointee(self.valobj)
+
+    def update(self):
+        self.fields = []
+
+        try:
+            pointee = self.target()
+            if pointee is None:
                 return
+            synthetic = pointee.GetSyntheticValue()
+            if synthetic.IsValid():
+                pointee = synthetic
+            for i in range(pointee.GetNumChildren()):
+                child = pointee.GetChildAtIndex(i)
+                self.fields.append((child.GetName(), child))
+        except Exception:
+            self.fields = []
+
+
+define_setting(
+    "struct-summaries",
+    True,
+    "Summarize structs without a provider of their own on one line like "
+    "Debug does, e.g. `Point { x: 1, y: 2 }`",
+)
+
+# Crates whose structs are implementation details of the types we format;
+# left to LLDB's default rendering instead of the struct summary
+STRUCT_SKIP_PREFIXES = ("core::", "alloc::", "std::")
+
+
+def is_rust_struct(sbtype, _dict) -> bool:
+    """
+    Type recognizer for Rust structs, named ones and tuple structs
+
+    Tuples, closures and other compiler generated types, enums, the
+    standard library's own structs, the third-party types with providers
+    of their own and the types of the disable-types setting aren't matched.
+    The name patterns of the latter two are checked last, after the cheap
+    name and layout checks.
+    """
+    name = sbtype.GetUnqualifiedType().GetName() or ""
+    if not name or name.startswith("(") or "{" in name:
+        return False
+    if name.startswith(STRUCT_SKIP_PREFIXES):
+        return False
+    return (
+        sbtype.GetTypeClass() == lldb.eTypeClassStruct
+        and sbtype.GetNumberOfFields() > 0
+        and not _has_enum_layout(sbtype)
+        and not _is_thirdparty_type(name)
+        and not _is_disabled_type(sbtype)
+    )
+
+
+PHANTOM_TYPE_PREFIX = "core::marker::Phantom"
+
+
+def _is_phantom(field_type) -> bool:
+    """PhantomData<T> and PhantomPinned, zero-sized markers with nothing to show"""
+    name = field_type.GetName() or ""
+    return name.startswith(PHANTOM_TYPE_PREFIX) and field_type.GetByteSize() == 0
+
+
+def has_phantom_fields(sbtype, _dict) -> bool:
+    """Type recognizer for the Rust structs of is_rust_struct with marker fields"""
+    return is_rust_struct(sbtype, _dict) and any(
+        _is_phantom(sbtype.GetFieldAtIndex(i).GetType())
+        for i in range(sbtype.GetNumberOfFields())
+    )
+
+
+def _struct_fields(valobj: SBValue):
+    """The (name, value) pairs of a struct's fields, without marker fields"""
+    fields = []
+    for i in range(valobj.GetNumChildren()):
+        child = valobj.GetChildAtIndex(i)
+        if not _is_phantom(child.GetType()):
+            fields.append((child.GetName(), child))
+    return fields
+
+
+def StructSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for structs without a specific provider
+
+    Renders the fields on one line like `{:?}` does: `Point { x: 1, y: 2 }`
+    or `Meters(5.0)` for a tuple struct. Fields are listed until the
+    preview-budget is used up, the rest are elided as `..`. The fields
+    stay expandable as the regular children. PhantomData and PhantomPinned
+    fields are left out, here and by StructSyntheticProvider.
+
+    Args:
+        valobj: The struct value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The one-line rendering, or "" if struct-summaries is off
+    """
+    if not get_setting("struct-summaries"):
+        return ""
+
+    valobj = valobj.GetNonSyntheticValue()
+    name = _strip_generics(valobj.GetType().GetUnqualifiedType().GetName() or "")
+    name = name.rsplit("::", 1)[-1]
+    fields = _struct_fields(valobj)
+    tuple_struct = _is_tuple_variant(fields)
+    if _ENUM_DEPTH[0] >= ENUM_MAX_DEPTH:
+        return "%s(...)" % name if tuple_struct else "%s { .. }" % name
+
+    budget = get_setting("preview-budget")
+    if get_setting("compact"):
+        budget //= 2
+    parts = []
+    used = 0
+    _ENUM_DEPTH[0] += 1
+    try:
+        for field_name, field in fields:
+            if used >= budget:
+                parts.append("..")
+                break
+            text = _value_summary(field) or "{...}"
+            if not tuple_struct:
+                text = _field(field_name, text)
+            parts.append(text)
+            used += len(text) + 2
+    finally:
+        _ENUM_DEPTH[0] -= 1
+
+    if tuple_struct:
+        return "%s(%s)" % (name, _join(parts))
+    return _braced(name, _join(parts))
+
+
+class StructSyntheticProvider:
+    """
+    Synthetic provider for the structs of has_phantom_fields
+
+    Exposes the fields except the zero-sized markers, so `Typed<T> { id,
+    _marker: PhantomData<T> }` expands to `id` alone.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.fields = []
+        self.update()
+
+    def num_children(self):
+        return len(self.fields)
+
+    def get_child_index(self, name: str):
+        for i, (field_name, _) in enumerate(self.fields):
+            if field_name == name:
+                return i
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if 0 <= index < len(self.fields):
+            return self.fields[index][1]
+        return None
+
+    def update(self):
+        try:
+            self.fields = _struct_fields(self.valobj.GetNonSyntheticValue())
+        except Exception:
+            self.fields = []
+
+    def has_children(self):
+        return len(self.fields) > 0
+
+
+TUPLE_TYPE_PATTERN = r"^\(.+\)$"
+
+
+def TupleSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for tuples
+
+    Renders the elements like Debug does, each through its own summary:
+    `("alpha", 1)`. A tuple of one element keeps its comma, `(5,)`.
+
+    Args:
+        valobj: The tuple to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The elements in parentheses
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    parts = [
+        _value_summary(valobj.GetChildAtIndex(i)) or "{...}"
+        for i in range(valobj.GetNumChildren())
+    ]
+    if len(parts) == 1:
+        return "(%s,)" % parts[0]
+    return "(%s)" % _join(parts)
+
+
+class TupleSyntheticProvider:
+    """
+    Synthetic provider for tuples
+
+    The elements are named `__0`, `__1`, ... in the debug info; they're
+    relabeled `0`, `1`, ... like Rust's `pair.0`, which also makes that
+    path work in `frame variable`. The old names still resolve.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.count = 0
+        self.update()
+
+    def num_children(self):
+        return self.count
+
+    def get_child_index(self, name: str):
+        index = name[2:] if name.startswith("__") else name
+        if index.isdigit() and int(index) < self.count:
+            return int(index)
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if index < 0 or index >= self.count:
+            return None
+        field = self.valobj.GetNonSyntheticValue().GetType().GetFieldAtIndex(index)
+        return self.valobj.CreateChildAtOffset(
+            str(index), field.GetOffsetInBytes(), field.GetType()
+        )
+
+    def update(self):
+        self.count = self.valobj.GetNonSyntheticValue().GetNumChildren()
+
+    def has_children(self):
+        return self.count > 0
+
+
+PIN_TYPE_PATTERN = r"^core::pin::Pin<.+>$"
+
+
+def _pin_pointee(valobj: SBValue):
+    """
+    The value a Pin<P> points to
+
+    The pointer field is `__pointer` since Rust 1.80, `pointer` before.
+    References and Box are followed to their pointee; other pointers such
+    as Rc are returned themselves, their own providers show the value.
+
+    Returns:
+        The pinned value, or None if it can't be read
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    pointer = valobj.GetChildMemberWithName("__pointer")
+    if not pointer.IsValid():
+        pointer = valobj.GetChildMemberWithName("pointer")
+    if not pointer.IsValid():
+        return None
+    if not pointer.TypeIsPointerType():
+        return pointer
+
+    pointee = pointer.Dereference()
+    if not pointee.IsValid() or not pointee.GetError().Success():
+        return None
+    return pointee
+
+
+def PinSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for Pin<P>
+
+    A pinned value formats like the value itself, so `self: Pin<&mut Self>`
+    in a poll method shows the future's fields rather than a wrapper.
+
+    Args:
+        valobj: The Pin value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The pinned value's summary, e.g. `Counter { polls: 2 }`
+    """
+    pointee = _pin_pointee(valobj)
+    if pointee is None:
+        return ""
+    return _value_summary(pointee)
+
+
+class PinSyntheticProvider(EnumRefSyntheticProvider):
+    """
+    Synthetic provider for Pin<P>
+
+    Exposes the children of the pinned value, so its fields expand directly
+    under the Pin.
+    """
+
+    def target(self):
+        return _pin_pointee(self.valobj)
+
+
+CELL_TYPE_PATTERN = r"^core::cell::(Unsafe)?Cell<.+>$"
+
+
+def _cell_value(valobj: SBValue):
+    """
+    The value inside a Cell<T> or UnsafeCell<T>
+
+    Cell { value: UnsafeCell<T> } and UnsafeCell { value: T }.
+
+    Returns:
+        The value, or None if the layout isn't recognized
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    value = valobj.GetChildMemberWithName("value")
+    if not value.IsValid():
+        return None
+    if (value.GetTypeName() or "").startswith("core::cell::UnsafeCell<"):
+        value = value.GetChildMemberWithName("value")
+    return value if value.IsValid() else None
+
+
+def CellSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::cell::Cell<T> and UnsafeCell<T>
+
+    The cell formats like its value, so combinations such as
+    Cell<Option<NonNull<T>>> render as `None` or `Some(0x5555...)`.
+
+    Args:
+        valobj: The cell to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The value's summary
+    """
+    value = _cell_value(valobj)
+    if value is None:
+        return ""
+    return _value_summary(value)
+
+
+class CellSyntheticProvider(EnumRefSyntheticProvider):
+    """
+    Synthetic provider for core::cell::Cell<T> and UnsafeCell<T>
+
+    Exposes the children of the value inside the cell.
+    """
+
+    def target(self):
+        return _cell_value(self.valobj)
+
+
+def _tinyvec_payload(valobj: SBValue):
+    """The ArrayVec or Vec inside a TinyVec, None if unknown"""
+    name, fields = _enum_parts(_referent(valobj))
+    if name not in ("Inline", "Heap") or not fields:
+        return None
+    return fields[0][1]
+
+
+def TinyVecSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for tinyvec::TinyVec<[T; N]>
+
+    TinyVec is `Inline(ArrayVec<[T; N]>)` until it spills to `Heap(Vec<T>)`;
+    the summary is that of the one in use, with `(inline)` or `(heap)` in
+    the metadata setting.
+
+    Args:
+        valobj: The TinyVec to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The elements like "size=2 [1, 2]"
+    """
+    payload = _tinyvec_payload(valobj)
+    if payload is None:
+        return ""
+    summary = _value_summary(payload)
+    if get_setting("metadata"):
+        inline = (payload.GetTypeName() or "").startswith("tinyvec::")
+        summary += " (inline)" if inline else " (heap)"
+    return summary
+
+
+class TinyVecSyntheticProvider(EnumRefSyntheticProvider):
+    """
+    Synthetic provider for tinyvec::TinyVec<[T; N]>
+
+    Exposes the elements of the ArrayVec or Vec in use.
+    """
+
+    def target(self):
+        return _tinyvec_payload(self.valobj)
+
+
+def NonNullSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::ptr::NonNull<T>
+
+    Shows the address like a raw pointer, NonNull may dangle just the same.
+
+    Args:
+        valobj: The NonNull value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The address, e.g. `0x5555555596b0`
+    """
+    return "0x%x" % _unwrap_pointer(valobj.GetNonSyntheticValue())
+
+
+# State machine types generated for async fns and blocks, e.g.
+# `my_crate::fetch::{async_fn_env#0}`. Older toolchains call them generators
+# or coroutines.
+ASYNC_TYPE_PATTERN = (
+    r"^.+::\{(async_fn_env|async_block_env|coroutine_env|generator_env)#[0-9]+\}"
+    r"(<.+>)?$"
+)
+
+# Variant names of the state machine states
+ASYNC_STATE = re.compile(r"^(Unresumed|Returned|Panicked|Suspend[0-9]+)$")
+
+
+def _async_label(name: str) -> str:
+    """Readable name of a state machine field: `__awaitee` -> `awaitee`"""
+    if name.startswith("_ref__"):
+        return name[len("_ref__") :]
+    if name.startswith("__") and not name[2:].isdigit():
+        return name[2:]
+    return name
+
+
+def _async_parts(valobj: SBValue):
+    """
+    Decodes an async state machine, which LLDB presents like an enum
+
+    The variants are the states: `Unresumed`, `Returned`, `Panicked` and
+    `Suspend0`, `Suspend1`, ... for each await point, holding the locals
+    alive there. This relies on compiler internals, so None is returned
+    whenever the layout doesn't look as expected.
+
+    Returns:
+        A (owner, state, fields) tuple, fields being (label, value) pairs
+        with cleaned up labels, or None
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    type_name = valobj.GetType().GetUnqualifiedType().GetName() or ""
+    match = re.match(r"^(.+)::\{(\w+)#[0-9]+\}", _strip_generics(type_name))
+    if match is None:
+        return None
+
+    state, fields = _enum_parts(valobj)
+    if state is None or not ASYNC_STATE.match(state):
+        return None
+
+    if match.group(2).startswith("async_fn"):
+        owner = "async fn %s" % match.group(1).rsplit("::", 1)[-1]
+    else:
+        owner = "async block in %s" % match.group(1).rsplit("::", 1)[-1]
+    return owner, state, [(_async_label(name), field) for name, field in fields]
+
+
+def AsyncSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for async fn and async block futures (best effort)
+
+    Args:
+        valobj: The future to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The function, state and live locals, e.g.
+        `async fn fetch: Suspend0 { id: 3, awaitee: {...} }`, or "" to fall
+        back to the default rendering
+    """
+    parts = _async_parts(valobj)
+    if parts is None:
+        return ""
+    owner, state, fields = parts
+    if not fields:
+        return _field(owner, state)
+    return _field(
+        owner,
+        _braced(
+            state,
+            _join(
+                _field(label, _value_summary(field) or "{...}")
+                for label, field in fields
+            ),
+        ),
+    )
+
+
+class AsyncSyntheticProvider:
+    """
+    Synthetic provider for async fn and async block futures (best effort)
+
+    Exposes the locals of the current state under their cleaned up labels;
+    unrecognized layouts keep their raw children.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.fields = []
+        self.raw = None
+        self.update()
+
+    def num_children(self):
+        if self.raw is not None:
+            return self.raw.GetNumChildren()
+        return len(self.fields)
+
+    def get_child_index(self, name: str):
+        if self.raw is not None:
+            return self.raw.GetIndexOfChildWithName(name)
+        for i, (label, _) in enumerate(self.fields):
+            if label == name:
+                return i
+        return -1
+
+    def get_child_at_index(self, index: int):
+        if self.raw is not None:
+            return self.raw.GetChildAtIndex(index)
+        if index < 0 or index >= len(self.fields):
+            return None
+        label, field = self.fields[index]
+        return self.valobj.CreateValueFromAddress(
+            label, field.GetLoadAddress(), field.GetType()
+        )
+
+    def update(self):
+        self.fields = []
+        self.raw = None
+
+        try:
+            parts = _async_parts(self.valobj)
+            if parts is not None:
+                self.fields = parts[2]
+            else:
+                self.raw = self.valobj.GetNonSyntheticValue()
+        except Exception:
+            self.fields = []
+            self.raw = self.valobj.GetNonSyntheticValue()
+
+    def has_children(self):
+        return self.num_children() > 0
+
+
+def RangeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for the core::ops range types
+
+    Renders the range syntax from whichever of `start` and `end` the type
+    has: `a..b`, `a..=b`, `a..`, `..b`, `..=b` and `..`. Exhausted
+    RangeInclusive iterators are marked like Debug does.
+
+    Args:
+        valobj: The range value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The range in Rust syntax, e.g. "1..5"
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    type_name = _strip_generics(valobj.GetType().GetUnqualifiedType().GetName() or "")
+
+    start = valobj.GetChildMemberWithName("start")
+    end = valobj.GetChildMemberWithName("end")
+    text = "%s..%s%s" % (
+        _value_summary(start) if start.IsValid() else "",
+        "=" if type_name.endswith("Inclusive") else "",
+        _value_summary(end) if end.IsValid() else "",
+    )
+
+    exhausted = valobj.GetChildMemberWithName("exhausted")
+    if exhausted.IsValid() and exhausted.GetValueAsUnsigned():
+        text += " (exhausted)"
+    return text
+
+
+def BoundSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::ops::Bound<T>
+
+    Args:
+        valobj: The Bound value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        `Included(x)`, `Excluded(x)` or `Unbounded`
+    """
+    name, fields = _enum_parts(valobj)
+    if name is None:
+        return _unknown_variant(valobj) or ""
+    if not fields:
+        return name
+    return "%s(%s)" % (name, _value_summary(fields[0][1]) or "{...}")
+
+
+def CowSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for alloc::borrow::Cow<B>
+
+    Registered by name, so Cow composes with the providers around it, e.g.
+    `Arc<Cow<str>>`, on LLDB versions without the generic enum providers.
+
+    Args:
+        valobj: The Cow value to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        `Borrowed(x)` or `Owned(x)` with x in its own summary
+    """
+    name, fields = _enum_parts(valobj)
+    if not name or not fields:
+        return ""
+    return "%s(%s)" % (name, _value_summary(fields[0][1]) or "{...}")
+
+
+# Concrete type names by executable and vtable address, see _vtable_type_name
+_VTABLE_TYPES = {}
+
+
+def _vtable_type_name(target, vtable: int):
+    """
+    Name of the concrete type behind a trait object's vtable
+
+    rustc describes each vtable as a global named `<T as Trait>::{vtable}`.
+    Its symbol usually carries that name; when it doesn't, the debug info
+    variable at the address is looked up with `image lookup`.
+
+    Returns:
+        The type name T, or None if the vtable can't be identified
+    """
+    key = (str(target.GetExecutable()), vtable)
+    if key in _VTABLE_TYPES:
+        return _VTABLE_TYPES[key]
+
+    names = [target.ResolveLoadAddress(vtable).GetSymbol().GetName() or ""]
+    if "{vtable}" not in names[0]:
+        result = lldb.SBCommandReturnObject()
+        target.GetDebugger().GetCommandInterpreter().HandleCommand(
+            "image lookup -v -a 0x%x" % vtable, result
+        )
+        names = re.findall(r'name = "([^"]*\{vtable\})"', result.GetOutput() or "")
+
+    type_name = None
+    for name in names:
+        if name.startswith("<") and name.endswith(">::{vtable}"):
+            type_name = name[1 : -len(">::{vtable}")].rsplit(" as ", 1)[0]
+            break
+    _VTABLE_TYPES[key] = type_name
+    return type_name
+
+
+def _dyn_value(valobj: SBValue):
+    """
+    The concrete value behind Box<dyn Trait>, &dyn Trait or &mut dyn Trait
+
+    Returns:
+        The value typed as its concrete type, or None if it can't be resolved
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    pointer = valobj.GetChildMemberWithName("pointer")
+    vtable = valobj.GetChildMemberWithName("vtable")
+    if not pointer.IsValid() or not vtable.IsValid():
+        return None
+    address = pointer.GetValueAsUnsigned()
+    if address == 0:
+        return None
+
+    target = valobj.GetTarget()
+    type_name = _vtable_type_name(target, vtable.GetValueAsUnsigned())
+    if type_name is None:
+        return None
+    concrete = target.FindFirstType(type_name)
+    if not concrete.IsValid():
+        return None
+    return valobj.CreateValueFromAddress("value", address, concrete)
+
+
+def _concrete_summary(value: SBValue) -> str:
+    """Renders the concrete value of a trait object, see DynSummaryProvider"""
+    text = _value_summary(value)
+    if not text:
+        name = _strip_generics(value.GetTypeName() or "").rsplit("::", 1)[-1]
+        if value.GetNumChildren() == 1:
+            field = value.GetChildAtIndex(0)
+            text = "%s(%s)" % (name, _value_summary(field) or _compact_label(field))
+        else:
+            text = _compact_label(value)
+    return text
+
+
+def DynSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for trait objects: Box<dyn Trait> and &dyn Trait
+
+    The concrete type is found from the vtable and the value rendered with
+    its own summary. Values without one show their type name, wrappers of a
+    single field also the field, e.g. `StringError("missing file")`.
+
+    Args:
+        valobj: The trait object to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The concrete value's summary, or "" if it can't be resolved
+    """
+    value = _dyn_value(valobj)
+    if value is None:
+        return ""
+
+    text = _concrete_summary(value)
+    address = valobj.GetNonSyntheticValue().GetChildMemberWithName("pointer")
+    return text + _address_suffix(valobj, address.GetValueAsUnsigned())
+
+
+class DynSyntheticProvider:
+    """
+    Synthetic provider for trait objects: Box<dyn Trait> and &dyn Trait
+
+    Exposes the concrete value as the only child, `value`.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.value = None
+        self.update()
+
+    def num_children(self):
+        return 1 if self.value is not None else 0
+
+    def get_child_index(self, name: str):
+        return 0 if name == "value" and self.value is not None else -1
+
+    def get_child_at_index(self, index: int):
+        return self.value if index == 0 else None
+
+    def update(self):
+        try:
+            self.value = _dyn_value(self.valobj)
+        except Exception:
+            self.value = None
+
+    def has_children(self):
+        return self.value is not None
+
+
+def _error_kind_name(target, kind: int) -> str:
+    """Name of the std::io::ErrorKind with discriminant kind, e.g. NotFound"""
+    kind_type = target.FindFirstType("std::io::error::ErrorKind")
+    members = kind_type.GetEnumMembers() if kind_type.IsValid() else None
+    if members is not None:
+        for i in range(members.GetSize()):
+            member = members.GetTypeEnumMemberAtIndex(i)
+            if member.GetValueAsUnsigned() == kind:
+                return (member.GetName() or "").rsplit("::", 1)[-1]
+    return str(kind)
+
+
+def _io_error_field_kind(valobj: SBValue) -> str:
+    """Renders the `kind` field of io::Error's Custom or SimpleMessage"""
+    kind = valobj.GetChildMemberWithName("kind")
+    return kind.GetValue() or _error_kind_name(
+        valobj.GetTarget(), kind.GetValueAsUnsigned()
+    )
+
+
+# std::io::ErrorKind of each errno, like std's decode_error_kind on Unix.
+# Names missing from the host's errno module are skipped.
+ERRNO_KINDS = {
+    getattr(errno, name): kind
+    for name, kind in [
+        ("E2BIG", "ArgumentListTooLong"),
+        ("EADDRINUSE", "AddrInUse"),
+        ("EADDRNOTAVAIL", "AddrNotAvailable"),
+        ("EBUSY", "ResourceBusy"),
+        ("ECONNABORTED", "ConnectionAborted"),
+        ("ECONNREFUSED", "ConnectionRefused"),
+        ("ECONNRESET", "ConnectionReset"),
+        ("EDEADLK", "Deadlock"),
+        ("EDQUOT", "QuotaExceeded"),
+        ("EEXIST", "AlreadyExists"),
+        ("EFBIG", "FileTooLarge"),
+        ("EHOSTUNREACH", "HostUnreachable"),
+        ("EINTR", "Interrupted"),
+        ("EINVAL", "InvalidInput"),
+        ("EISDIR", "IsADirectory"),
+        ("ELOOP", "FilesystemLoop"),
+        ("ENOENT", "NotFound"),
+        ("ENOMEM", "OutOfMemory"),
+        ("ENOSPC", "StorageFull"),
+        ("ENOSYS", "Unsupported"),
+        ("EMLINK", "TooManyLinks"),
+        ("ENAMETOOLONG", "InvalidFilename"),
+        ("ENETDOWN", "NetworkDown"),
+        ("ENETUNREACH", "NetworkUnreachable"),
+        ("ENOTCONN", "NotConnected"),
+        ("ENOTDIR", "NotADirectory"),
+        ("ENOTEMPTY", "DirectoryNotEmpty"),
+        ("EPIPE", "BrokenPipe"),
+        ("EROFS", "ReadOnlyFilesystem"),
+        ("ESPIPE", "NotSeekable"),
+        ("ESTALE", "StaleNetworkFileHandle"),
+        ("ETIMEDOUT", "TimedOut"),
+        ("ETXTBSY", "ExecutableFileBusy"),
+        ("EXDEV", "CrossesDevices"),
+        ("EINPROGRESS", "InProgress"),
+        ("EACCES", "PermissionDenied"),
+        ("EPERM", "PermissionDenied"),
+        ("EAGAIN", "WouldBlock"),
+    ]
+    if hasattr(errno, name)
+}
+
+# Target triple words of each host platform, see _host_errno_applies
+HOST_TRIPLE_WORDS = {"linux": "linux", "darwin": "apple", "freebsd": "freebsd"}
+
+
+def _host_errno_applies(target) -> bool:
+    """
+    Whether the host's errno numbers and messages are the target's
+
+    Error codes differ between operating systems, so they are only named
+    when the debugger runs on the same kind of system as the target.
+    """
+    for platform, word in HOST_TRIPLE_WORDS.items():
+        if sys.platform.startswith(platform):
+            return word in (target.GetTriple() or "")
+    return False
+
+
+def _os_error_fields(target, code: int):
+    """The fields of io::Error's Debug for an OS error code"""
+    fields = [_field("code", "%d" % code)]
+    if _host_errno_applies(target):
+        fields.append(_field("kind", ERRNO_KINDS.get(code, "Uncategorized")))
+        fields.append(_field("message", '"%s"' % os.strerror(code)))
+    return _join(fields)
+
+
+def IoErrorSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::io::Error
+
+    On 64-bit targets the error is a single tagged pointer: the low two
+    bits select an OS error code or an ErrorKind in the upper 32 bits, or
+    a pointer to a static SimpleMessage or a boxed Custom error. Other
+    targets store the ErrorData enum, rendered by its own summary.
+    An OS error gets its kind and message from the host's errno table when
+    the host runs the same kind of system as the target.
+
+    Args:
+        valobj: The io::Error to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The error like Debug shows it, e.g. `Os { code: 2, kind: NotFound,
+        message: "No such file or directory" }`, `Kind(NotFound)` or
+        `Custom { kind: Other, error: "..." }`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    repr_field = valobj.GetChildMemberWithName("repr")
+    data = repr_field.GetChildMemberWithName("__0")
+    if not data.IsValid():
+        return ""
+    if data.GetChildMemberWithName("$variants$").IsValid():
+        return _value_summary(data)
+
+    bits = _unwrap_pointer(data)
+    tag = bits & 0b11
+    target = valobj.GetTarget()
+    if tag == 0b10:
+        code = (bits >> 32) & 0xFFFFFFFF
+        code = code - (1 << 32) if code >> 31 else code
+        return _braced("Os", _os_error_fields(target, code))
+    if tag == 0b11:
+        return "Kind(%s)" % _error_kind_name(target, bits >> 32)
+
+    if tag == 0b01:
+        inner_type = target.FindFirstType("std::io::error::Custom")
+    else:
+        inner_type = target.FindFirstType("std::io::error::SimpleMessage")
+    if not inner_type.IsValid():
+        return ""
+    inner = valobj.CreateValueFromAddress("inner", bits & ~0b11, inner_type)
+    if tag == 0b01:
+        error = inner.GetChildMemberWithName("error")
+        return _braced(
+            "Custom",
+            _join(
+                [
+                    _field("kind", _io_error_field_kind(inner)),
+                    _field("error", _value_summary(error) or "{...}"),
+                ]
+            ),
+        )
+    message = _value_summary(inner.GetChildMemberWithName("message")) or "{...}"
+    return _braced(
+        "Error",
+        _join(
+            [_field("kind", _io_error_field_kind(inner)), _field("message", message)]
+        ),
+    )
+
+
+def _anyhow_object(valobj: SBValue):
+    """
+    The error inside an anyhow::Error
+
+    The error is a pointer to an ErrorImpl<E> { vtable, backtrace, _object }
+    typed as ErrorImpl<()>. Its vtable is anyhow's own, not a trait object
+    one, but its functions are instantiated for E, e.g.
+    `object_drop<std::io::error::Error>`, which gives the concrete type.
+
+    Returns:
+        The E, or None if it can't be resolved
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    pointer = valobj.GetChildMemberWithName("inner").GetChildMemberWithName("ptr")
+    address = _unwrap_pointer(pointer)
+    if address == 0:
+        return None
+    erased = pointer.GetChildMemberWithName("pointer").Dereference()
+    vtable = erased.GetChildMemberWithName("vtable").Dereference()
+    drop = vtable.GetChildMemberWithName("object_drop").GetValueAsUnsigned()
+    if drop == 0:
+        return None
+
+    target = valobj.GetTarget()
+    address_of_drop = target.ResolveLoadAddress(drop)
+    names = [
+        address_of_drop.GetFunction().GetDisplayName() or "",
+        address_of_drop.GetSymbol().GetName() or "",
+    ]
+    for name in names:
+        match = re.search(r"object_drop(::)?<(.+)>$", name)
+        if match is None:
+            continue
+        impl_type = target.FindFirstType("anyhow::error::ErrorImpl<%s>" % match[2])
+        if impl_type.IsValid():
+            impl = valobj.CreateValueFromAddress("impl", address, impl_type)
+            return impl.GetChildMemberWithName("_object")
+    return None
+
+
+def AnyhowErrorSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for anyhow::Error
+
+    The wrapped error is rendered like the concrete value of a trait
+    object. The messages of anyhow!("...") and Error::msg are shown
+    without the MessageError or DisplayError wrapping them.
+
+    Args:
+        valobj: The anyhow::Error to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The error's summary, e.g. `"config missing"` or `Os { code: 2, ... }`,
+        or "" if it can't be resolved
+    """
+    value = _anyhow_object(valobj)
+    if value is None or not value.IsValid():
+        return ""
+    name = _strip_generics(value.GetTypeName() or "").rsplit("::", 1)[-1]
+    if name in ("MessageError", "DisplayError"):
+        value = value.GetChildAtIndex(0)
+    return _concrete_summary(value)
+
+
+# Signals with the same number on Linux, macOS and the BSDs
+SIGNAL_NAMES = {
+    1: "SIGHUP",
+    2: "SIGINT",
+    3: "SIGQUIT",
+    4: "SIGILL",
+    5: "SIGTRAP",
+    6: "SIGABRT",
+    8: "SIGFPE",
+    9: "SIGKILL",
+    11: "SIGSEGV",
+    13: "SIGPIPE",
+    14: "SIGALRM",
+    15: "SIGTERM",
+}
+
+
+def _signal_name(signal: int) -> str:
+    return " (%s)" % SIGNAL_NAMES[signal] if signal in SIGNAL_NAMES else ""
+
+
+def _format_wait_status(status: int) -> str:
+    """
+    Decodes a Unix wait status like ExitStatus's Display does
+
+    The low 7 bits are 0 for a normal exit with the code in the next byte,
+    0x7f for a stopped child with the signal in the next byte (0xffff for a
+    continued one), otherwise the terminating signal with bit 7 set if a
+    core was dumped.
+    """
+    signal = status & 0x7F
+    if signal == 0:
+        return "exit status: %d" % ((status >> 8) & 0xFF)
+    if status == 0xFFFF:
+        return "continued (WIFCONTINUED)"
+    if status & 0xFF == 0x7F:
+        stop = (status >> 8) & 0xFF
+        return "stopped (not terminated) by signal: %d%s" % (stop, _signal_name(stop))
+    if signal != 0x7F:
+        core = " (core dumped)" if status & 0x80 else ""
+        return "signal: %d%s%s" % (signal, _signal_name(signal), core)
+    return "unrecognised wait status: %d 0x%x" % (status, status)
+
+
+def ExitStatusSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for std::process::ExitStatus
+
+    On Unix the status is the raw wait status of the child, decoded into
+    its exit code or signal. On Windows it's the exit code itself.
+
+    Args:
+        valobj: The ExitStatus to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The status like Display shows it, e.g. `exit status: 3` or
+        `signal: 9 (SIGKILL)`
+    """
+    status = _innermost(valobj.GetNonSyntheticValue())
+    if status.GetNumChildren() != 0 or not status.GetValue():
+        return ""
+    if "windows" in (valobj.GetTarget().GetTriple() or ""):
+        return "exit code: %d" % status.GetValueAsUnsigned()
+    return _format_wait_status(status.GetValueAsUnsigned() & 0xFFFFFFFF)
+
+
+# Display messages of the number parsing errors by kind
+INT_ERROR_MESSAGES = {
+    "Empty": "cannot parse integer from empty string",
+    "InvalidDigit": "invalid digit found in string",
+    "PosOverflow": "number too large to fit in target type",
+    "NegOverflow": "number too small to fit in target type",
+    "Zero": "number would be zero for non-zero type",
+}
+
+FLOAT_ERROR_MESSAGES = {
+    "Empty": "cannot parse float from empty string",
+    "Invalid": "invalid float literal",
+}
+
+
+def _error_kind(valobj: SBValue) -> str:
+    """The variant name of an error's fieldless `kind` enum, e.g. InvalidDigit"""
+    kind = valobj.GetNonSyntheticValue().GetChildMemberWithName("kind")
+    return (kind.GetValue() or "").rsplit("::", 1)[-1]
+
+
+def NumErrorSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for TryFromIntError, ParseIntError and ParseFloatError
+
+    The errors only hold the kind of failure, shown as the message their
+    Display prints.
+
+    Args:
+        valobj: The error to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The message, e.g. `invalid digit found in string`
+    """
+    name = (valobj.GetType().GetUnqualifiedType().GetName() or "").rsplit("::", 1)
+    if name[-1] == "TryFromIntError":
+        return "out of range integral type conversion attempted"
+    messages = INT_ERROR_MESSAGES
+    if name[-1] == "ParseFloatError":
+        messages = FLOAT_ERROR_MESSAGES
+    kind = _error_kind(valobj)
+    return messages.get(kind, kind)
+
+
+def Utf8ErrorSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for core::str::Utf8Error
+
+    Args:
+        valobj: The error to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The message its Display prints, e.g.
+        `invalid utf-8 sequence of 1 bytes from index 1`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    valid_up_to = valobj.GetChildMemberWithName("valid_up_to")
+    error_len = valobj.GetChildMemberWithName("error_len")
+    if not valid_up_to.IsValid() or not error_len.IsValid():
+        return ""
+    name, payload = _option_payload(error_len)
+    if name == "Some" and payload is not None:
+        return "invalid utf-8 sequence of %d bytes from index %d" % (
+            payload.GetValueAsUnsigned(),
+            valid_up_to.GetValueAsUnsigned(),
+        )
+    return "incomplete utf-8 byte sequence from index %d" % (
+        valid_up_to.GetValueAsUnsigned()
+    )
+
+
+def is_pointer_type(sbtype, _dict) -> bool:
+    """
+    Type recognizer for references, Box and raw pointers
+
+    Registered only while show-addresses is on, see _apply_show_addresses.
+    """
+    return (
+        sbtype.IsPointerType() or sbtype.IsReferenceType()
+    ) and not _is_disabled_type(sbtype)
+
+
+def AddressSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for pointers while show-addresses is on
+
+    References and Box show their pointee's summary followed by the address,
+    raw pointers only the address since they may dangle.
+
+    Args:
+        valobj: The pointer to format
+        _dict: LLDB internal bookkeeping parameter
+
+    Returns:
+        The summary with an address suffix, e.g. `5 @0x5555555596b0`
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    suffix = _address_suffix(valobj)
+    raw = (valobj.GetTypeName() or "").startswith("*")
+    if raw or not valobj.GetValueAsUnsigned():
+        return suffix.lstrip()
+
+    pointee = valobj.Dereference()
+    if not pointee.IsValid() or not pointee.GetError().Success():
+        return suffix.lstrip()
+    return (_value_summary(pointee) + suffix).lstrip()
+
+
+# The LLDB type categories the providers are registered in, so each group can
+# be switched from LLDB with `type category enable/disable <name>`
+STD_CATEGORY = "rust-std"
+THIRDPARTY_CATEGORY = "rust-thirdparty"
+
+# Crates with a category of their own, the others share THIRDPARTY_CATEGORY
+CRATE_CATEGORIES = {"smol-str": "rust-smolstr"}
+
+# Holds the pass-through providers of the disable-types setting
+DISABLED_CATEGORY = "rust-disabled"
+
+CATEGORIES = (
+    (STD_CATEGORY, THIRDPARTY_CATEGORY)
+    + tuple(CRATE_CATEGORIES.values())
+    + (DISABLED_CATEGORY,)
+)
+
+
+def _category(debugger: lldb.SBDebugger, name: str = STD_CATEGORY):
+    """
+    The type category called name, created and enabled on first use
+
+    LLDB searches the most recently enabled category first, so categories
+    created later take precedence over the generic rust-std providers.
+    """
+    category = debugger.GetCategory(name)
+    if not category.IsValid():
+        category = debugger.CreateCategory(name)
+        category.SetEnabled(True)
+    return category
+
+
+def _apply_show_addresses(debugger: lldb.SBDebugger, _setting=None):
+    """
+    Registers or removes AddressSummaryProvider for the pointer types
+
+    Our own providers append the suffix themselves; this covers pointers
+    to everything else. Formatter lookups are cached per type, so the
+    provider is registered with the setting rather than checking it on
+    every lookup.
+    """
+    if not hasattr(lldb, "eFormatterMatchCallback"):
+        return
+
+    category = _category(debugger)
+    specifier = lldb.SBTypeNameSpecifier(
+        "rust_bonus_types.is_pointer_type", lldb.eFormatterMatchCallback
+    )
+    if not get_setting("show-addresses"):
+        category.DeleteTypeSummary(specifier)
+        return
+
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.AddressSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(specifier, summary)
+
+
+define_setting(
+    "show-addresses",
+    False,
+    "Append the address of the referenced data to summaries of references, "
+    "Box, Rc, Arc and raw pointers",
+    on_change=_apply_show_addresses,
+)
+
+
+# User rules map type name regexes to declarative directives, one rule per
+# line of the file named by the `user-rules` setting:
+#
+#     # comment
+#     ^my_crate::Wrapper$    transparent-field:inner
+#     ^my_crate::Id<.+>$     summary-field:0
+#     ^my_crate::Token$      unwrap-single-variant
+#
+# transparent-field:F    summary and children are those of field F
+# summary-field:F        summary is the summary of field F, children unchanged
+# unwrap-single-variant  summary and children are those of the payload of an
+#                        enum with a single variant, like transparent-field
+#
+# Fields are given by name or by index.
+UserRule = namedtuple("UserRule", "regex pattern directive argument")
+
+# Directives by name, with whether they take a `:argument`
+USER_RULE_DIRECTIVES = {
+    "transparent-field": True,
+    "summary-field": True,
+    "unwrap-single-variant": False,
+}
+
+# Directives whose children are those of the field they select
+USER_RULE_TRANSPARENT = ("transparent-field", "unwrap-single-variant")
+
+USER_RULES = []
+
+
+def _parse_user_rules(text: str):
+    """
+    Parses the contents of a user rules file
+
+    Raises:
+        ValueError: naming the offending line
+    """
+    rules = []
+    for lineno, line in enumerate(text.splitlines(), 1):
+        line = line.strip()
+        if not line or line.startswith("#"):
+            continue
+
+        # The regex may contain spaces (`Map<K, V>`), the directive can't
+        parts = line.rsplit(None, 1)
+        if len(parts) != 2:
+            raise ValueError("line %d: expected '<type regex> <directive>'" % lineno)
+        pattern, directive = parts
+
+        name, _, argument = directive.partition(":")
+        if name not in USER_RULE_DIRECTIVES or USER_RULE_DIRECTIVES[name] != bool(
+            argument
+        ):
+            raise ValueError("line %d: unknown directive '%s'" % (lineno, directive))
+
+        try:
+            regex = re.compile(pattern)
+        except re.error as e:
+            raise ValueError("line %d: invalid regex: %s" % (lineno, e))
+
+        rules.append(UserRule(regex, pattern, name, argument))
+    return rules
+
+
+def _load_user_rules(debugger: lldb.SBDebugger, setting: FormatterSetting):
+    """
+    Replaces the registered user rules with the ones from setting.value
+
+    Called when the `user-rules` setting changes.
+    """
+    path = os.path.expanduser(setting.value)
+    rules = []
+    if path:
+        try:
+            with open(path) as f:
+                rules = _parse_user_rules(f.read())
+        except (IOError, OSError) as e:
+            raise ValueError(str(e))
+
+    category = _category(debugger)
+    for rule in USER_RULES:
+        specifier = lldb.SBTypeNameSpecifier(rule.pattern, lldb.eFormatterMatchRegex)
+        category.DeleteTypeSummary(specifier)
+        category.DeleteTypeSynthetic(specifier)
+
+    USER_RULES[:] = rules
+    for rule in rules:
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.UserRuleSummaryProvider"
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(
+            lldb.SBTypeNameSpecifier(rule.pattern, lldb.eFormatterMatchRegex),
+            summary,
+        )
+
+        if rule.directive in USER_RULE_TRANSPARENT:
+            synth = lldb.SBTypeSynthetic.CreateWithClassName(
+                "rust_bonus_types.UserRuleSyntheticProvider"
+            )
+            synth.SetOptions(lldb.eTypeOptionCascade)
+            category.AddTypeSynthetic(
+                lldb.SBTypeNameSpecifier(rule.pattern, lldb.eFormatterMatchRegex),
+                synth,
+            )
+
+
+define_setting(
+    "user-rules",
+    "",
+    "Path of a file with rules mapping type name regexes to formatting "
+    "directives",
+    on_change=_load_user_rules,
+)
+
+
+def _single_variant_payload(valobj: SBValue):
+    """
+    The payload of an enum with exactly one variant
+
+    A variant with a single field, `V(String)`, unwraps to that field;
+    one with several to the variant's fields together.
+
+    Returns:
+        The payload, or None for other enums or a variant without fields
+    """
+    variants = valobj.GetChildMemberWithName("$variants$")
+    if variants.GetNumChildren() != 1:
+        return None
+    payload = variants.GetChildAtIndex(0).GetChildMemberWithName("value")
+    if payload.GetNumChildren() == 1:
+        return payload.GetChildAtIndex(0)
+    if payload.GetNumChildren() > 1:
+        return payload
+    return None
+
+
+def _user_rule_field(valobj: SBValue):
+    """
+    Finds the field named by the first user rule matching valobj's type
+
+    For unwrap-single-variant that is the payload of the enum's variant.
+
+    Returns:
+        The field, or None if no rule applies or the field doesn't exist
+    """
+    valobj = valobj.GetNonSyntheticValue()
+    type_name = valobj.GetType().GetUnqualifiedType().GetName() or ""
+
+    for rule in USER_RULES:
+        if not rule.regex.search(type_name):
+            continue
+        if rule.directive == "unwrap-single-variant":
+            return _single_variant_payload(valobj)
+        if rule.argument.isdigit():
+            field = valobj.GetChildAtIndex(int(rule.argument))
+        else:
+            field = valobj.GetChildMemberWithName(rule.argument)
+        return field if field.IsValid() else None
+    return None
+
+
+def UserRuleSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for types matched by a user rule
+
+    Renders the field named by the rule through that field's own summary.
+    """
+    field = _user_rule_field(valobj)
+    if field is None:
+        return ""
+    return _value_summary(field)
+
+
+class UserRuleSyntheticProvider:
+    """
+    Synthetic provider for types matched by a `transparent-field` or
+    `unwrap-single-variant` user rule
+
+    Shows the children of the selected field in place of the wrapper's own.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.field = None
+        self.update()
+
+    def num_children(self):
+        if self.field is None:
+            return 0
+        return self.field.GetNumChildren()
+
+    def get_child_index(self, name: str):
+        if self.field is None:
+            return -1
+        return self.field.GetIndexOfChildWithName(name)
+
+    def get_child_at_index(self, index: int):
+        if self.field is None:
+            return None
+        return self.field.GetChildAtIndex(index)
+
+    def update(self):
+        self.field = None
+
+        try:
+            field = _user_rule_field(self.valobj)
+            if field is not None:
+                # Prefer the field's own synthetic children, if it has any
+                synthetic = field.GetSyntheticValue()
+                self.field = synthetic if synthetic.IsValid() else field
+        except Exception:
+            self.field = None
+
+    def has_children(self):
+        return self.num_children() > 0
+
+
+# Type name regexes registered for the disable-types setting
+DISABLED_TYPES = []
+
+# Syntax of Python's re that LLDB's POSIX extended regexes don't have: class
+# escapes like \d, groups starting with (? and lazy quantifiers
+_PYTHON_ONLY_REGEX = re.compile(r"\\[dDwWsSbBAZ]|\(\?|[*+?}]\?")
+
+
+def _is_disabled_type(sbtype) -> bool:
+    """
+    Whether the disable-types setting leaves sbtype to LLDB's default
+    formatting
+
+    Checked by our providers themselves, so the setting also holds when
+    another category is searched before rust-disabled. References and
+    pointers are matched by their pointee too, as LLDB does.
+    """
+    if not DISABLED_TYPES:
+        return False
+    names = [sbtype.GetUnqualifiedType().GetName() or ""]
+    if sbtype.IsPointerType() or sbtype.IsReferenceType():
+        names.append(sbtype.GetPointeeType().GetUnqualifiedType().GetName() or "")
+    return any(
+        re.search(pattern, name) for pattern in DISABLED_TYPES for name in names
+    )
+
+
+def _split_type_patterns(text: str):
+    """
+    Splits a comma-separated list of type name regexes
+
+    Only commas outside brackets separate patterns, so `HashMap<K, V>` and
+    `[0-9]{1,3}` stay whole.
+    """
+    patterns = []
+    depth = 0
+    start = 0
+    for i, char in enumerate(text):
+        if char in "<([{":
+            depth += 1
+        elif char in ">)]}":
+            depth = max(depth - 1, 0)
+        elif char == "," and depth == 0:
+            patterns.append(text[start:i])
+            start = i + 1
+    patterns.append(text[start:])
+    return [pattern.strip() for pattern in patterns if pattern.strip()]
+
+
+def _apply_disabled_types(debugger: lldb.SBDebugger, setting: FormatterSetting):
+    """
+    Registers the pass-through providers for the `disable-types` setting
+
+    The patterns are POSIX extended regexes, as LLDB matches them; those
+    using syntax only Python's re has are rejected. They go into a
+    category of their own with DisabledTypeSummaryProvider and
+    DisabledTypeSyntheticProvider, which show the value the way LLDB does
+    without formatters. LLDB searches the most recently enabled category
+    first, so one enabled later may still match these types; our
+    providers check _is_disabled_type and step aside themselves.
+    """
+    patterns = _split_type_patterns(setting.value)
+    for pattern in patterns:
+        if _PYTHON_ONLY_REGEX.search(pattern):
+            raise ValueError(
+                "invalid regex '%s': uses Python regex syntax, LLDB matches "
+                "POSIX extended regexes" % pattern
+            )
+        try:
+            re.compile(pattern)
+        except re.error as e:
+            raise ValueError("invalid regex '%s': %s" % (pattern, e))
+
+    category = _category(debugger, DISABLED_CATEGORY)
+    for pattern in DISABLED_TYPES:
+        specifier = lldb.SBTypeNameSpecifier(pattern, lldb.eFormatterMatchRegex)
+        category.DeleteTypeSummary(specifier)
+        category.DeleteTypeSynthetic(specifier)
+
+    DISABLED_TYPES[:] = patterns
+    for pattern in patterns:
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.DisabledTypeSummaryProvider"
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(
+            lldb.SBTypeNameSpecifier(pattern, lldb.eFormatterMatchRegex), summary
+        )
+
+        synth = lldb.SBTypeSynthetic.CreateWithClassName(
+            "rust_bonus_types.DisabledTypeSyntheticProvider"
+        )
+        synth.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSynthetic(
+            lldb.SBTypeNameSpecifier(pattern, lldb.eFormatterMatchRegex), synth
+        )
+
+    category.SetEnabled(False)
+    category.SetEnabled(True)
+
+
+define_setting(
+    "disable-types",
+    "",
+    "Comma-separated type name regexes (POSIX extended) to leave to LLDB's "
+    "default formatting, for types whose layout is decoded wrongly",
+    on_change=_apply_disabled_types,
+)
+
+
+def DisabledTypeSummaryProvider(valobj: SBValue, _dict) -> str:
+    """
+    Summary provider for types matched by the disable-types setting
+
+    Scalars keep their plain value, other types get no summary, as without
+    formatters.
+    """
+    return ""
+
+
+class DisabledTypeSyntheticProvider:
+    """
+    Synthetic provider for types matched by the disable-types setting
+
+    Shows the raw fields of the value, as without formatters.
+    """
+
+    def __init__(self, valobj: SBValue, _dict):
+        self.valobj = valobj
+        self.raw = valobj.GetNonSyntheticValue()
+
+    def num_children(self):
+        return self.raw.GetNumChildren()
+
+    def get_child_index(self, name: str):
+        return self.raw.GetIndexOfChildWithName(name)
+
+    def get_child_at_index(self, index: int):
+        return self.raw.GetChildAtIndex(index)
+
+    def update(self):
+        self.raw = self.valobj.GetNonSyntheticValue()
+
+    def has_children(self):
+        return self.raw.MightHaveChildren()
+
+
+def RustFormatterSetCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-set <name> <value>`
+
+    Changes one of the formatter settings. Printing is not cached, so the
+    new value applies to the next `frame variable` or `p`.
+    """
+    args = command.split(None, 1)
+    if len(args) != 2:
+        result.SetError("usage: rust-formatter-set <name> <value>")
+        return
+
+    name = setting_name(args[0])
+    setting = SETTINGS.get(name)
+    if setting is None:
+        result.SetError("unknown setting '%s%s'" % (SETTINGS_PREFIX, name))
+        return
+
+    previous = setting.value
+    try:
+        setting.value = setting.parse(args[1])
+        if setting.on_change is not None:
+            setting.on_change(debugger, setting)
+    except ValueError as e:
+        setting.value = previous
+        result.SetError("invalid value for '%s%s': %s" % (SETTINGS_PREFIX, name, e))
+        return
+
+    result.AppendMessage("%s%s = %s" % (SETTINGS_PREFIX, name, setting.value))
+
+
+def _setting_text(value) -> str:
+    """Renders a setting value the way rust-formatter-set accepts it"""
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    if isinstance(value, str):
+        return '"%s"' % value
+    return str(value)
+
+
+def RustFormatterSettingsCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-settings`
+
+    Lists every setting of the SETTINGS registry with its current value,
+    its default and its description, sorted by name.
+    """
+    for name in sorted(SETTINGS):
+        setting = SETTINGS[name]
+        result.AppendMessage(
+            "%s%s = %s (default: %s)"
+            % (
+                SETTINGS_PREFIX,
+                name,
+                _setting_text(setting.value),
+                _setting_text(setting.default),
+            )
+        )
+        result.AppendMessage("    %s" % setting.description)
+
+
+def _resolve_expression(debugger: lldb.SBDebugger, expr: str, result):
+    """
+    Resolves a command argument to a value in the selected frame.
+
+    Variable paths are tried first so the value keeps its memory location,
+    anything else is evaluated as an expression.
+    """
+    frame = (
+        debugger.GetSelectedTarget()
+        .GetProcess()
+        .GetSelectedThread()
+        .GetSelectedFrame()
+    )
+    if not frame.IsValid():
+        result.SetError("no selected frame, is the process stopped?")
+        return None
+
+    valobj = frame.GetValueForVariablePath(expr)
+    if not valobj.IsValid() or valobj.GetError().Fail():
+        valobj = frame.EvaluateExpression(expr)
+    if not valobj.IsValid() or valobj.GetError().Fail():
+        result.SetError("could not resolve '%s': %s" % (expr, valobj.GetError()))
+        return None
+    return valobj
+
+
+def _describe_fields(valobj: SBValue, offset: int, depth: int, lines, discriminants):
+    """
+    Appends one line per field of valobj, recursing into aggregate fields.
+
+    Offsets come from the debug info and are relative to the inspected value.
+    Every `$discr$` field found on the way is recorded in discriminants.
+    """
+    valtype = valobj.GetType()
+    for i in range(valtype.GetNumberOfFields()):
+        field = valtype.GetFieldAtIndex(i)
+        field_type = field.GetType()
+        field_offset = offset + field.GetOffsetInBytes()
+        child = valobj.GetChildMemberWithName(field.GetName())
+
+        lines.append(
+            "%s+0x%02x %s: %s (%d bytes)"
+            % (
+                "  " * depth,
+                field_offset,
+                field.GetName(),
+                field_type.GetName(),
+                field_type.GetByteSize(),
+            )
+        )
+
+        if field.GetName() == "$discr$" and child.IsValid():
+            discriminants.append(
+                (field_offset, field_type.GetByteSize(), child.GetValueAsUnsigned())
+            )
+
+        if depth < 6 and child.IsValid() and field_type.GetNumberOfFields() > 0:
+            _describe_fields(child, field_offset, depth + 1, lines, discriminants)
+
+
+def RustFormatterDebugCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-debug <expr>`
+
+    Prints what the formatter sees for a value: the resolved type, the
+    summary and synthetic providers LLDB matched, the field layout with
+    offsets and the location of any enum discriminants. The synthetic
+    children are listed as well, which shows the decisions a provider made
+    (e.g. whether a SmolStr was decoded as Inline, Static or Heap).
+    """
+    expr = command.strip()
+    if not expr:
+        result.SetError("usage: rust-formatter-debug <expr>")
+        return
+
+    valobj = _resolve_expression(debugger, expr, result)
+    if valobj is None:
+        return
+
+    raw = valobj.GetNonSyntheticValue()
+    valtype = raw.GetType()
+
+    result.AppendMessage(
+        "type: %s (%d bytes)" % (valtype.GetName(), valtype.GetByteSize())
+    )
+    warning = LAYOUT_WARNINGS.get(valtype.GetUnqualifiedType().GetName())
+    if warning is not None:
+        result.AppendMessage("warning: %s" % warning)
+    address = raw.GetLoadAddress()
+    if address != lldb.LLDB_INVALID_ADDRESS:
+        result.AppendMessage("address: 0x%x" % address)
+
+    summary = valobj.GetTypeSummary()
+    if summary.IsValid():
+        result.AppendMessage("summary provider: %s" % summary.GetData())
+    else:
+        result.AppendMessage("summary provider: <none>")
+
+    synthetic = valobj.GetTypeSynthetic()
+    if synthetic.IsValid():
+        result.AppendMessage("synthetic provider: %s" % synthetic.GetData())
+    else:
+        result.AppendMessage("synthetic provider: <none>")
+
+    result.AppendMessage("summary: %s" % (valobj.GetSummary() or "<none>"))
+
+    lines = []
+    discriminants = []
+    _describe_fields(raw, 0, 1, lines, discriminants)
+    result.AppendMessage("fields:")
+    for line in lines:
+        result.AppendMessage(line)
+
+    if discriminants:
+        for offset, size, value in discriminants:
+            result.AppendMessage(
+                "discriminant: +0x%02x (%d bytes) = %d" % (offset, size, value)
+            )
+    else:
+        result.AppendMessage("discriminant: <none>")
+
+    if synthetic.IsValid():
+        synth = raw.GetSyntheticValue()
+        result.AppendMessage("synthetic children:")
+        for i in range(synth.GetNumChildren()):
+            child = synth.GetChildAtIndex(i)
+            result.AppendMessage(
+                "  %s = %s"
+                % (child.GetName(), child.GetSummary() or child.GetValue() or "")
+            )
+
+
+define_setting(
+    "max-depth",
+    8,
+    "Levels of children rust-formatter-json descends into and of collections "
+    "previewed inside a summary, 0 for no limit",
+)
+
+
+def _json_tree(valobj: SBValue, depth: int):
+    """
+    Serializes a value and its children for rust-formatter-json
+
+    Children are the synthetic ones where a provider applies, capped by the
+    max-children settings like LLDB's own output. Values whose children
+    are below max-depth are marked `"truncated": true`.
+
+    Returns:
+        A dict with the name, type, summary and value (None if LLDB has
+        none) and the list of children
+    """
+    node = {
+        "name": valobj.GetName(),
+        "type": valobj.GetTypeName(),
+        "summary": valobj.GetSummary(),
+        "value": valobj.GetValue(),
+        "children": [],
+    }
+    count = valobj.GetNumChildren()
+    if count == 0:
+        return node
+
+    max_depth = get_setting("max-depth")
+    if max_depth > 0 and depth >= max_depth:
+        node["truncated"] = True
+        return node
+
+    for i in range(_max_children(valobj, count)):
+        child = valobj.GetChildAtIndex(i)
+        if child.IsValid():
+            node["children"].append(_json_tree(child, depth + 1))
+    return node
+
+
+def RustFormatterJsonCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-json <expr>`
+
+    Prints a value as a JSON tree of its type, summary, value and children,
+    for tools and test harnesses that want the formatted structure rather
+    than LLDB's text output. The same providers apply as when printing.
+    """
+    expr = command.strip()
+    if not expr:
+        result.SetError("usage: rust-formatter-json <expr>")
+        return
+
+    valobj = _resolve_expression(debugger, expr, result)
+    if valobj is None:
+        return
+    tree = _json_tree(valobj, 0)
+    result.AppendMessage(json.dumps(tree, indent=2, ensure_ascii=False))
+
+
+# Summaries rust-formatter-selftest expects for the variables in main() of
+# the debug_test crate, with the settings at their defaults
+SELFTEST_EXPECTATIONS = (
+    ("inline_empty", '""'),
+    ("inline_short", '"hello"'),
+    ("inline_medium", '"hello world!"'),
+    ("static_str", '"static string"'),
+    ("heap_long", '"this is a very long string that will be heap allocated"'),
+    ("heap_repeated", '"%s"' % ("x" * 100)),
+    ("inline_inline", '"inline"'),
+    ("from_string", '"from string"'),
+    ("inline_smallvec", "size=2 [1, 2]"),
+    ("heap_smallvec", "size=4 [1, 2, 4, 5]"),
+    ("empty_smallvec", "size=0 []"),
+    ("single_smallvec", "size=1 [42]"),
+    ("zero_cap_empty", "size=0 []"),
+    ("zero_cap_spilled", "size=3 [7, 8, 9]"),
+    ("test_vec", "size=3 [10, 20, 30]"),
+    ("some_f32", "Some(1.3)"),
+    ("none_f32", "None"),
+    ("some_i32", "Some(42)"),
+    ("none_i32", "None"),
+    ("some_string", 'Some("hello")'),
+    ("none_string", "None"),
+    ("some_bool", "Some(true)"),
+    ("none_bool", "None"),
+)
+
+
+def RustFormatterSelftestCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-selftest`
+
+    Run while stopped at the breakpoint of the debug_test crate. Compares
+    the summaries of its sample values in main() against
+    SELFTEST_EXPECTATIONS and prints one line per check, so a bug report
+    can include exactly what this installation renders. The command fails
+    if any check does, which makes `lldb --batch` usable in CI.
+    """
+    thread = debugger.GetSelectedTarget().GetProcess().GetSelectedThread()
+    frame = None
+    for i in range(thread.GetNumFrames()):
+        candidate = thread.GetFrameAtIndex(i)
+        if candidate.FindVariable(SELFTEST_EXPECTATIONS[0][0]).IsValid():
+            frame = candidate
+            break
+    if frame is None:
+        result.SetError(
+            "no frame with the debug_test variables, stop at its breakpoint first"
+        )
+        return
+
+    if any(setting.value != setting.default for setting in SETTINGS.values()):
+        result.AppendMessage("note: settings differ from their defaults")
+
+    failures = 0
+    for name, expected in SELFTEST_EXPECTATIONS:
+        valobj = frame.FindVariable(name)
+        actual = (valobj.GetSummary() or "") if valobj.IsValid() else None
+        if actual == expected:
+            result.AppendMessage("PASS %s = %s" % (name, actual))
+            continue
+        failures += 1
+        if actual is None:
+            result.AppendMessage("FAIL %s: variable not found" % name)
+        else:
+            result.AppendMessage(
+                "FAIL %s: expected %s, got %s" % (name, expected, actual)
+            )
+
+    passed = len(SELFTEST_EXPECTATIONS) - failures
+    result.AppendMessage("selftest: %d passed, %d failed" % (passed, failures))
+    if failures:
+        result.SetError("%d selftest checks failed" % failures)
+
+
+def RustFormatterVersionCommand(debugger, command, result, _dict):
+    """
+    Implements `rust-formatter-version`
+
+    Prints the formatter version and the providers of this module that are
+    currently registered, each with the type pattern it matches. Disabled
+    crates are left out and user rules show up, so this is what applies
+    right now.
+    """
+    result.AppendMessage("rust_bonus_types %s" % VERSION)
+
+    categories = [debugger.GetCategory(name) for name in CATEGORIES]
+    categories = [category for category in categories if category.IsValid()]
+    if not categories:
+        result.SetError("the rust-std category does not exist")
+        return
+
+    prefix = "rust_bonus_types."
+    result.AppendMessage("summary providers:")
+    for category in categories:
+        for i in range(category.GetNumSummaries()):
+            provider = category.GetSummaryAtIndex(i).GetData() or ""
+            if provider.startswith(prefix):
+                pattern = category.GetTypeNameSpecifierForSummaryAtIndex(i).GetName()
+                result.AppendMessage(
+                    "  %s: %s (%s)"
+                    % (provider[len(prefix) :], pattern, category.GetName())
+                )
+
+    result.AppendMessage("synthetic providers:")
+    for category in categories:
+        for i in range(category.GetNumSynthetics()):
+            provider = category.GetSyntheticAtIndex(i).GetData() or ""
+            if provider.startswith(prefix):
+                pattern = category.GetTypeNameSpecifierForSyntheticAtIndex(i).GetName()
+                result.AppendMessage(
+                    "  %s: %s (%s)"
+                    % (provider[len(prefix) :], pattern, category.GetName())
+                )
+
+
+# Providers for types of third-party crates, by the crate's settings name.
+# Their layouts follow the crate version, so a mismatching dependency could
+# be misread; each crate can be switched off with its `enable-<crate>`
+# setting, or all of them at once with `enable-thirdparty`.
+THIRDPARTY_PROVIDERS = {
+    "smol-str": [
+        (
+            "smol_str::SmolStr",
+            lldb.eFormatterMatchExact,
+            "SmolStrSummaryProvider",
+            "SmolStrSyntheticProvider",
+        ),
+    ],
+    "compact-str": [
+        (
+            "compact_str::CompactString",
+            lldb.eFormatterMatchExact,
+            "CompactStringSummaryProvider",
+            None,
+        ),
+    ],
+    "ecow": [
+        (
+            "ecow::string::EcoString",
+            lldb.eFormatterMatchExact,
+            "EcoStringSummaryProvider",
+            None,
+        ),
+    ],
+    "smallvec": [
+        (
+            "^smallvec::SmallVec<.+>$",
+            lldb.eFormatterMatchRegex,
+            "SmallVecSummaryProvider",
+            "SmallVecSyntheticProvider",
+        ),
+    ],
+    "indexmap": [
+        (
+            "^indexmap::set::IndexSet<.+>$",
+            lldb.eFormatterMatchRegex,
+            "IndexSetSummaryProvider",
+            "IndexSetSyntheticProvider",
+        ),
+        (
+            "^indexmap::map::IndexMap<.+>$",
+            lldb.eFormatterMatchRegex,
+            "IndexMapSummaryProvider",
+            "IndexMapSyntheticProvider",
+        ),
+    ],
+    "hashbrown": [
+        (
+            "^hashbrown::map::HashMap<.+>$",
+            lldb.eFormatterMatchRegex,
+            "HashMapSummaryProvider",
+            "HashMapSyntheticProvider",
+        ),
+        (
+            "^hashbrown::set::HashSet<.+>$",
+            lldb.eFormatterMatchRegex,
+            "HashSetSummaryProvider",
+            "HashSetSyntheticProvider",
+        ),
+    ],
+    "ahash": [
+        (
+            "^ahash::hash_map::AHashMap<.+>$",
+            lldb.eFormatterMatchRegex,
+            "HashMapSummaryProvider",
+            "HashMapSyntheticProvider",
+        ),
+        (
+            "^ahash::hash_set::AHashSet<.+>$",
+            lldb.eFormatterMatchRegex,
+            "HashSetSummaryProvider",
+            "HashSetSyntheticProvider",
+        ),
+    ],
+    "arrayvec": [
+        (
+            "^arrayvec::array_string::ArrayString<.+>$",
+            lldb.eFormatterMatchRegex,
+            "ArrayStringSummaryProvider",
+            None,
+        ),
+        (
+            "^arrayvec::arrayvec::ArrayVec<.+>$",
+            lldb.eFormatterMatchRegex,
+            "ArrayVecSummaryProvider",
+            "ArrayVecSyntheticProvider",
+        ),
+    ],
+    "bstr": [
+        (
+            "^(bstr::bstring::BString|&(mut )?bstr::bstr::BStr)$",
+            lldb.eFormatterMatchRegex,
+            "BStringSummaryProvider",
+            None,
+        ),
+    ],
+    "once-cell": [
+        (
+            "^once_cell::(sync|unsync)::(OnceCell|Lazy)<.+>$",
+            lldb.eFormatterMatchRegex,
+            "OnceSummaryProvider",
+            "OnceSyntheticProvider",
+        ),
+    ],
+    "chrono": [
+        (
+            "^chrono::naive::(date::)?NaiveDate$",
+            lldb.eFormatterMatchRegex,
+            "NaiveDateSummaryProvider",
+            None,
+        ),
+        (
+            "^chrono::naive::datetime::NaiveDateTime$",
+            lldb.eFormatterMatchRegex,
+            "NaiveDateTimeSummaryProvider",
+            None,
+        ),
+        (
+            "^chrono::datetime::DateTime<.+>$",
+            lldb.eFormatterMatchRegex,
+            "DateTimeSummaryProvider",
+            None,
+        ),
+        (
+            "^chrono::(time_delta::TimeDelta|duration::Duration)$",
+            lldb.eFormatterMatchRegex,
+            "ChronoDurationSummaryProvider",
+            None,
+        ),
+    ],
+    "glam": [
+        (
+            "^glam::f32::([a-z0-9]+::)?quat::Quat$",
+            lldb.eFormatterMatchRegex,
+            "GlamQuatSummaryProvider",
+            None,
+        ),
+        (
+            "^glam::f32::affine2::Affine2$",
+            lldb.eFormatterMatchRegex,
+            "GlamAffine2SummaryProvider",
+            None,
+        ),
+        (
+            "^glam::f32::affine3a::Affine3A$",
+            lldb.eFormatterMatchRegex,
+            "GlamAffine3ASummaryProvider",
+            None,
+        ),
+        (
+            "^glam::[iu](8|16|32|64)::[a-z0-9]+::[IU](8|16|64)?Vec[234]$",
+            lldb.eFormatterMatchRegex,
+            "GlamIntVecSummaryProvider",
+            None,
+        ),
+    ],
+    "anyhow": [
+        (
+            "anyhow::Error",
+            lldb.eFormatterMatchExact,
+            "AnyhowErrorSummaryProvider",
+            None,
+        ),
+    ],
+    "nalgebra": [
+        (
+            "^nalgebra::base::matrix::Matrix<.+, "
+            "nalgebra::base::array_storage::ArrayStorage<.+>>$",
+            lldb.eFormatterMatchRegex,
+            "NalgebraMatrixSummaryProvider",
+            None,
+        ),
+    ],
+    "bitvec": [
+        (
+            "^(bitvec::vec::BitVec<.+>|&(mut )?bitvec::slice::BitSlice<.+>)$",
+            lldb.eFormatterMatchRegex,
+            "BitVecSummaryProvider",
+            "BitVecSyntheticProvider",
+        ),
+    ],
+    "tinyvec": [
+        (
+            "^tinyvec::arrayvec::ArrayVec<.+>$",
+            lldb.eFormatterMatchRegex,
+            "ArrayVecSummaryProvider",
+            "ArrayVecSyntheticProvider",
+        ),
+        (
+            "^tinyvec::tinyvec::TinyVec<.+>$",
+            lldb.eFormatterMatchRegex,
+            "TinyVecSummaryProvider",
+            "TinyVecSyntheticProvider",
+        ),
+    ],
+    "num-rational": [
+        (
+            "^num_rational::Ratio<.+>$",
+            lldb.eFormatterMatchRegex,
+            "RatioSummaryProvider",
+            None,
+        ),
+    ],
+}
+
+# One pattern matching the types of all of THIRDPARTY_PROVIDERS, for the
+# recognizers checking many types
+THIRDPARTY_TYPE_RE = re.compile(
+    "|".join(
+        "(?:%s)"
+        % (
+            pattern
+            if match == lldb.eFormatterMatchRegex
+            else "^%s$" % re.escape(pattern)
+        )
+        for providers in THIRDPARTY_PROVIDERS.values()
+        for pattern, match, _summary_name, _synth_name in providers
+    )
+)
+
+
+def _apply_thirdparty(debugger: lldb.SBDebugger, _setting=None):
+    """
+    Registers the third-party providers of enabled crates and removes the
+    others, so disabled types fall back to LLDB's default rendering; the
+    generic struct and enum recognizers skip them, see _is_thirdparty_type
+
+    Called on load and when one of the enable settings changes.
+    """
+    for crate, providers in THIRDPARTY_PROVIDERS.items():
+        category = _category(debugger, CRATE_CATEGORIES.get(crate, THIRDPARTY_CATEGORY))
+        enabled = get_setting("enable-thirdparty") and get_setting("enable-" + crate)
+        for type_name, match, summary_name, synth_name in providers:
+            specifier = lldb.SBTypeNameSpecifier(type_name, match)
+            if not enabled:
+                category.DeleteTypeSummary(specifier)
+                if synth_name is not None:
+                    category.DeleteTypeSynthetic(specifier)
+                continue
+
+            summary = lldb.SBTypeSummary.CreateWithFunctionName(
+                "rust_bonus_types.%s" % summary_name
+            )
+            summary.SetOptions(lldb.eTypeOptionCascade)
+            category.AddTypeSummary(specifier, summary)
+
+            if synth_name is not None:
+                synth = lldb.SBTypeSynthetic.CreateWithClassName(
+                    "rust_bonus_types.%s" % synth_name
+                )
+                synth.SetOptions(lldb.eTypeOptionCascade)
+                category.AddTypeSynthetic(specifier, synth)
+
+
+define_setting(
+    "enable-thirdparty",
+    True,
+    "Use the providers for types of third-party crates",
+    on_change=_apply_thirdparty,
+)
+
+for _crate in THIRDPARTY_PROVIDERS:
+    define_setting(
+        "enable-" + _crate,
+        True,
+        "Use the providers for types of the %s crate" % _crate.replace("-", "_"),
+        on_change=_apply_thirdparty,
+    )
+
+
+def _disabled_types_aside(function):
+    """
+    Wraps a summary provider to render the types of disable-types like
+    DisabledTypeSummaryProvider
+    """
+
+    @functools.wraps(function)
+    def summary(valobj: SBValue, _dict) -> str:
+        if _is_disabled_type(valobj.GetType()):
+            return DisabledTypeSummaryProvider(valobj, _dict)
+        return function(valobj, _dict)
+
+    return summary
+
+
+def _new_synthetic_provider(cls, valobj: SBValue, _dict=None):
+    """
+    __new__ of our synthetic providers, a DisabledTypeSyntheticProvider for
+    the types of disable-types
+
+    Only when LLDB creates the provider; the ones our code creates for its
+    own reads pass None for _dict and always get cls.
+    """
+    if _dict is not None and _is_disabled_type(valobj.GetType()):
+        return DisabledTypeSyntheticProvider(valobj, _dict)
+    return object.__new__(cls)
+
+
+# Whichever category LLDB finds first, our providers leave the types of
+# disable-types to LLDB's default formatting, see _is_disabled_type
+for _name, _provider in list(globals().items()):
+    if _name.startswith("DisabledType"):
+        continue
+    if _name.endswith("SummaryProvider") and callable(_provider):
+        globals()[_name] = _disabled_types_aside(_provider)
+    elif _name.endswith("SyntheticProvider") and isinstance(_provider, type):
+        _provider.__new__ = staticmethod(_new_synthetic_provider)
+
+
+def __lldb_init_module(debugger: lldb.SBDebugger, _internal_dict):
+    """
+    This function is called by LLDB when the module is loaded.
+    It registers all the type summaries and synthetic providers.
+    """
+    # Create rust-std before the third-party categories, so theirs are
+    # searched first on load; the generic struct and enum recognizers skip
+    # third-party types either way
+    category = _category(debugger)
+
+    # Register the providers that depend on settings: those of enabled
+    # third-party crates and the show-addresses pointer summaries
+    _apply_thirdparty(debugger)
+    _apply_show_addresses(debugger)
+
+    # Register tuple summary and synthetic providers
+    summary_options = lldb.SBTypeNameSpecifier(
+        TUPLE_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.TupleSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        TUPLE_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.TupleSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register Option<T> summary provider
+    option_summary_options = lldb.SBTypeNameSpecifier(
+        "^core::option::Option<.+>$", lldb.eFormatterMatchRegex
+    )
+    option_summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.OptionSummaryProvider"
+    )
+    option_summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(option_summary_options, option_summary)
+
+    # Register Option<T> synthetic provider
+    option_synth_options = lldb.SBTypeNameSpecifier(
+        "^core::option::Option<.+>$", lldb.eFormatterMatchRegex
+    )
+    option_synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.OptionSyntheticProvider"
+    )
+    option_synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(option_synth_options, option_synth)
+
+    # Register NonZero summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^core::num::nonzero::NonZero(<.+>|U8|U16|U32|U64|U128|Usize|I8|I16|I32|I64|I128|Isize)$",
+        lldb.eFormatterMatchRegex,
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.NonZeroSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register char summary provider, not for pointers to char
+    summary_options = lldb.SBTypeNameSpecifier("char", lldb.eFormatterMatchExact)
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.CharSummaryProvider"
+    )
+    summary.SetOptions(
+        lldb.eTypeOptionCascade
+        | lldb.eTypeOptionSkipPointers
+        | lldb.eTypeOptionSkipReferences
+    )
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register IP and socket address summary providers
+    for type_name, provider in (
+        ("Ipv4Addr", "Ipv4AddrSummaryProvider"),
+        ("Ipv6Addr", "Ipv6AddrSummaryProvider"),
+        ("SocketAddrV4", "SocketAddrV4SummaryProvider"),
+        ("SocketAddrV6", "SocketAddrV6SummaryProvider"),
+    ):
+        summary_options = lldb.SBTypeNameSpecifier(
+            "^(core|std)::net::([a-z_]+::)?%s$" % type_name,
+            lldb.eFormatterMatchRegex,
+        )
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.%s" % provider
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(summary_options, summary)
+
+    # Register Duration, Instant and SystemTime summary providers, and the
+    # synthetic providers hiding the timespec of the latter two
+    summary_options = lldb.SBTypeNameSpecifier(
+        "core::time::Duration", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.StdDurationSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    summary_options = lldb.SBTypeNameSpecifier(
+        "std::time::Instant", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.InstantSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        "std::time::Instant", lldb.eFormatterMatchExact
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.TimespecSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    summary_options = lldb.SBTypeNameSpecifier(
+        "std::time::SystemTime", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.SystemTimeSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        "std::time::SystemTime", lldb.eFormatterMatchExact
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.TimespecSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register range summary providers
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^core::ops::range::Range(Inclusive|From|To|ToInclusive)?<.+>$",
+        lldb.eFormatterMatchRegex,
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.RangeSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    summary_options = lldb.SBTypeNameSpecifier(
+        "core::ops::range::RangeFull", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.RangeSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^core::ops::range::Bound<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.BoundSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register Cow providers
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::borrow::Cow<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.CowSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::borrow::Cow<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.EnumSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register trait object providers
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^(alloc::boxed::Box<dyn .+>|&(mut )?dyn .+)$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.DynSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^(alloc::boxed::Box<dyn .+>|&(mut )?dyn .+)$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.DynSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register io::Error summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "std::io::error::Error", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.IoErrorSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register ExitStatus and the number and UTF-8 error summary providers
+    for pattern, provider in (
+        ("^std::process::ExitStatus$", "ExitStatusSummaryProvider"),
+        (
+            "^core::num::([a-z0-9_]+::)*(TryFromIntError|ParseIntError"
+            "|ParseFloatError)$",
+            "NumErrorSummaryProvider",
+        ),
+        ("^core::str::([a-z0-9_]+::)*Utf8Error$", "Utf8ErrorSummaryProvider"),
+    ):
+        summary_options = lldb.SBTypeNameSpecifier(pattern, lldb.eFormatterMatchRegex)
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.%s" % provider
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(summary_options, summary)
+
+    # Register Vec<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::vec::Vec<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.VecSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register Vec<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::vec::Vec<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.VecSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-            if self.is_heap:
-                # Get heap pointer address
-                heap = raw.GetChildMemberWithName("heap")
-                if not heap.IsValid():
-                    self.length = 0
-                    return
+    # Register VecDeque<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::vec_deque::VecDeque<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.VecDequeSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-                ptr_wrapper = heap.GetChildMemberWithName("__0")
-                if not ptr_wrapper.IsValid():
-                    self.length = 0
-                    return
+    # Register VecDeque<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::vec_deque::VecDeque<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.VecDequeSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-                pointer = ptr_wrapper.GetChildMemberWithName("pointer")
-                if not pointer.IsValid():
-                    self.length = 0
-                    return
+    # Register Box<[T]> and &[T] summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        SLICE_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.SliceSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-                self.heap_ptr = pointer.GetValueAsUnsigned()
-                if self.heap_ptr == 0:
-                    self.length = 0
-                    return
-            else:
-                # Get inline storage address
-                # Navigate to the inline array: raw.inline.value.value.value
-                inline = raw.GetChildMemberWithName("inline")
-                if not inline.IsValid():
-                    self.length = 0
-                    return
+    # Register Box<[T]> and &[T] synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        SLICE_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.SliceSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-                value1 = inline.GetChildMemberWithName("value")
-                if not value1.IsValid():
-                    self.length = 0
-                    return
+    # Register [T; N] summary and synthetic providers, not for references to
+    # arrays, which show the address like other pointers
+    summary_options = lldb.SBTypeNameSpecifier(
+        ARRAY_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.ArraySummaryProvider"
+    )
+    summary.SetOptions(
+        lldb.eTypeOptionCascade
+        | lldb.eTypeOptionSkipPointers
+        | lldb.eTypeOptionSkipReferences
+    )
+    category.AddTypeSummary(summary_options, summary)
 
-                value2 = value1.GetChildMemberWithName("value")
-                if not value2.IsValid():
-                    self.length = 0
-                    return
+    synth_options = lldb.SBTypeNameSpecifier(
+        ARRAY_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.ArraySyntheticProvider"
+    )
+    synth.SetOptions(
+        lldb.eTypeOptionCascade
+        | lldb.eTypeOptionSkipPointers
+        | lldb.eTypeOptionSkipReferences
+    )
+    category.AddTypeSynthetic(synth_options, synth)
 
-                value_array = value2.GetChildMemberWithName("value")
-                if not value_array.IsValid():
-                    self.length = 0
-                    return
+    # Register iterator summary providers
+    for pattern, provider in (
+        (r"^core::slice::iter::Iter(Mut)?<.+>$", "SliceIterSummaryProvider"),
+        (r"^alloc::vec::into_iter::IntoIter<.+>$", "VecIntoIterSummaryProvider"),
+        (
+            r"^std::collections::hash::map::(Iter|IterMut|Keys|Values|IntoIter)<.+>$",
+            "HashMapIterSummaryProvider",
+        ),
+    ):
+        summary_options = lldb.SBTypeNameSpecifier(pattern, lldb.eFormatterMatchRegex)
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.%s" % provider
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(summary_options, summary)
+
+    # Register String summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "alloc::string::String", lldb.eFormatterMatchExact
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.StringSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-                # Get the address of the inline array
-                self.inline_data_address = value_array.GetLoadAddress()
-                if self.inline_data_address == 0:
-                    self.length = 0
-                    return
+    # Register BTreeMap<K, V> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::btree::map::BTreeMap<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.BTreeMapSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-        except Exception as e:
-            self.length = 0
-            self.is_heap = False
-            self.heap_ptr = 0
-            self.inline_data_address = 0
+    # Register BTreeMap<K, V> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::btree::map::BTreeMap<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.BTreeMapSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-    def has_children(self):
-        return self.length > 0
+    # Register BTreeSet<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::btree::set::BTreeSet<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.BTreeSetSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
+    # Register BTreeSet<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::collections::btree::set::BTreeSet<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.BTreeSetSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-def OptionSummaryProvider(valobj: SBValue, _dict) -> str:
-    """
-    Summary provider for core::option::Option<T>
+    # Register HashMap<K, V> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^std::collections::hash::map::HashMap<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.HashMapSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-    Formats Option<T> as "None" or "Some(value)"
+    # Register HashMap<K, V> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^std::collections::hash::map::HashMap<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.HashMapSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-    Args:
-        valobj: The Option<T> value to format
-        _dict: LLDB internal bookkeeping parameter
+    # Register HashSet<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^std::collections::hash::set::HashSet<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.HashSetSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-    Returns:
-        A string representation like "None" or "Some(1.3)"
-    """
-    valobj = valobj.GetNonSyntheticValue()
+    # Register HashSet<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^std::collections::hash::set::HashSet<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.HashSetSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-    # Option is an enum with variants None and Some(T)
-    # Check for $variants$ structure (LLDB's enum encoding)
-    variants = valobj.GetChildMemberWithName("$variants$")
-    if not variants.IsValid():
-        # Try alternative: check for direct variant fields
-        # Some Rust versions encode it differently
-        none_variant = valobj.GetChildMemberWithName("None")
-        some_variant = valobj.GetChildMemberWithName("Some")
-
-        if none_variant.IsValid():
-            return "None"
-        elif some_variant.IsValid():
-            # Get the inner value
-            inner = some_variant.GetChildAtIndex(0)
-            if inner.IsValid():
-                return "Some(%s)" % inner.GetValue()
-            return "Some(...)"
-
-        # Fallback: try to get summary from first child
-        if valobj.GetNumChildren() > 0:
-            first_child = valobj.GetChildAtIndex(0)
-            if first_child.IsValid():
-                return first_child.GetSummary() or str(first_child.GetValue() or "")
-
-        return "<Option>"
-
-    # Standard discriminant-based enum
-    # Look for discriminant in various possible locations
-    discr_field = None
-
-    # Try $discr$ at the variants level
-    discr_field = variants.GetChildMemberWithName("$discr$")
-
-    # Try looking in variant structures
-    if not discr_field or not discr_field.IsValid():
-        for i in range(variants.GetNumChildren()):
-            child = variants.GetChildAtIndex(i)
-            discr_candidate = child.GetChildMemberWithName("$discr$")
-            if discr_candidate and discr_candidate.IsValid():
-                discr_field = discr_candidate
-                break
+    # Register Rc<T> and Arc<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^alloc::(rc::Rc|sync::Arc)<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.RefCountedSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-    if not discr_field or not discr_field.IsValid():
-        # Try direct variant access
-        variant0 = variants.GetChildMemberWithName("$variant$0")
-        variant1 = variants.GetChildMemberWithName("$variant$1")
-        variant_unnamed = variants.GetChildMemberWithName("$variant$")
-
-        # Check for niche-optimized Option (e.g., Option<String>)
-        # These use $variant$ (unnamed) for Some and $variant$0 for discriminant
-        if variant0 and variant0.IsValid():
-            discr = variant0.GetChildMemberWithName("$discr$")
-            if discr and discr.IsValid():
-                discr_val = discr.GetValueAsUnsigned()
-                if discr_val == 0:
-                    return "None"
-                elif variant_unnamed and variant_unnamed.IsValid():
-                    # Some variant in unnamed variant
-                    value = variant_unnamed.GetChildMemberWithName("value")
-                    if value and value.IsValid():
-                        inner = value.GetChildAtIndex(0)
-                        if inner.IsValid():
-                            inner_val = inner.GetValue()
-                            if inner_val:
-                                return "Some(%s)" % inner_val
-                            # Try getting the summary instead
-                            inner_summary = inner.GetSummary()
-                            if inner_summary:
-                                return "Some(%s)" % inner_summary
-                            return "Some(...)"
-
-        if variant1 and variant1.IsValid():
-            # Likely Some variant
-            value = variant1.GetChildMemberWithName("value")
-            if value and value.IsValid():
-                inner = value.GetChildAtIndex(0)
-                if inner.IsValid():
-                    inner_val = inner.GetValue()
-                    if inner_val:
-                        return "Some(%s)" % inner_val
-                    # Try getting the summary instead
-                    inner_summary = inner.GetSummary()
-                    if inner_summary:
-                        return "Some(%s)" % inner_summary
-                    return "Some(...)"
-
-        return "<Option>"
-
-    discriminant = discr_field.GetValueAsUnsigned()
-
-    # Check for niche optimization: discriminant with high bit set often indicates None
-    # For 64-bit: 0x8000000000000000, for 32-bit: 0x80000000
-    is_niche_none = discriminant >= 0x8000000000000000
-
-    # Discriminant 0 is typically None for simple types
-    # For niche-optimized types, high bit set means None
-    if discriminant == 0 or is_niche_none:
-        return "None"
-    else:
-        # For niche-optimized types, check $variant$ (unnamed)
-        variant_unnamed = variants.GetChildMemberWithName("$variant$")
-        if variant_unnamed and variant_unnamed.IsValid():
-            value = variant_unnamed.GetChildMemberWithName("value")
-            if value and value.IsValid():
-                inner = value.GetChildAtIndex(0)
-                if inner.IsValid():
-                    inner_val = inner.GetValue()
-                    if inner_val:
-                        return "Some(%s)" % inner_val
-                    inner_summary = inner.GetSummary()
-                    if inner_summary:
-                        return "Some(%s)" % inner_summary
-                    return "Some(...)"
-
-        # Get the Some variant value from $variant$1
-        variant1 = variants.GetChildMemberWithName("$variant$1")
-        if not variant1 or not variant1.IsValid():
-            variant1 = variants.GetChildMemberWithName("Some")
-
-        if variant1 and variant1.IsValid():
-            # The value is typically in a field named "value" or "__0"
-            value = variant1.GetChildMemberWithName("value")
-            if not value or not value.IsValid():
-                value = variant1.GetChildMemberWithName("__0")
-
-            if value and value.IsValid():
-                # Get the actual inner value
-                inner_val = value.GetValue()
-                if inner_val:
-                    return "Some(%s)" % inner_val
-
-                # Try summary if value is not directly available
-                inner_summary = value.GetSummary()
-                if inner_summary:
-                    return "Some(%s)" % inner_summary
-
-                # For complex types, just indicate Some
-                return "Some(...)"
-
-        return "Some(...)"
+    # Register Rc<T> and Arc<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^alloc::(rc::Rc|sync::Arc)<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.RefCountedSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-    return "<Option>"
+    # Register RefCell<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^core::cell::RefCell<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.RefCellSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
+    # Register RefCell<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        "^core::cell::RefCell<.+>$", lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.RefCellSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-def __lldb_init_module(debugger: lldb.SBDebugger, _internal_dict):
-    """
-    This function is called by LLDB when the module is loaded.
-    It registers all the type summaries and synthetic providers.
-    """
-    # Get or create the "rust" category
-    # The Rust toolchain already creates this category, so we'll add to it
-    category_name = "rust"
-    category = debugger.GetCategory(category_name)
+    # Register Cell<T> and UnsafeCell<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        CELL_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.CellSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-    if not category.IsValid():
-        # If for some reason the category doesn't exist, create it
-        category = debugger.CreateCategory(category_name)
-        category.SetEnabled(True)
+    # Register Cell<T> and UnsafeCell<T> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        CELL_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.CellSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register NonNull<T> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        "^core::ptr::non_null::NonNull<.+>$", lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.NonNullSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
 
-    # Register SmolStr summary provider
+    # Register OnceLock<T> and LazyLock<T, F> summary provider
     summary_options = lldb.SBTypeNameSpecifier(
-        "smol_str::SmolStr", lldb.eFormatterMatchExact
+        ONCE_TYPE_PATTERN, lldb.eFormatterMatchRegex
     )
     summary = lldb.SBTypeSummary.CreateWithFunctionName(
-        "rust_bonus_types.SmolStrSummaryProvider"
+        "rust_bonus_types.OnceSummaryProvider"
     )
     summary.SetOptions(lldb.eTypeOptionCascade)
     category.AddTypeSummary(summary_options, summary)
 
-    # Register SmolStr synthetic provider
+    # Register OnceLock<T> and LazyLock<T, F> synthetic provider
     synth_options = lldb.SBTypeNameSpecifier(
-        "smol_str::SmolStr", lldb.eFormatterMatchExact
+        ONCE_TYPE_PATTERN, lldb.eFormatterMatchRegex
     )
     synth = lldb.SBTypeSynthetic.CreateWithClassName(
-        "rust_bonus_types.SmolStrSyntheticProvider"
+        "rust_bonus_types.OnceSyntheticProvider"
     )
     synth.SetOptions(lldb.eTypeOptionCascade)
     category.AddTypeSynthetic(synth_options, synth)
 
-    # Register SmallVec summary provider
+    # Register Mutex<T> and RwLock<T> summary provider
     summary_options = lldb.SBTypeNameSpecifier(
-        "^smallvec::SmallVec<.+>$", lldb.eFormatterMatchRegex
+        LOCK_TYPE_PATTERN, lldb.eFormatterMatchRegex
     )
     summary = lldb.SBTypeSummary.CreateWithFunctionName(
-        "rust_bonus_types.SmallVecSummaryProvider"
+        "rust_bonus_types.LockSummaryProvider"
     )
     summary.SetOptions(lldb.eTypeOptionCascade)
     category.AddTypeSummary(summary_options, summary)
 
-    # Register SmallVec synthetic provider
+    # Register Mutex<T> and RwLock<T> synthetic provider
     synth_options = lldb.SBTypeNameSpecifier(
-        "^smallvec::SmallVec<.+>$", lldb.eFormatterMatchRegex
+        LOCK_TYPE_PATTERN, lldb.eFormatterMatchRegex
     )
     synth = lldb.SBTypeSynthetic.CreateWithClassName(
-        "rust_bonus_types.SmallVecSyntheticProvider"
+        "rust_bonus_types.LockSyntheticProvider"
     )
     synth.SetOptions(lldb.eTypeOptionCascade)
     category.AddTypeSynthetic(synth_options, synth)
 
-    # Register Option<T> summary provider
-    option_summary_options = lldb.SBTypeNameSpecifier(
-        "^core::option::Option<.+>$", lldb.eFormatterMatchRegex
+    # Register &str and Box<str> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        STR_TYPE_PATTERN, lldb.eFormatterMatchRegex
     )
-    option_summary = lldb.SBTypeSummary.CreateWithFunctionName(
-        "rust_bonus_types.OptionSummaryProvider"
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.StrSummaryProvider"
     )
-    option_summary.SetOptions(lldb.eTypeOptionCascade)
-    category.AddTypeSummary(option_summary_options, option_summary)
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register Pin<P> summary provider
+    summary_options = lldb.SBTypeNameSpecifier(
+        PIN_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.PinSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    # Register Pin<P> synthetic provider
+    synth_options = lldb.SBTypeNameSpecifier(
+        PIN_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.PinSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
+
+    # Register async state machine providers
+    summary_options = lldb.SBTypeNameSpecifier(
+        ASYNC_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    summary = lldb.SBTypeSummary.CreateWithFunctionName(
+        "rust_bonus_types.AsyncSummaryProvider"
+    )
+    summary.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSummary(summary_options, summary)
+
+    synth_options = lldb.SBTypeNameSpecifier(
+        ASYNC_TYPE_PATTERN, lldb.eFormatterMatchRegex
+    )
+    synth = lldb.SBTypeSynthetic.CreateWithClassName(
+        "rust_bonus_types.AsyncSyntheticProvider"
+    )
+    synth.SetOptions(lldb.eTypeOptionCascade)
+    category.AddTypeSynthetic(synth_options, synth)
 
-    print("✓ Rust bonus types loaded: SmolStr, SmallVec, Option")
+    # Register the generic enum providers, for enums and for references to
+    # them. They match by type recognizer, which LLDB only checks after the
+    # exact and regex matches of the same category, so Option and friends
+    # keep their own providers; the other categories may be searched after
+    # this one, so the recognizers skip third-party types themselves. Older
+    # LLDB versions without recognizer support keep their default enum
+    # rendering.
+    if hasattr(lldb, "eFormatterMatchCallback"):
+        enum_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.is_rust_enum", lldb.eFormatterMatchCallback
+        )
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.EnumSummaryProvider"
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(enum_options, summary)
+
+        enum_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.is_rust_enum", lldb.eFormatterMatchCallback
+        )
+        synth = lldb.SBTypeSynthetic.CreateWithClassName(
+            "rust_bonus_types.EnumSyntheticProvider"
+        )
+        synth.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSynthetic(enum_options, synth)
+
+        enum_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.is_rust_enum_ref", lldb.eFormatterMatchCallback
+        )
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.EnumRefSummaryProvider"
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(enum_options, summary)
+
+        enum_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.is_rust_enum_ref", lldb.eFormatterMatchCallback
+        )
+        synth = lldb.SBTypeSynthetic.CreateWithClassName(
+            "rust_bonus_types.EnumRefSyntheticProvider"
+        )
+        synth.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSynthetic(enum_options, synth)
+
+    # Register the generic struct summary provider. Like the enum providers
+    # it's a type recognizer, so it applies to structs no other provider of
+    # rust-std matched and that aren't third-party types; the fields stay
+    # LLDB's regular children, except for PhantomData markers hidden by the
+    # struct synthetic provider.
+    if hasattr(lldb, "eFormatterMatchCallback"):
+        struct_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.is_rust_struct", lldb.eFormatterMatchCallback
+        )
+        summary = lldb.SBTypeSummary.CreateWithFunctionName(
+            "rust_bonus_types.StructSummaryProvider"
+        )
+        summary.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSummary(struct_options, summary)
+
+        struct_options = lldb.SBTypeNameSpecifier(
+            "rust_bonus_types.has_phantom_fields", lldb.eFormatterMatchCallback
+        )
+        synth = lldb.SBTypeSynthetic.CreateWithClassName(
+            "rust_bonus_types.StructSyntheticProvider"
+        )
+        synth.SetOptions(lldb.eTypeOptionCascade)
+        category.AddTypeSynthetic(struct_options, synth)
+
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterSetCommand "
+        "rust-formatter-set"
+    )
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterSettingsCommand "
+        "rust-formatter-settings"
+    )
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterDebugCommand "
+        "rust-formatter-debug"
+    )
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterSelftestCommand "
+        "rust-formatter-selftest"
+    )
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterJsonCommand "
+        "rust-formatter-json"
+    )
+    debugger.HandleCommand(
+        "command script add -f rust_bonus_types.RustFormatterVersionCommand "
+        "rust-formatter-version"
+    )
+
+    print(
+        "✓ Rust bonus types loaded: "
+        "SmolStr, CompactString, EcoString, ArrayString, ArrayVec, TinyVec, "
+        "BString, SmallVec, "
+        "BitVec, IndexMap, IndexSet, hashbrown, ahash, chrono, glam, nalgebra, "
+        "Ratio, Option, "
+        "NonZero, char, Vec, VecDeque, slices, arrays, iterators, String, &str, "
+        "Box<str>, tuples, BTreeMap, BTreeSet, HashMap, HashSet, Rc, Arc, Cell, "
+        "RefCell, NonNull, Mutex, RwLock, OnceLock, LazyLock, OnceCell, LazyCell, "
+        "once_cell, IP/socket addresses, ranges, Duration, Instant, SystemTime, "
+        "Cow, trait objects, io::Error, anyhow::Error, ExitStatus, number and "
+        "UTF-8 errors, Pin, async fns, enums, structs"
+    )